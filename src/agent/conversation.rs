@@ -4,11 +4,144 @@
 
 use std::collections::VecDeque;
 
-use crate::core::Message;
+use crate::core::tokens::TokenCache;
+use crate::core::{Message, Result, ToolCall};
+use crate::llm::{GenerateOptions, LLMProvider, TokenUsage};
 
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Append a suffix to a path's file name (e.g. `session.json` -> `session.json.bak`)
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Path of the backup kept for a session file
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+/// Salvage complete messages from a damaged session file
+///
+/// Parses message objects one by one from the `messages` array, stopping at
+/// the first one that is incomplete. Falls back to treating the content as
+/// line-delimited JSON with one message per line.
+fn salvage_messages(content: &str) -> Vec<Message> {
+    let mut messages = Vec::new();
+
+    if let Some(start) = content
+        .find("\"messages\"")
+        .and_then(|i| content[i..].find('[').map(|j| i + j + 1))
+    {
+        let mut rest = &content[start..];
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+            if rest.is_empty() || rest.starts_with(']') {
+                break;
+            }
+
+            let mut stream = serde_json::Deserializer::from_str(rest).into_iter::<Message>();
+            match stream.next() {
+                Some(Ok(message)) => {
+                    messages.push(message);
+                    rest = &rest[stream.byte_offset()..];
+                }
+                _ => break,
+            }
+        }
+    }
+
+    if messages.is_empty() {
+        messages = content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Message>(line.trim()).ok())
+            .collect();
+    }
+
+    messages
+}
+
+/// Write `content` to `path` atomically
+///
+/// The content is written and synced to a temp file in the same directory,
+/// then renamed over the target, so a crash mid-write never leaves a
+/// half-written session behind. The previous version is kept as `<path>.bak`.
+fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let tmp_path = with_suffix(path, ".tmp");
+
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    if path.exists() {
+        fs::copy(path, backup_path(path))?;
+    }
+
+    fs::rename(&tmp_path, path)
+}
+
+/// A named session saved under `.praxis/sessions`
+#[derive(Debug, Clone)]
+pub struct SessionInfo {
+    /// Session name (file stem)
+    pub name: String,
+    /// Number of messages in the session
+    pub messages: usize,
+    /// When the session file was last written
+    pub modified: Option<std::time::SystemTime>,
+}
+
+/// Path of a named session file in `dir`, rejecting names that aren't plain
+pub fn session_path(dir: &Path, name: &str) -> Option<PathBuf> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.starts_with('.');
+
+    valid.then(|| dir.join(format!("{}.json", name)))
+}
+
+/// List the named sessions in `dir`, most recently modified first
+pub fn list_sessions(dir: &Path) -> std::io::Result<Vec<SessionInfo>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let messages = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<Conversation>(&c).ok())
+            .map(|c| c.len())
+            .unwrap_or(0);
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+        sessions.push(SessionInfo {
+            name: name.to_string(),
+            messages,
+            modified,
+        });
+    }
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.modified));
+    Ok(sessions)
+}
 
 /// Manages conversation history
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,11 +150,21 @@ pub struct Conversation {
     messages: VecDeque<Message>,
     /// Maximum history length
     max_length: usize,
-    /// System prompt (always first)
+    /// Instructions sent before the history (system, developer, ...), in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    prelude: Vec<Message>,
+    /// System prompt from sessions saved before `prelude` existed
+    #[serde(default, skip_serializing)]
     system_prompt: Option<String>,
+    /// Condensed summary of messages that were summarized away
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
     /// Path for per-project persistence
     #[serde(skip)]
     persistence_path: Option<PathBuf>,
+    /// Cached per-message token estimates
+    #[serde(skip)]
+    token_cache: TokenCache,
 }
 
 impl Conversation {
@@ -30,8 +173,11 @@ impl Conversation {
         Self {
             messages: VecDeque::new(),
             max_length,
+            prelude: Vec::new(),
             system_prompt: None,
+            summary: None,
             persistence_path: None,
+            token_cache: TokenCache::new(),
         }
     }
 
@@ -47,6 +193,21 @@ impl Conversation {
         Ok(())
     }
 
+    /// Persist to `path` from now on without loading its contents
+    pub fn set_persistence_path(&mut self, path: PathBuf) {
+        self.persistence_path = Some(path);
+    }
+
+    /// Get the file this conversation persists to, if any
+    pub fn persistence_path(&self) -> Option<&Path> {
+        self.persistence_path.as_deref()
+    }
+
+    /// Write the conversation to its persistence file now
+    pub fn flush(&self) {
+        self.save();
+    }
+
     /// Load conversation history from a file
     pub fn load(&mut self, path: &PathBuf) -> std::io::Result<()> {
         let content = fs::read_to_string(path)?;
@@ -56,19 +217,72 @@ impl Conversation {
 
         match serde_json::from_str::<Conversation>(&content) {
             Ok(loaded) => {
-                self.messages = loaded.messages;
-                self.max_length = loaded.max_length;
-                self.system_prompt = loaded.system_prompt;
+                self.apply(loaded);
                 Ok(())
             }
             Err(e) => {
-                eprintln!("Warning: Failed to parse session file: {}", e);
-                // Don't fail completely, just start fresh but keep the path for future saves
+                self.recover(path, &content, &e);
                 Ok(())
             }
         }
     }
 
+    /// Replace this conversation's state with a loaded one
+    fn apply(&mut self, loaded: Conversation) {
+        self.messages = loaded.messages;
+        self.max_length = loaded.max_length;
+        self.prelude = loaded.prelude;
+        if let Some(prompt) = loaded.system_prompt {
+            self.replace_system_prelude(prompt);
+        }
+        self.summary = loaded.summary;
+    }
+
+    /// Recover as much history as possible from a corrupt session file
+    ///
+    /// The corrupt file is preserved as `<path>.corrupt`, then the `.bak`
+    /// copy and a best-effort salvage of complete messages are tried in
+    /// that order. Only if both fail does the session start fresh.
+    fn recover(&mut self, path: &Path, content: &str, error: &serde_json::Error) {
+        warn!(path = %path.display(), %error, "Session file is corrupt");
+
+        // Move the corrupt file aside so the next save doesn't rotate it into `.bak`
+        let corrupt_path = with_suffix(path, ".corrupt");
+        match fs::rename(path, &corrupt_path) {
+            Ok(()) => info!(path = %corrupt_path.display(), "Saved the corrupt session file"),
+            Err(e) => warn!(error = %e, "Could not back up the corrupt session file"),
+        }
+
+        let backup = backup_path(path);
+        if let Some(loaded) = fs::read_to_string(&backup)
+            .ok()
+            .and_then(|c| serde_json::from_str::<Conversation>(&c).ok())
+        {
+            info!(
+                messages = loaded.messages.len(),
+                backup = %backup.display(),
+                "Restored session from backup"
+            );
+            self.apply(loaded);
+            return;
+        }
+
+        let salvaged = salvage_messages(content);
+        if !salvaged.is_empty() {
+            info!(
+                messages = salvaged.len(),
+                "Recovered messages from the readable part of the session file"
+            );
+            self.messages = salvaged.into_iter().collect();
+            while self.messages.len() > self.max_length {
+                self.messages.pop_front();
+            }
+            return;
+        }
+
+        warn!("No session history could be recovered; starting a fresh session");
+    }
+
     /// Save conversation history to file
     fn save(&self) {
         if let Some(ref path) = self.persistence_path {
@@ -79,21 +293,42 @@ impl Conversation {
 
             match serde_json::to_string_pretty(self) {
                 Ok(content) => {
-                    if let Err(e) = fs::write(path, content) {
-                        eprintln!("Warning: Failed to save session: {}", e);
+                    if let Err(e) = write_atomic(path, &content) {
+                        warn!(error = %e, "Failed to save session");
                     }
                 }
-                Err(e) => eprintln!("Warning: Failed to serialize session: {}", e),
+                Err(e) => warn!(error = %e, "Failed to serialize session"),
             }
         }
     }
 
     /// Set the system prompt
+    ///
+    /// Replaces the first `system` prelude message, or adds one at the front.
     pub fn set_system_prompt(&mut self, prompt: impl Into<String>) {
-        self.system_prompt = Some(prompt.into());
+        self.replace_system_prelude(prompt.into());
         self.save();
     }
 
+    fn replace_system_prelude(&mut self, prompt: String) {
+        match self.prelude.iter_mut().find(|m| m.role == "system") {
+            Some(message) => message.content = prompt,
+            None => self.prelude.insert(0, Message::system(prompt)),
+        }
+    }
+
+    /// Add an instruction message (e.g. role `system` or `developer`) to
+    /// send before the history, after any existing ones
+    pub fn add_prelude(&mut self, role: impl Into<String>, content: impl Into<String>) {
+        self.prelude.push(Message::new(role, content));
+        self.save();
+    }
+
+    /// Get the prelude messages, in order
+    pub fn prelude(&self) -> &[Message] {
+        &self.prelude
+    }
+
     /// Add a user message
     pub fn add_user(&mut self, content: impl Into<String>) {
         self.add_message(Message::user(content));
@@ -104,6 +339,16 @@ impl Conversation {
         self.add_message(Message::assistant(content));
     }
 
+    /// Add an assistant message that called tools
+    pub fn add_assistant_with_tools(&mut self, content: impl Into<String>, calls: Vec<ToolCall>) {
+        self.add_message(Message::assistant_with_tools(content, calls));
+    }
+
+    /// Add the output of a tool call
+    pub fn add_tool_result(&mut self, name: impl Into<String>, output: impl Into<String>) {
+        self.add_message(Message::tool(name, output));
+    }
+
     /// Add a message and maintain size limit
     fn add_message(&mut self, message: Message) {
         self.messages.push_back(message);
@@ -112,17 +357,92 @@ impl Conversation {
         while self.messages.len() > self.max_length {
             self.messages.pop_front();
         }
+        self.drop_orphaned_tool_results();
 
         self.save();
     }
 
-    /// Get all messages including system prompt
-    pub fn get_messages(&self) -> Vec<Message> {
-        let mut result = Vec::new();
+    /// Remove tool results at the front whose calling message is gone
+    fn drop_orphaned_tool_results(&mut self) {
+        while self.messages.front().is_some_and(|m| m.role == "tool") {
+            self.messages.pop_front();
+        }
+    }
+
+    /// Condense the oldest `count` messages into the running summary
+    ///
+    /// The executor `model` folds them into any existing summary, which is
+    /// then kept at the front of the history in place of those messages.
+    /// Returns the token usage of the summarization call, if reported.
+    pub async fn summarize_oldest(
+        &mut self,
+        llm: &dyn LLMProvider,
+        model: &str,
+        count: usize,
+    ) -> Result<Option<TokenUsage>> {
+        let mut count = count.min(self.messages.len());
+        if count == 0 {
+            return Ok(None);
+        }
+        // Summarize tool results together with the call that produced them
+        while self.messages.get(count).is_some_and(|m| m.role == "tool") {
+            count += 1;
+        }
+
+        let mut prompt = String::from(
+            "Summarize this conversation so it can be continued later. Keep facts, \
+             decisions, file names and open questions; drop pleasantries.\n\n",
+        );
+        if let Some(ref summary) = self.summary {
+            prompt.push_str(&format!("Summary so far:\n{}\n\n", summary));
+        }
+        prompt.push_str("Messages:\n");
+        for message in self.messages.iter().take(count) {
+            prompt.push_str(&format!("{}: {}\n", message.role, message.content));
+        }
+
+        let response = llm
+            .chat(
+                model,
+                &[Message::user(prompt)],
+                Some(GenerateOptions {
+                    temperature: Some(0.3),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        self.summary = Some(response.content.trim().to_string());
+        self.messages.drain(..count);
+        self.save();
+        Ok(response.usage)
+    }
 
-        if let Some(ref prompt) = self.system_prompt {
-            result.push(Message::system(prompt.clone()));
+    /// Get the summary of earlier, summarized messages
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// The summary as a message, placed after the system prompt
+    fn summary_message(&self) -> Option<Message> {
+        self.summary
+            .as_ref()
+            .map(|s| Message::system(format!("Summary of the earlier conversation:\n{}", s)))
+    }
+
+    /// Remove and return the most recent message
+    pub fn pop_last(&mut self) -> Option<Message> {
+        let message = self.messages.pop_back();
+        if message.is_some() {
+            self.save();
         }
+        message
+    }
+
+    /// Get all messages including the prelude
+    pub fn get_messages(&self) -> Vec<Message> {
+        let mut result = self.prelude.clone();
+        result.extend(self.summary_message());
 
         result.extend(self.messages.iter().cloned());
         result
@@ -171,6 +491,7 @@ impl Conversation {
     /// Clear all history
     pub fn clear(&mut self) {
         self.messages.clear();
+        self.summary = None;
         self.save();
     }
 
@@ -184,24 +505,78 @@ impl Conversation {
         self.messages.is_empty()
     }
 
-    /// Get the context window (System prompt + last N messages)
+    /// Estimate the number of tokens in a message (cached by content)
+    pub fn estimate_tokens(&self, message: &Message) -> usize {
+        self.token_cache.estimate_message(message)
+    }
+
+    /// Estimate the total tokens of the prelude and full history
+    pub fn total_tokens(&self) -> usize {
+        let prelude = self
+            .prelude
+            .iter()
+            .map(|m| self.estimate_tokens(m))
+            .sum::<usize>();
+
+        prelude
+            + self
+                .messages
+                .iter()
+                .map(|m| self.estimate_tokens(m))
+                .sum::<usize>()
+    }
+
+    /// Get the context window (prelude + last N messages)
     ///
     /// This effectively implements the RLM strategy: only the most recent context
     /// is loaded into the model's immediate window. The rest is available via
     /// the `analyze_conversation` tool.
+    ///
+    /// The window never starts on a tool result: it's widened back to the
+    /// assistant message that made the call.
     pub fn get_context_window(&self, window_size: usize) -> Vec<Message> {
-        let mut result = Vec::new();
+        let mut result = self.prelude.clone();
+        result.extend(self.summary_message());
 
-        if let Some(ref prompt) = self.system_prompt {
-            result.push(Message::system(prompt.clone()));
+        let len = self.messages.len();
+        let mut start = len.saturating_sub(window_size);
+        while start > 0 && self.messages[start].role == "tool" {
+            start -= 1;
         }
 
-        let len = self.messages.len();
-        let start = if len > window_size {
-            len - window_size
-        } else {
-            0
-        };
+        result.extend(self.messages.iter().skip(start).cloned());
+
+        result
+    }
+
+    /// Get the context window by estimated tokens: the prelude plus as many
+    /// of the most recent messages as fit in `max_tokens`
+    ///
+    /// The prelude (and any summary) is always kept, even if it alone is
+    /// over budget. The window never starts on a tool result: it's narrowed
+    /// forward past tool results whose call didn't fit.
+    pub fn get_context_window_tokens(&self, max_tokens: usize) -> Vec<Message> {
+        let mut result = self.prelude.clone();
+        result.extend(self.summary_message());
+
+        let mut remaining = max_tokens.saturating_sub(
+            result
+                .iter()
+                .map(|m| self.estimate_tokens(m))
+                .sum::<usize>(),
+        );
+        let mut start = self.messages.len();
+        for message in self.messages.iter().rev() {
+            let tokens = self.estimate_tokens(message);
+            if tokens > remaining {
+                break;
+            }
+            remaining -= tokens;
+            start -= 1;
+        }
+        while start < self.messages.len() && self.messages[start].role == "tool" {
+            start += 1;
+        }
 
         result.extend(self.messages.iter().skip(start).cloned());
 
@@ -253,6 +628,59 @@ mod tests {
         assert_eq!(messages[0].role, "system");
     }
 
+    #[test]
+    fn test_prelude_messages() {
+        let mut conv = Conversation::new(10);
+        conv.add_prelude("developer", "Answer in British English");
+        conv.set_system_prompt("You are a helpful assistant");
+        conv.set_system_prompt("You are a terse assistant");
+        conv.add_user("Hello");
+
+        let roles: Vec<&str> = conv.prelude().iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["system", "developer"]);
+
+        let window = conv.get_context_window(5);
+        assert_eq!(window.len(), 3);
+        assert_eq!(window[0].content, "You are a terse assistant");
+        assert_eq!(window[1].role, "developer");
+
+        // Sessions saved with a plain system prompt still load
+        let old = r#"{"messages": [], "max_length": 10, "system_prompt": "Be brief"}"#;
+        let mut loaded = Conversation::new(10);
+        loaded.apply(serde_json::from_str(old).unwrap());
+        assert_eq!(loaded.get_messages()[0].content, "Be brief");
+    }
+
+    #[test]
+    fn test_context_window_by_tokens() {
+        let mut conv = Conversation::new(10);
+        conv.set_system_prompt("You are a terse assistant");
+        conv.add_user("Hello");
+        conv.add_assistant("Hi.");
+        conv.add_user("Read the log");
+        conv.add_assistant_with_tools(
+            "x".repeat(4000),
+            vec![ToolCall::new(
+                "read_file",
+                serde_json::json!({"path": "log"}),
+            )],
+        );
+        conv.add_tool_result("read_file", "ok");
+        conv.add_assistant("The log is fine.");
+
+        // The call doesn't fit, so its result is left out too
+        let window = conv.get_context_window_tokens(100);
+        let contents: Vec<&str> = window.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec!["You are a terse assistant", "The log is fine."]
+        );
+
+        // The system prompt is kept even when nothing else fits
+        assert_eq!(conv.get_context_window_tokens(0).len(), 1);
+        assert_eq!(conv.get_context_window_tokens(10_000).len(), 7);
+    }
+
     #[test]
     fn test_persistence_save_load() -> std::io::Result<()> {
         let temp_dir = std::env::temp_dir().join("praxis_test");
@@ -328,4 +756,149 @@ mod tests {
         std::fs::remove_file(file_path)?;
         Ok(())
     }
+
+    #[test]
+    fn test_tool_messages_persist_and_stay_grouped() -> std::io::Result<()> {
+        let temp_dir = std::env::temp_dir().join("praxis_test_tool_messages");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let file_path = temp_dir.join("session.json");
+
+        let mut conv = Conversation::new(10);
+        conv.enable_persistence(file_path.clone())?;
+        conv.add_user("What's in Cargo.toml?");
+        conv.add_assistant_with_tools(
+            "",
+            vec![
+                ToolCall::new("read_file", serde_json::json!({"path": "Cargo.toml"})),
+                ToolCall::new("read_file", serde_json::json!({"path": "Cargo.lock"})),
+            ],
+        );
+        conv.add_tool_result("read_file", "[package]");
+        conv.add_tool_result("read_file", "# lockfile");
+        conv.add_assistant("It defines the praxis package.");
+
+        let mut reloaded = Conversation::new(10);
+        reloaded.enable_persistence(file_path)?;
+        assert_eq!(reloaded.len(), 5);
+        assert_eq!(reloaded.messages[1].tool_calls.as_ref().unwrap().len(), 2);
+        assert_eq!(reloaded.messages[2].role, "tool");
+        assert_eq!(reloaded.messages[2].tool_name.as_deref(), Some("read_file"));
+
+        // A window cutting between the call and its results is widened
+        let window = reloaded.get_context_window(2);
+        assert_eq!(window.len(), 4);
+        assert!(window[0].tool_calls.is_some());
+
+        std::fs::remove_dir_all(temp_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_named_sessions() -> std::io::Result<()> {
+        let dir = std::env::temp_dir().join("praxis_test_sessions");
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(session_path(&dir, "../escape").is_none());
+        assert!(session_path(&dir, "").is_none());
+
+        let mut conv = Conversation::new(10);
+        conv.enable_persistence(session_path(&dir, "feature-a").unwrap())?;
+        conv.add_user("one");
+        conv.add_assistant("two");
+
+        let sessions = list_sessions(&dir)?;
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].name, "feature-a");
+        assert_eq!(sessions[0].messages, 2);
+
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_write_keeps_original() -> std::io::Result<()> {
+        let temp_dir = std::env::temp_dir().join("praxis_test_atomic");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir)?;
+        let file_path = temp_dir.join("session.json");
+
+        let mut conv = Conversation::new(10);
+        conv.enable_persistence(file_path.clone())?;
+        conv.add_user("first");
+        conv.add_assistant("second");
+
+        // Simulate a crash after the temp file was partially written but
+        // before it was renamed over the session
+        std::fs::write(with_suffix(&file_path, ".tmp"), "{\"messages\": [")?;
+
+        let mut reloaded = Conversation::new(10);
+        reloaded.enable_persistence(file_path.clone())?;
+        assert_eq!(reloaded.len(), 2);
+
+        // The backup holds the version before the last save
+        let backup = std::fs::read_to_string(backup_path(&file_path))?;
+        assert!(backup.contains("first"));
+        assert!(!backup.contains("second"));
+
+        std::fs::remove_dir_all(temp_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_recovers_truncated_session() -> std::io::Result<()> {
+        let temp_dir = std::env::temp_dir().join("praxis_test_corrupt");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir)?;
+        let file_path = temp_dir.join("session.json");
+
+        let mut original = Conversation::new(10);
+        original.add_user("keep me");
+        original.add_assistant("and me");
+        original.add_user("lost");
+        let json = serde_json::to_string_pretty(&original).unwrap();
+
+        // Cut the file off in the middle of the last message
+        let cut = json.find("lost").unwrap();
+        std::fs::write(&file_path, &json[..cut])?;
+
+        let mut conv = Conversation::new(10);
+        conv.enable_persistence(file_path.clone())?;
+
+        assert_eq!(conv.len(), 2);
+        assert_eq!(conv.last_assistant_message().unwrap().content, "and me");
+        assert!(with_suffix(&file_path, ".corrupt").exists());
+
+        // Saving after recovery writes a clean file in place of the corrupt one
+        conv.add_user("new");
+        let saved = std::fs::read_to_string(&file_path)?;
+        assert!(serde_json::from_str::<Conversation>(&saved).is_ok());
+
+        std::fs::remove_dir_all(temp_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_recovers_corrupt_session_from_backup() -> std::io::Result<()> {
+        let temp_dir = std::env::temp_dir().join("praxis_test_corrupt_bak");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir)?;
+        let file_path = temp_dir.join("session.json");
+
+        let mut original = Conversation::new(10);
+        original.add_user("from backup");
+        std::fs::write(
+            backup_path(&file_path),
+            serde_json::to_string_pretty(&original).unwrap(),
+        )?;
+        std::fs::write(&file_path, "not json at all")?;
+
+        let mut conv = Conversation::new(10);
+        conv.enable_persistence(file_path.clone())?;
+
+        assert_eq!(conv.len(), 1);
+        assert_eq!(conv.last_user_message().unwrap().content, "from backup");
+
+        std::fs::remove_dir_all(temp_dir)?;
+        Ok(())
+    }
 }
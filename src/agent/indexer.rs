@@ -0,0 +1,272 @@
+//! Project indexing
+//!
+//! The "indexer" sub-agent summarizes a project's key files (README,
+//! manifests, entry points) into a short note for the orchestrator's
+//! context. Summaries are cached in `.praxis/index.json` by modification
+//! time, so re-indexing only summarizes files that changed.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::agent::sub_agent::SubAgent;
+use crate::core::{PraxisError, Result};
+
+/// Name of the indexing sub-agent
+pub const INDEXER_NAME: &str = "indexer";
+
+/// System prompt of the indexing sub-agent
+pub const INDEXER_PROMPT: &str = "You summarize source files for a coding agent that \
+will work on the project. Be brief and factual; never invent details.";
+
+/// Files worth summarizing, relative to the project root
+const KEY_FILES: &[&str] = &[
+    "README.md",
+    "README",
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "go.mod",
+    "src/main.rs",
+    "src/lib.rs",
+    "main.py",
+    "src/index.ts",
+    "src/index.js",
+];
+
+/// Most characters of a file shown to the indexer
+const MAX_INDEXED_CHARS: usize = 12_000;
+
+/// Where the index is cached under the project root
+fn index_path(workdir: &Path) -> PathBuf {
+    workdir.join(".praxis").join("index.json")
+}
+
+/// A file's summary and the modification time it was made from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    /// Milliseconds since the Unix epoch
+    modified_ms: u64,
+    summary: String,
+}
+
+/// A key file whose summary is missing or out of date
+#[derive(Debug, Clone)]
+pub struct PendingFile {
+    /// Path relative to the project root
+    pub path: String,
+    modified_ms: u64,
+    contents: String,
+}
+
+impl PendingFile {
+    /// The task given to the indexer for this file
+    pub fn task(&self) -> String {
+        format!(
+            "Summarize `{}` in at most 5 short bullet points: what it's for, key names \
+             (crates, modules, commands, entry points) and anything surprising. \
+             Reply with only the bullets.\n\n```\n{}\n```",
+            self.path, self.contents
+        )
+    }
+}
+
+/// Summaries of a project's key files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectIndex {
+    files: BTreeMap<String, IndexEntry>,
+}
+
+impl ProjectIndex {
+    /// Load the cached index for `workdir`, or an empty one if there is
+    /// none or it can't be read
+    pub fn load(workdir: &Path) -> Self {
+        let path = index_path(workdir);
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(path = %path.display(), error = %e, "Ignoring unreadable project index");
+            Self::default()
+        })
+    }
+
+    /// Write the index to `.praxis/index.json` under `workdir`
+    pub fn save(&self, workdir: &Path) -> Result<()> {
+        let path = index_path(workdir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Forget files that are gone, and list the key files that need a new
+    /// summary
+    pub fn refresh(&mut self, workdir: &Path) -> Vec<PendingFile> {
+        let present: Vec<(&str, u64)> = KEY_FILES
+            .iter()
+            .filter_map(|path| Some((*path, modified_ms(&workdir.join(path))?)))
+            .collect();
+        self.files
+            .retain(|path, _| present.iter().any(|(p, _)| p == path));
+
+        present
+            .into_iter()
+            .filter(|(path, modified_ms)| {
+                self.files
+                    .get(*path)
+                    .is_none_or(|entry| entry.modified_ms != *modified_ms)
+            })
+            .filter_map(|(path, modified_ms)| {
+                let contents = fs::read_to_string(workdir.join(path)).ok()?;
+                let contents = match contents.char_indices().nth(MAX_INDEXED_CHARS) {
+                    Some((end, _)) => format!("{}\n... (truncated)", &contents[..end]),
+                    None => contents,
+                };
+                Some(PendingFile {
+                    path: path.to_string(),
+                    modified_ms,
+                    contents,
+                })
+            })
+            .collect()
+    }
+
+    /// Record the summary of a pending file
+    pub fn insert(&mut self, file: &PendingFile, summary: impl Into<String>) {
+        self.files.insert(
+            file.path.clone(),
+            IndexEntry {
+                modified_ms: file.modified_ms,
+                summary: summary.into().trim().to_string(),
+            },
+        );
+    }
+
+    /// Number of files summarized
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no files are summarized
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// The summaries as a note for the orchestrator's context
+    pub fn note(&self) -> Option<String> {
+        if self.files.is_empty() {
+            return None;
+        }
+        Some(
+            self.files
+                .iter()
+                .map(|(path, entry)| format!("### {}\n{}", path, entry.summary))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        )
+    }
+}
+
+/// What an indexing run did
+#[derive(Debug, Clone)]
+pub struct IndexReport {
+    /// The index after the run
+    pub index: ProjectIndex,
+    /// Files summarized this run; the rest came from the cache
+    pub summarized: usize,
+}
+
+impl std::fmt::Display for IndexReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Indexed {} files ({} summarized, {} unchanged)",
+            self.index.len(),
+            self.summarized,
+            self.index.len() - self.summarized
+        )
+    }
+}
+
+/// Bring the index for `workdir` up to date with `indexer` and save it
+pub async fn index_project(indexer: &SubAgent, workdir: &Path) -> Result<IndexReport> {
+    let mut index = ProjectIndex::load(workdir);
+    let pending = index.refresh(workdir);
+
+    for file in &pending {
+        match indexer.run(&file.task()).await {
+            Ok(summary) => index.insert(file, summary),
+            Err(e) => {
+                // Keep what was summarized so far for next time
+                index.save(workdir)?;
+                return Err(PraxisError::Other(format!(
+                    "Couldn't summarize {}: {}",
+                    file.path, e
+                )));
+            }
+        }
+    }
+
+    index.save(workdir)?;
+    Ok(IndexReport {
+        index,
+        summarized: pending.len(),
+    })
+}
+
+/// Modification time of `path` in milliseconds since the Unix epoch
+fn modified_ms(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let elapsed = modified.duration_since(UNIX_EPOCH).ok()?;
+    u64::try_from(elapsed.as_millis()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_refresh_only_returns_changed_files() {
+        let root = std::env::temp_dir().join("praxis_project_index");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("README.md"), "# Demo").unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+
+        let mut index = ProjectIndex::load(&root);
+        let pending = index.refresh(&root);
+        let paths: Vec<&str> = pending.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["README.md", "src/main.rs"]);
+        assert!(pending[0].task().contains("# Demo"));
+        for file in &pending {
+            index.insert(file, format!("- {}", file.path));
+        }
+        index.save(&root).unwrap();
+
+        // Cached summaries are reused until the file changes
+        let mut index = ProjectIndex::load(&root);
+        assert!(index.refresh(&root).is_empty());
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(root.join("README.md"))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        fs::remove_file(root.join("src/main.rs")).unwrap();
+
+        let pending = index.refresh(&root);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].path, "README.md");
+        assert_eq!(index.note().unwrap(), "### README.md\n- README.md");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
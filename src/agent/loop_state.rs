@@ -2,8 +2,13 @@
 //!
 //! Tracks the state of the ReAct reasoning loop including observations from tool executions.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
+use crate::core::PraxisError;
+
 /// State of the agent reasoning loop
 #[derive(Debug, Clone)]
 pub struct AgentLoopState {
@@ -15,6 +20,53 @@ pub struct AgentLoopState {
     pub observations: Vec<Observation>,
     /// Final answer if the agent has completed reasoning
     pub final_answer: Option<String>,
+    /// Set (possibly from another task) to stop and answer from current observations
+    force_answer: Arc<AtomicBool>,
+    /// Turn on which each observation was added (parallel to `observations`)
+    observation_turns: Vec<usize>,
+    /// How observations are included in prompts
+    policy: ObservationPolicy,
+    /// Number of observations in the most recent batch
+    last_batch_len: usize,
+    /// The latest batch repeated the one before it
+    repeat_detected: bool,
+    /// Screenshots (base64) from the latest batch, shown on the next turn
+    images: Vec<String>,
+}
+
+/// How observations are laid out for the model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ObservationFormat {
+    /// A `### Observation` section per tool call
+    #[default]
+    Markdown,
+    /// A JSON array of `{tool, success, output}` objects
+    Json,
+}
+
+/// Controls how observations are included in later prompts
+#[derive(Debug, Clone)]
+pub struct ObservationPolicy {
+    /// Maximum characters of a failed observation's output to include
+    pub max_failure_chars: usize,
+    /// Replace failed observations with a one-line reference once shown
+    pub collapse_failures: bool,
+    /// Maximum bytes of any observation's output, keeping its head and tail
+    pub max_output_bytes: usize,
+    /// Markdown sections or a JSON array
+    pub format: ObservationFormat,
+}
+
+impl Default for ObservationPolicy {
+    fn default() -> Self {
+        Self {
+            max_failure_chars: 300,
+            collapse_failures: true,
+            max_output_bytes: 8192,
+            format: ObservationFormat::default(),
+        }
+    }
 }
 
 impl AgentLoopState {
@@ -25,34 +77,154 @@ impl AgentLoopState {
             max_turns,
             observations: Vec::new(),
             final_answer: None,
+            force_answer: Arc::new(AtomicBool::new(false)),
+            observation_turns: Vec::new(),
+            policy: ObservationPolicy::default(),
+            last_batch_len: 0,
+            repeat_detected: false,
+            images: Vec::new(),
         }
     }
 
+    /// Use a specific observation inclusion policy
+    pub fn with_observation_policy(mut self, policy: ObservationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Share a "force final answer" flag with the caller
+    pub fn with_force_answer_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.force_answer = flag;
+        self
+    }
+
+    /// Request that the loop stop and answer from current observations
+    pub fn force_answer(&self) {
+        self.force_answer.store(true, Ordering::SeqCst);
+    }
+
+    /// Check if a final answer was forced
+    pub fn answer_forced(&self) -> bool {
+        self.force_answer.load(Ordering::SeqCst)
+    }
+
+    /// Attach images to the next orchestrator turn, replacing earlier ones
+    pub fn set_images(&mut self, images: Vec<String>) {
+        self.images = images;
+    }
+
+    /// Images to attach to the next orchestrator turn
+    pub fn images(&self) -> &[String] {
+        &self.images
+    }
+
     /// Check if the loop should continue
     pub fn should_continue(&self) -> bool {
-        self.turn < self.max_turns && self.final_answer.is_none()
+        self.turn < self.max_turns && self.final_answer.is_none() && !self.answer_forced()
     }
 
     /// Format observations for inclusion in the next prompt
+    ///
+    /// Failed observations are truncated to the policy limit and, once they
+    /// have been shown in a prompt, collapsed to a one-line reference so a
+    /// recurring error doesn't dominate the context. The policy's format
+    /// picks Markdown sections or a JSON array.
     pub fn format_observations(&self) -> String {
         if self.observations.is_empty() {
             return String::new();
         }
 
         let mut output = String::from("\n\n## Tool Observations:\n");
+        let mut entries = Vec::new();
         for (i, obs) in self.observations.iter().enumerate() {
-            output.push_str(&format!(
-                "\n### Observation {} ({})\n{}\n",
-                i + 1,
-                obs.tool_name,
-                obs.output
-            ));
+            let added = self.observation_turns.get(i).copied().unwrap_or(self.turn);
+            let failed = obs.kind != ObservationKind::Success;
+            let collapsed = failed && self.policy.collapse_failures && added + 1 < self.turn;
+
+            let text = if !failed {
+                truncate_middle(&obs.output, self.policy.max_output_bytes)
+            } else if collapsed {
+                format!(
+                    "tool {} {} (see turn {})",
+                    obs.tool_name,
+                    obs.kind.label(),
+                    added + 1
+                )
+            } else {
+                truncate_middle(
+                    &truncate_chars(&obs.output, self.policy.max_failure_chars),
+                    self.policy.max_output_bytes,
+                )
+            };
+
+            match self.policy.format {
+                ObservationFormat::Markdown if failed && !collapsed => {
+                    output.push_str(&format!(
+                        "\n### Observation {} ({}, {})\n{}\n",
+                        i + 1,
+                        obs.tool_name,
+                        obs.kind.label(),
+                        text
+                    ));
+                }
+                ObservationFormat::Markdown => {
+                    output.push_str(&format!(
+                        "\n### Observation {} ({})\n{}\n",
+                        i + 1,
+                        obs.tool_name,
+                        text
+                    ));
+                }
+                ObservationFormat::Json => {
+                    let mut entry = serde_json::json!({
+                        "tool": obs.tool_name,
+                        "success": obs.success,
+                        "output": text,
+                    });
+                    if failed {
+                        entry["kind"] = obs.kind.label().into();
+                    }
+                    entries.push(entry);
+                }
+            }
+        }
+
+        if self.policy.format == ObservationFormat::Json {
+            output.push_str(&serde_json::Value::Array(entries).to_string());
+            output.push('\n');
+        }
+
+        if self.repeat_detected {
+            output.push_str(
+                "\nNOTE: Repeated action detected. Your last tool calls returned exactly \
+                 the same results as the ones before. Don't repeat them; give your final \
+                 answer from the observations above.\n",
+            );
         }
         output
     }
 
+    /// Check if `batch` is identical to the most recently added batch
+    pub fn is_repeat(&self, batch: &[Observation]) -> bool {
+        !batch.is_empty()
+            && batch.len() == self.last_batch_len
+            && self.observations[self.observations.len() - self.last_batch_len..] == *batch
+    }
+
+    /// Record that the latest batch repeated the previous one, instead of
+    /// adding it again
+    pub fn note_repeat(&mut self) {
+        self.repeat_detected = true;
+    }
+
     /// Add observations from a batch of tool executions
     pub fn add_observations(&mut self, observations: Vec<Observation>) {
+        self.last_batch_len = observations.len();
+        self.repeat_detected = false;
+        self.observation_turns
+            .resize(self.observations.len(), self.turn);
+        self.observation_turns
+            .extend(std::iter::repeat_n(self.turn, observations.len()));
         self.observations.extend(observations);
     }
 
@@ -62,13 +234,106 @@ impl AgentLoopState {
     }
 }
 
+/// Truncate `text` to at most `max` characters, marking the cut
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => format!("{}... (truncated)", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Cut the middle out of `text` so it fits in `max_bytes`, marking the gap
+///
+/// The head and tail are kept because snapshots list useful refs near the end.
+pub(crate) fn truncate_middle(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+
+    let mut head_end = max_bytes / 2;
+    while !text.is_char_boundary(head_end) {
+        head_end -= 1;
+    }
+    let mut tail_start = text.len() - (max_bytes - head_end);
+    while !text.is_char_boundary(tail_start) {
+        tail_start += 1;
+    }
+
+    format!(
+        "{}\n...[truncated {} bytes]...\n{}",
+        &text[..head_end],
+        tail_start - head_end,
+        &text[tail_start..]
+    )
+}
+
+/// How a tool execution ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObservationKind {
+    /// The tool ran and did what was asked
+    #[default]
+    Success,
+    /// The tool ran and reported a failure
+    ToolError,
+    /// The tool, or the model behind it, ran out of time; retrying may work
+    Timeout,
+    /// The call itself was wrong (unknown tool, bad arguments) and never ran
+    ValidationError,
+    /// The user or the agent stopped the tool
+    Cancelled,
+}
+
+impl ObservationKind {
+    /// How the kind reads in prompts, e.g. "(read_file, timed out)"
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Success => "ok",
+            Self::ToolError => "failed",
+            Self::Timeout => "timed out",
+            Self::ValidationError => "invalid call",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// The kind of failure an error describes
+    pub fn of_error(error: &PraxisError) -> Self {
+        match error {
+            PraxisError::Cancelled => Self::Cancelled,
+            PraxisError::Http(e) if e.is_timeout() => Self::Timeout,
+            other => Self::of_failure(&other.to_string()),
+        }
+    }
+
+    /// The kind of failure a tool's error message describes
+    ///
+    /// Tools report timeouts and declined confirmations as plain failures,
+    /// so they're recognized by their wording.
+    pub fn of_failure(message: &str) -> Self {
+        let message = message.to_lowercase();
+        if ["timed out", "timeout", "did not finish within"]
+            .iter()
+            .any(|m| message.contains(m))
+        {
+            Self::Timeout
+        } else if message.contains("declined") || message.contains("cancelled") {
+            Self::Cancelled
+        } else {
+            Self::ToolError
+        }
+    }
+}
+
 /// An observation from a tool execution
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Observation {
     /// Name of the tool that produced this observation
     pub tool_name: String,
     /// Whether the tool execution was successful
     pub success: bool,
+    /// How the execution ended, so failures can be told apart
+    #[serde(default)]
+    pub kind: ObservationKind,
     /// Human-readable output from the tool
     pub output: String,
     /// Optional structured data from the tool
@@ -82,21 +347,42 @@ impl Observation {
         Self {
             tool_name: tool_name.into(),
             success: true,
+            kind: ObservationKind::Success,
             output: output.into(),
             data: None,
         }
     }
 
-    /// Create an error observation
+    /// Create an error observation, its kind inferred from the message
     pub fn error(tool_name: impl Into<String>, error: impl Into<String>) -> Self {
+        let error = error.into();
+        Self::failure(tool_name, ObservationKind::of_failure(&error), error)
+    }
+
+    /// Create an observation of a failure of a specific kind
+    pub fn failure(
+        tool_name: impl Into<String>,
+        kind: ObservationKind,
+        error: impl Into<String>,
+    ) -> Self {
         Self {
             tool_name: tool_name.into(),
             success: false,
+            kind,
             output: error.into(),
             data: None,
         }
     }
 
+    /// Create an observation of a tool that returned an error
+    pub fn from_error(tool_name: impl Into<String>, error: &PraxisError) -> Self {
+        Self::failure(
+            tool_name,
+            ObservationKind::of_error(error),
+            error.to_string(),
+        )
+    }
+
     /// Create an observation with structured data
     pub fn with_data(
         tool_name: impl Into<String>,
@@ -106,6 +392,7 @@ impl Observation {
         Self {
             tool_name: tool_name.into(),
             success: true,
+            kind: ObservationKind::Success,
             output: output.into(),
             data: Some(data),
         }
@@ -114,9 +401,15 @@ impl Observation {
 
 impl From<crate::core::ToolResult> for Observation {
     fn from(result: crate::core::ToolResult) -> Self {
+        let kind = if result.success {
+            ObservationKind::Success
+        } else {
+            ObservationKind::of_failure(&result.output)
+        };
         Self {
             tool_name: result.tool_name,
             success: result.success,
+            kind,
             output: result.output,
             data: result.data,
         }
@@ -148,6 +441,141 @@ mod tests {
         assert!(!state.should_continue()); // Reached max turns
     }
 
+    #[test]
+    fn test_force_answer_stops_loop() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let state = AgentLoopState::new(10).with_force_answer_flag(flag.clone());
+        assert!(state.should_continue());
+
+        // Set from outside, as the REPL does while a turn is running
+        flag.store(true, Ordering::SeqCst);
+        assert!(!state.should_continue());
+        assert!(state.answer_forced());
+    }
+
+    #[test]
+    fn test_repeated_failure_collapses() {
+        let error = format!("connection refused\n{}", "x".repeat(600));
+        let mut state = AgentLoopState::new(10);
+
+        for _ in 0..3 {
+            state.add_observations(vec![Observation::error("browser_url", &error)]);
+            state.next_turn();
+
+            // Only the failure from the latest turn is expanded
+            let formatted = state.format_observations();
+            assert_eq!(formatted.matches("connection refused").count(), 1);
+        }
+
+        let formatted = state.format_observations();
+        assert!(formatted.contains("tool browser_url failed (see turn 1)"));
+        assert!(formatted.contains("tool browser_url failed (see turn 2)"));
+        assert!(formatted.contains("... (truncated)"));
+        assert!(formatted.len() < error.len());
+        assert!(!formatted.contains(&error));
+    }
+
+    #[test]
+    fn test_failure_kinds_are_labelled() {
+        let mut state = AgentLoopState::new(10);
+        state.add_observations(vec![
+            Observation::from_error(
+                "browser_url",
+                &PraxisError::browser("timeout: agent-browser open did not finish within 30000ms"),
+            ),
+            Observation::failure(
+                "read_file",
+                ObservationKind::ValidationError,
+                "Invalid call to read_file: missing 'path'",
+            ),
+            Observation::from(crate::core::ToolResult::failure(
+                "run_command",
+                "The user declined to run `rm -rf target`",
+            )),
+            Observation::from_error("write_code", &PraxisError::tool("Cannot read 'a.rs'")),
+        ]);
+
+        let kinds: Vec<ObservationKind> = state.observations.iter().map(|o| o.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ObservationKind::Timeout,
+                ObservationKind::ValidationError,
+                ObservationKind::Cancelled,
+                ObservationKind::ToolError,
+            ]
+        );
+        let formatted = state.format_observations();
+        assert!(formatted.contains("### Observation 1 (browser_url, timed out)"));
+        assert!(formatted.contains("### Observation 2 (read_file, invalid call)"));
+        assert!(formatted.contains("### Observation 3 (run_command, cancelled)"));
+        assert!(formatted.contains("### Observation 4 (write_code, failed)"));
+    }
+
+    #[test]
+    fn test_json_observation_format() {
+        let mut state = AgentLoopState::new(10).with_observation_policy(ObservationPolicy {
+            format: ObservationFormat::Json,
+            ..Default::default()
+        });
+        state.add_observations(vec![
+            Observation::success("read_file", "fn main() {}"),
+            Observation::error("run_command", "exit status 1"),
+        ]);
+
+        let formatted = state.format_observations();
+        let json = formatted
+            .trim()
+            .strip_prefix("## Tool Observations:")
+            .unwrap();
+        let entries: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            entries,
+            serde_json::json!([
+                {"tool": "read_file", "success": true, "output": "fn main() {}"},
+                {"tool": "run_command", "success": false, "output": "exit status 1", "kind": "failed"},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_large_observation_keeps_head_and_tail() {
+        let output = format!("HEAD{}ref=e42", "é".repeat(10_000));
+        let mut state = AgentLoopState::new(10).with_observation_policy(ObservationPolicy {
+            max_output_bytes: 1000,
+            ..Default::default()
+        });
+        state.add_observations(vec![Observation::success("browser_snapshot", &output)]);
+
+        let formatted = state.format_observations();
+        assert!(formatted.contains("HEAD"));
+        assert!(formatted.contains("ref=e42"));
+        assert!(formatted.contains("...[truncated 19012 bytes]..."));
+        assert!(formatted.len() < 1200);
+    }
+
+    #[test]
+    fn test_repeated_batch_detected() {
+        let batch = vec![Observation::success(
+            "browser_snapshot",
+            "Found 22 elements",
+        )];
+        let mut state = AgentLoopState::new(10);
+        assert!(!state.is_repeat(&batch));
+
+        state.add_observations(batch.clone());
+        assert!(state.is_repeat(&batch));
+        assert!(!state.is_repeat(&[Observation::success(
+            "browser_snapshot",
+            "Found 23 elements"
+        )]));
+
+        state.note_repeat();
+        let formatted = state.format_observations();
+        assert_eq!(formatted.matches("Found 22 elements").count(), 1);
+        assert!(formatted.contains("Repeated action detected"));
+    }
+
     #[test]
     fn test_format_observations() {
         let mut state = AgentLoopState::new(10);
@@ -3,11 +3,17 @@
 //! Contains the main agent logic that coordinates LLM calls and tool execution.
 
 pub mod conversation;
+pub mod indexer;
 pub mod loop_state;
 pub mod orchestrator;
+pub mod progress;
 pub mod sub_agent;
 
-pub use conversation::Conversation;
-pub use loop_state::{AgentLoopState, Observation};
-pub use orchestrator::Agent;
+pub use conversation::{Conversation, SessionInfo};
+pub use indexer::{IndexReport, ProjectIndex};
+pub use loop_state::{
+    AgentLoopState, Observation, ObservationFormat, ObservationKind, ObservationPolicy,
+};
+pub use orchestrator::{strip_code_fences, Agent};
+pub use progress::{ConsoleReporter, NullReporter, ProgressReporter};
 pub use sub_agent::{SubAgent, SubAgentBuilder, SubAgentManager};
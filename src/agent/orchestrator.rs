@@ -3,15 +3,33 @@
 //! Main agent that coordinates between models, tools, and conversation.
 //! Implements a ReAct-style reasoning loop (Thought → Action → Observation).
 
-use std::io::{self, Write};
-use std::sync::Arc;
-
-use crate::agent::conversation::Conversation;
-use crate::agent::loop_state::{AgentLoopState, Observation};
-use crate::core::{Config, Message, PraxisError, Result, ToolCall, ToolDefinition};
-use crate::llm::{create_provider, GenerateOptions, LLMProvider};
-use crate::tools::browser::BrowserExecutor;
-use crate::tools::ToolRegistry;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use std::future::Future;
+use tokio_util::sync::CancellationToken;
+
+use tracing::{debug, warn, Instrument};
+
+use crate::agent::conversation::{self, Conversation, SessionInfo};
+use crate::agent::indexer::{self, IndexReport, INDEXER_NAME, INDEXER_PROMPT};
+use crate::agent::loop_state::{
+    truncate_middle, AgentLoopState, Observation, ObservationKind, ObservationPolicy,
+};
+use crate::agent::progress::{ConsoleReporter, ProgressReporter};
+use crate::agent::sub_agent::{SubAgent, SubAgentManager};
+use crate::core::config::ProviderType;
+use crate::core::{Config, Message, PraxisError, Result, ToolCall, ToolCategory, ToolDefinition};
+use crate::llm::{
+    capabilities, create_provider, prompt_template, GenerateOptions, LLMProvider, LLMResponse,
+    PromptToolAdapter, PullProgressCallback, TokenUsage, PROMPT_TOOL_RULES,
+};
+use crate::tools::browser::{BrowserExecutor, SessionStart, Version};
+use crate::tools::system::ConfirmRequest;
+use crate::tools::{ToolFuture, ToolHandler, ToolRegistry};
 
 /// Main agent that orchestrates LLM and tools
 pub struct Agent {
@@ -25,24 +43,59 @@ pub struct Agent {
     conversation: Conversation,
     /// Whether browser is available
     browser_available: bool,
+    /// Detected agent-browser version, once checked
+    browser_version: Option<Version>,
+    /// Why an installed agent-browser can't be used (e.g. too old)
+    browser_problem: Option<String>,
+    /// How the browser session was attached, with `browser.reuse_session`
+    browser_session: Option<SessionStart>,
+    /// Set to stop the current run and answer from its observations
+    force_answer: Arc<AtomicBool>,
+    /// Token usage accumulated by the run in progress
+    run_usage: Arc<Mutex<TokenUsage>>,
+    /// Token usage of the last completed run
+    last_usage: Option<TokenUsage>,
+    /// Token usage of every run this session
+    session_usage: TokenUsage,
+    /// User input of the last run
+    last_input: String,
+    /// Tool calls made during the last run, in order
+    last_tool_calls: Vec<ToolCall>,
+    /// Sub-agents spawned this session
+    sub_agents: SubAgentManager,
+    /// Summaries of the project's key files, once indexed
+    project_notes: Arc<Mutex<Option<String>>>,
+    /// Answer of the last run already shown through the reporter as it
+    /// streamed, if any
+    streamed_answer: Mutex<Option<String>>,
+    /// Receives progress from the reasoning loop
+    reporter: Arc<dyn ProgressReporter>,
+    /// Where shell commands are confirmed, kept so rebuilt registries use it too
+    command_confirmation: Option<tokio::sync::mpsc::UnboundedSender<ConfirmRequest>>,
+    /// Tools added with `register_tool`, kept so rebuilt registries have them too
+    custom_tools: Vec<(ToolDefinition, ToolCategory, ToolHandler)>,
+    /// Project root for sessions, file tools and shell commands
+    workdir: std::path::PathBuf,
+    /// Whether `shutdown` has already run
+    shut_down: bool,
 }
 
 impl Agent {
     /// Create a new agent with default configuration
     pub async fn new() -> Result<Self> {
-        Self::with_config(Config::load()).await
+        Self::with_config(Config::load()?).await
     }
 
     /// Create an agent with custom configuration
     pub async fn with_config(config: Config) -> Result<Self> {
         let llm = create_provider(&config).await?;
+        Ok(Self::with_provider(config, llm))
+    }
 
-        let tools = if config.browser.enabled {
-            ToolRegistry::with_browser(&config.browser.session_name)
-        } else {
-            ToolRegistry::new()
-        };
-
+    /// Create an agent that uses a specific LLM provider
+    pub fn with_provider(config: Config, llm: Arc<dyn LLMProvider>) -> Self {
+        let tools = build_tools(&config);
+        let workdir = config.workdir();
         let mut conversation = Conversation::new(config.agent.max_history);
 
         // Set system prompt if configured
@@ -50,13 +103,40 @@ impl Agent {
             conversation.set_system_prompt(prompt.clone());
         }
 
-        Ok(Self {
+        Self {
             config,
             llm,
             tools: Arc::new(tools),
             conversation,
             browser_available: false, // Will be checked on first use
-        })
+            browser_version: None,
+            browser_problem: None,
+            browser_session: None,
+            force_answer: Arc::new(AtomicBool::new(false)),
+            run_usage: Arc::new(Mutex::new(TokenUsage::default())),
+            last_usage: None,
+            session_usage: TokenUsage::default(),
+            last_input: String::new(),
+            last_tool_calls: Vec::new(),
+            sub_agents: SubAgentManager::new(),
+            project_notes: Arc::new(Mutex::new(None)),
+            streamed_answer: Mutex::new(None),
+            reporter: Arc::new(ConsoleReporter),
+            command_confirmation: None,
+            custom_tools: Vec::new(),
+            workdir,
+            shut_down: false,
+        }
+    }
+
+    /// The project root sessions, file tools and shell commands use
+    pub fn workdir(&self) -> &std::path::Path {
+        &self.workdir
+    }
+
+    /// Replace the progress reporter (e.g. with `NullReporter` to run silently)
+    pub fn set_reporter(&mut self, reporter: Arc<dyn ProgressReporter>) {
+        self.reporter = reporter;
     }
 
     /// Enable session persistence
@@ -66,6 +146,111 @@ impl Agent {
             .map_err(|e| PraxisError::config(format!("Failed to enable persistence: {}", e)))
     }
 
+    /// Replace the active conversation, flushing the old one to its file
+    ///
+    /// Returns the previous conversation.
+    pub fn swap_conversation(&mut self, conversation: Conversation) -> Conversation {
+        self.conversation.flush();
+        std::mem::replace(&mut self.conversation, conversation)
+    }
+
+    /// Directory holding named sessions (`.praxis/sessions` under the
+    /// project root)
+    fn sessions_dir(&self) -> std::path::PathBuf {
+        self.workdir.join(".praxis").join("sessions")
+    }
+
+    /// File of the named session
+    fn named_session_path(&self, name: &str) -> Result<std::path::PathBuf> {
+        conversation::session_path(&self.sessions_dir(), name)
+            .ok_or_else(|| PraxisError::config(format!("Invalid session name: {}", name)))
+    }
+
+    /// An empty conversation with the configured system prompt
+    fn new_conversation(&self) -> Conversation {
+        let mut conversation = Conversation::new(self.config.agent.max_history);
+        if let Some(ref prompt) = self.config.agent.system_prompt {
+            conversation.set_system_prompt(prompt.clone());
+        }
+        conversation
+    }
+
+    /// Save the current conversation as a named session and continue in it
+    pub fn save_session(&mut self, name: &str) -> Result<std::path::PathBuf> {
+        let path = self.named_session_path(name)?;
+
+        let mut named = self.conversation.clone();
+        named.set_persistence_path(path.clone());
+        named.flush();
+        self.swap_conversation(named);
+        Ok(path)
+    }
+
+    /// Switch to a named session, saving the current one first
+    pub fn load_session(&mut self, name: &str) -> Result<usize> {
+        let path = self.named_session_path(name)?;
+        if !path.exists() {
+            return Err(PraxisError::config(format!("No session named '{}'", name)));
+        }
+
+        let mut loaded = self.new_conversation();
+        loaded
+            .enable_persistence(path)
+            .map_err(|e| PraxisError::config(format!("Failed to load session: {}", e)))?;
+
+        let count = loaded.len();
+        self.swap_conversation(loaded);
+        Ok(count)
+    }
+
+    /// List named sessions
+    pub fn list_sessions(&self) -> Result<Vec<SessionInfo>> {
+        conversation::list_sessions(&self.sessions_dir())
+            .map_err(|e| PraxisError::config(format!("Failed to list sessions: {}", e)))
+    }
+
+    /// The user messages of a named session, in order
+    pub fn session_inputs(&self, name: &str) -> Result<Vec<String>> {
+        let path = self.named_session_path(name)?;
+        if !path.exists() {
+            return Err(PraxisError::config(format!("No session named '{}'", name)));
+        }
+
+        let mut session = Conversation::new(self.config.agent.max_history);
+        session
+            .load(&path)
+            .map_err(|e| PraxisError::config(format!("Failed to load session: {}", e)))?;
+        Ok(session
+            .get_history()
+            .iter()
+            .filter(|m| m.role == "user")
+            .map(|m| m.content.clone())
+            .collect())
+    }
+
+    /// Run a named session's user messages through `process` again, in
+    /// order, starting from an empty history
+    ///
+    /// `on_response` gets each input's position, text and result as it
+    /// finishes. The current conversation is restored afterwards. Returns
+    /// the number of inputs replayed.
+    pub async fn replay_session(
+        &mut self,
+        name: &str,
+        mut on_response: impl FnMut(usize, &str, &Result<String>),
+    ) -> Result<usize> {
+        let inputs = self.session_inputs(name)?;
+        let previous = self.swap_conversation(self.new_conversation());
+
+        for (index, input) in inputs.iter().enumerate() {
+            let result = self.process(input).await;
+            on_response(index, input, &result);
+        }
+
+        self.swap_conversation(previous);
+        Ok(inputs.len())
+    }
+
     /// Initialize the agent (check dependencies, models, etc.)
     pub async fn initialize(&mut self) -> Result<()> {
         // Check if Ollama is reachable
@@ -74,7 +259,7 @@ impl Agent {
             Err(_) => {
                 // If using Ollama, provide specific error, otherwise generic
                 match self.config.provider {
-                    crate::core::config::ProviderType::Ollama => {
+                    ProviderType::Ollama => {
                         return Err(PraxisError::OllamaNotReachable(
                             self.config.ollama_url(),
                             self.config.models.orchestrator.clone(),
@@ -91,75 +276,437 @@ impl Agent {
             }
         };
 
-        if self.config.agent.debug {
-            eprintln!("DEBUG: Available models: {:?}", models);
-        }
+        debug!(?models, "Available models");
 
-        // Check orchestrator model
-        if !self
-            .llm
-            .is_model_available(&self.config.models.orchestrator)
-            .await?
-        {
-            return Err(PraxisError::ModelNotFound(
+        if models.is_empty() && self.config.provider == ProviderType::Ollama {
+            return Err(PraxisError::NoModelsInstalled(
                 self.config.models.orchestrator.clone(),
-            ));
-        }
-
-        // Check executor model
-        if !self
-            .llm
-            .is_model_available(&self.config.models.executor)
-            .await?
-        {
-            return Err(PraxisError::ModelNotFound(
                 self.config.models.executor.clone(),
             ));
         }
 
+        // Check orchestrator and executor models (or a fallback for each)
+        let models = &self.config.models;
+        self.check_model_available(
+            "orchestrator",
+            &models.orchestrator,
+            &models.alternatives.orchestrators,
+        )
+        .await?;
+        self.check_model_available("executor", &models.executor, &models.alternatives.executors)
+            .await?;
+
         // Check if agent-browser is available
         if self.config.browser.enabled {
-            self.browser_available = BrowserExecutor::is_available().await;
+            let checked = BrowserExecutor::check_version().await;
+            self.browser_available = checked.is_ok();
+            self.browser_version = checked.as_ref().ok().copied();
+            self.browser_problem = match checked {
+                Ok(_) | Err(PraxisError::AgentBrowserNotFound) => None,
+                Err(e) => {
+                    warn!("{}", e);
+                    Some(e.to_string())
+                }
+            };
+
+            self.browser_session = None;
+            if self.browser_available && self.config.browser.reuse_session {
+                if let Some(browser) = self.tools.browser_executor() {
+                    match browser.resume().await {
+                        Ok(start) => self.browser_session = Some(start),
+                        Err(e) => warn!("Couldn't attach to the browser session: {}", e),
+                    }
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Clean up before exiting: save the conversation and close the browser
+    ///
+    /// The browser stays open when `browser.reuse_session` is on, so the next
+    /// start can pick it up. Calling this again does nothing.
+    pub async fn shutdown(&mut self) {
+        if self.shut_down {
+            return;
+        }
+        self.shut_down = true;
+
+        self.conversation.flush();
+
+        if self.browser_available && !self.config.browser.reuse_session {
+            if let Some(browser) = self.tools.browser_executor() {
+                if let Err(e) = browser.close().await {
+                    warn!("Couldn't close the browser session: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Fail with `ModelNotFound` unless `primary` or one of its fallbacks is available
+    async fn check_model_available(
+        &self,
+        role: &str,
+        primary: &str,
+        alternatives: &[String],
+    ) -> Result<()> {
+        for model in self.fallback_chain(primary, alternatives) {
+            if self.llm.is_model_available(&model).await? {
+                if model != primary {
+                    warn!(role, model = %primary, fallback = %model, "Model not available, will fall back");
+                }
+                return Ok(());
+            }
+        }
+        Err(PraxisError::ModelNotFound(primary.to_string()))
+    }
+
     /// Process a user message using ReAct reasoning loop
     ///
     /// The loop continues until:
     /// 1. The model produces a response without tool calls (final answer)
     /// 2. Maximum turns are reached
     pub async fn process(&mut self, user_input: &str) -> Result<String> {
+        self.process_with_cancel(user_input, None).await
+    }
+
+    /// Process a user message, stopping early if `cancel` is triggered
+    ///
+    /// Cancellation is checked between turns and aborts any in-flight model
+    /// call. On cancel the pending user message is removed so history stays
+    /// as it was after the last completed exchange, and
+    /// `PraxisError::Cancelled` is returned.
+    pub async fn process_with_cancel(
+        &mut self,
+        user_input: &str,
+        cancel: Option<CancellationToken>,
+    ) -> Result<String> {
+        if let Ok(mut usage) = self.run_usage.lock() {
+            *usage = TokenUsage::default();
+        }
+
+        self.last_input = user_input.to_string();
+        self.last_tool_calls.clear();
+
+        let result = self.run_loop(user_input, cancel).await;
+
+        // Record usage even for failed or cancelled runs; the tokens were spent
+        let usage = self
+            .run_usage
+            .lock()
+            .map(|usage| usage.clone())
+            .unwrap_or_default();
+        self.session_usage.add(Some(&usage));
+        self.last_usage = Some(usage);
+
+        result
+    }
+
+    /// Answer with the executor model directly, skipping tools and the ReAct loop
+    ///
+    /// The conversation is sent as context and the exchange is added to it,
+    /// so `chat` and `process` can be mixed in one session.
+    pub async fn chat(&mut self, input: &str) -> Result<String> {
+        self.conversation.add_user(input);
+        let messages = self
+            .conversation
+            .get_context_window(self.config.agent.context_window);
+
+        let response = match self.executor_chat(&messages).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.conversation.pop_last();
+                return Err(e);
+            }
+        };
+
+        let usage = response.usage.clone().unwrap_or(TokenUsage {
+            partial: true,
+            ..TokenUsage::default()
+        });
+        self.session_usage.add(Some(&usage));
+        self.last_usage = Some(usage);
+        self.last_tool_calls.clear();
+
+        self.conversation.add_assistant(&response.content);
+        Ok(response.content)
+    }
+
+    /// Token usage of the last `process` run, summed over every model call
+    pub fn last_usage(&self) -> Option<TokenUsage> {
+        self.last_usage.clone()
+    }
+
+    /// Token usage summed over every run this session
+    pub fn session_usage(&self) -> &TokenUsage {
+        &self.session_usage
+    }
+
+    /// Tool calls made during the last `process` run, in order
+    pub fn last_tool_calls(&self) -> &[ToolCall] {
+        &self.last_tool_calls
+    }
+
+    /// Ask the orchestrator model to explain the last run's tool choices
+    ///
+    /// This is a standalone call: it doesn't read or add to the conversation.
+    pub async fn explain_last_run(&mut self) -> Result<String> {
+        if self.last_input.is_empty() {
+            return Ok("No request has been processed yet.".to_string());
+        }
+        if self.last_tool_calls.is_empty() {
+            return Ok(
+                "The last request was answered directly, without using any tools.".to_string(),
+            );
+        }
+
+        let messages = vec![Message::user(build_why_prompt(
+            &self.last_input,
+            &self.last_tool_calls,
+        ))];
+
+        let response = self
+            .llm
+            .chat(
+                &self.config.models.orchestrator,
+                &messages,
+                Some(GenerateOptions {
+                    temperature: Some(0.3),
+                    ..sampling_options(&self.config)
+                }),
+            )
+            .await?;
+
+        self.session_usage.add(response.usage.as_ref());
+        Ok(response.content)
+    }
+
+    /// Run a task on the named sub-agent, creating it if needed
+    ///
+    /// New sub-agents use this agent's provider with the current executor
+    /// model, and share its tool registry. They stay registered for the rest of the session.
+    pub async fn spawn_sub_agent(&mut self, name: &str, task: &str) -> Result<String> {
+        let agent = match self.sub_agents.get_agent(name) {
+            Some(agent) => agent.clone(),
+            None => {
+                let agent = SubAgent::builder(name)
+                    .llm(self.llm.clone())
+                    .model(&self.config.models.executor)
+                    .tools(self.tools.clone())
+                    .build()?;
+                self.sub_agents.add_agent(agent.clone());
+                agent
+            }
+        };
+
+        agent.run(task).await
+    }
+
+    /// Get the sub-agents spawned this session
+    pub fn sub_agents(&self) -> &SubAgentManager {
+        &self.sub_agents
+    }
+
+    /// The "indexer" sub-agent, registered on first use
+    fn indexer(&mut self) -> Result<SubAgent> {
+        if let Some(agent) = self.sub_agents.get_agent(INDEXER_NAME) {
+            return Ok(agent.clone());
+        }
+        let agent = SubAgent::builder(INDEXER_NAME)
+            .system_prompt(INDEXER_PROMPT)
+            .without_tools()
+            .llm(self.llm.clone())
+            .model(&self.config.models.executor)
+            .build()?;
+        self.sub_agents.add_agent(agent.clone());
+        Ok(agent)
+    }
+
+    /// Summarize the project's key files into notes for the orchestrator
+    ///
+    /// Summaries are cached in `.praxis/index.json`, so only files changed
+    /// since the last run are summarized again.
+    pub async fn index_project(&mut self) -> Result<IndexReport> {
+        let sub_agent = self.indexer()?;
+        self.reporter
+            .status("[indexer] Summarizing project files...");
+        let report = indexer::index_project(&sub_agent, &self.workdir).await?;
+        *self.project_notes.lock().unwrap() = report.index.note();
+        Ok(report)
+    }
+
+    /// Run `index_project` in the background; the orchestrator sees the
+    /// notes from its first call after indexing finishes
+    pub fn start_indexing(&mut self) -> Result<tokio::task::JoinHandle<Result<IndexReport>>> {
+        let sub_agent = self.indexer()?;
+        let workdir = self.workdir.clone();
+        let notes = self.project_notes.clone();
+
+        Ok(tokio::spawn(async move {
+            let report = indexer::index_project(&sub_agent, &workdir).await;
+            match report {
+                Ok(ref report) => *notes.lock().unwrap() = report.index.note(),
+                Err(ref e) => warn!("Project indexing failed: {}", e),
+            }
+            report
+        }))
+    }
+
+    /// The part of the last run's `answer` that wasn't already shown
+    /// through the reporter while it streamed
+    ///
+    /// All of `answer` unless it was synthesized with executor streaming on;
+    /// then only what was added afterwards (e.g. a "stopped early" note).
+    pub fn unstreamed_part<'a>(&self, answer: &'a str) -> &'a str {
+        match self.streamed_answer.lock().unwrap().as_deref() {
+            Some(streamed) => answer
+                .strip_prefix(streamed)
+                .map_or(answer, str::trim_start),
+            None => answer,
+        }
+    }
+
+    /// Summaries of the project's key files, once indexed
+    pub fn project_notes(&self) -> Option<String> {
+        self.project_notes.lock().unwrap().clone()
+    }
+
+    /// Run the ReAct loop for one user message
+    async fn run_loop(
+        &mut self,
+        user_input: &str,
+        cancel: Option<CancellationToken>,
+    ) -> Result<String> {
         // Add user message to history
         self.conversation.add_user(user_input);
 
         // Initialize loop state
-        let mut state = AgentLoopState::new(self.config.agent.max_turns);
-
-        println!(
-            "\n[Agent] Starting reasoning loop (max {} turns)",
-            state.max_turns
-        );
+        self.force_answer.store(false, Ordering::SeqCst);
+        *self.streamed_answer.lock().unwrap() = None;
+        let mut state = AgentLoopState::new(self.config.agent.max_turns)
+            .with_force_answer_flag(self.force_answer.clone())
+            .with_observation_policy(ObservationPolicy {
+                max_failure_chars: self.config.agent.max_failed_observation_chars,
+                collapse_failures: self.config.agent.collapse_failed_observations,
+                max_output_bytes: self.config.agent.max_observation_bytes,
+                format: self.config.agent.observation_format,
+            });
+
+        self.reporter.loop_started(state.max_turns);
+
+        // Tool calls and their results, added to the history once the run completes
+        let mut trace = Vec::new();
+        let started = Instant::now();
+        let mut budget_exceeded = None;
+        let mut nudged = false;
 
         // ReAct Loop: Thought → Action → Observation
         while state.should_continue() {
+            if is_cancelled(&cancel) {
+                return Err(self.abort_cancelled());
+            }
+
+            if let Some(budget) = self.exceeded_budget(started) {
+                budget_exceeded = Some(budget);
+                break;
+            }
+
             let turn = state.turn + 1;
-            println!("\n[Turn {}/{}] Analyzing...", turn, state.max_turns);
+            self.reporter.turn_started(turn, state.max_turns);
+            let turn_span = tracing::info_span!("turn", turn);
 
             // Build context with observations from previous turns
-            let response = self
-                .call_orchestrator_with_context(user_input, &state)
-                .await?;
+            let response = tokio::select! {
+                r = self
+                    .call_orchestrator_with_context(user_input, &state, None)
+                    .instrument(turn_span.clone()) => Some(r),
+                _ = wait_for_cancel(&cancel) => None,
+            };
+            let Some(response) = response else {
+                return Err(self.abort_cancelled());
+            };
+            let mut response = response?;
+
+            // Give the model a chance to fix malformed tool calls
+            let mut repairs = 0;
+            while repairs < self.config.agent.max_repair_retries {
+                let Some(problem) = self.find_invalid_tool_call(&response.tool_calls) else {
+                    break;
+                };
+                repairs += 1;
+                self.reporter.status(&format!(
+                    "[Turn {}] Invalid tool call ({}). Asking the model to retry...",
+                    turn, problem
+                ));
+
+                let note = format!("\n\nYour {}; please retry.", problem);
+                let retried = tokio::select! {
+                    r = self
+                        .call_orchestrator_with_context(user_input, &state, Some(&note))
+                        .instrument(turn_span.clone()) => Some(r),
+                    _ = wait_for_cancel(&cancel) => None,
+                };
+                let Some(retried) = retried else {
+                    return Err(self.abort_cancelled());
+                };
+                response = retried?;
+            }
+
+            // A weak model may describe its next step instead of taking it
+            if response.tool_calls.is_empty()
+                && !nudged
+                && trace.is_empty()
+                && self.config.agent.nudge_on_intent
+                && announces_action(&response.content)
+            {
+                nudged = true;
+                self.reporter.status(&format!(
+                    "[Turn {}] Model described an action without calling a tool. Nudging...",
+                    turn
+                ));
+
+                let nudged_response = tokio::select! {
+                    r = self
+                        .call_orchestrator_with_context(user_input, &state, Some(INTENT_NUDGE))
+                        .instrument(turn_span.clone()) => Some(r),
+                    _ = wait_for_cancel(&cancel) => None,
+                };
+                let Some(nudged_response) = nudged_response else {
+                    return Err(self.abort_cancelled());
+                };
+                response = nudged_response?;
+            }
+
+            // A truncated stream can leave nothing at all; try once more, a little warmer
+            if response.tool_calls.is_empty() && response.content.trim().is_empty() {
+                let temperature = (self.config.models.orchestrator_temperature
+                    + EMPTY_RETRY_TEMPERATURE_STEP)
+                    .min(2.0);
+                warn!(parent: &turn_span, temperature, "Empty orchestrator response, retrying once");
+                self.reporter.status(&format!(
+                    "[Turn {}] Model returned an empty response. Retrying...",
+                    turn
+                ));
+
+                let retried = tokio::select! {
+                    r = self
+                        .call_orchestrator_at(user_input, &state, None, temperature)
+                        .instrument(turn_span.clone()) => Some(r),
+                    _ = wait_for_cancel(&cancel) => None,
+                };
+                let Some(retried) = retried else {
+                    return Err(self.abort_cancelled());
+                };
+                response = retried?;
+            }
 
             // Check if the model wants to use tools
             if response.tool_calls.is_empty() {
                 // No tool calls = final answer
                 if !response.content.is_empty() {
                     state.final_answer = Some(response.content.clone());
-                    if self.config.agent.debug {
-                        eprintln!("DEBUG: Final answer received on turn {}", turn);
-                    }
+                    debug!(parent: &turn_span, "Final answer received");
                 } else {
                     // Empty response with no tools - shouldn't happen but handle gracefully
                     state.final_answer =
@@ -168,23 +715,44 @@ impl Agent {
                 break;
             }
 
-            // Execute tools
-            println!(
-                "[Turn {}] Executing {} tool(s)...",
-                turn,
-                response.tool_calls.len()
-            );
+            self.last_tool_calls
+                .extend(response.tool_calls.iter().cloned());
 
-            let observations = self.execute_tools(&response.tool_calls).await?;
+            // Execute tools
+            self.reporter.tools_started(turn, response.tool_calls.len());
+
+            let observations = tokio::select! {
+                r = self
+                    .execute_tools(&response.tool_calls)
+                    .instrument(turn_span.clone()) => Some(r),
+                _ = wait_for_cancel(&cancel) => None,
+            };
+            let Some(observations) = observations else {
+                return Err(self.abort_cancelled());
+            };
+            let observations = observations?;
 
-            // Print tool results
             for obs in &observations {
-                let status = if obs.success { "✓" } else { "✗" };
-                println!("  {} {} ", status, obs.tool_name);
+                self.reporter.tool_result(obs);
             }
 
-            // Add observations to state
-            state.add_observations(observations);
+            trace.push(Message::assistant_with_tools(
+                response.content.clone(),
+                response.tool_calls.clone(),
+            ));
+            trace.extend(observations.iter().map(|obs| self.tool_message(obs)));
+            state.set_images(screenshot_images(&observations));
+
+            // Don't pile up identical results from a looping model
+            if state.is_repeat(&observations) {
+                self.reporter.status(&format!(
+                    "[Turn {}] Repeated action detected; asking for a final answer",
+                    turn
+                ));
+                state.note_repeat();
+            } else {
+                state.add_observations(observations);
+            }
             state.next_turn();
         }
 
@@ -192,28 +760,165 @@ impl Agent {
         let answer = if let Some(answer) = state.final_answer {
             answer
         } else {
-            // Max turns reached - synthesize from observations
-            println!("\n[Agent] Max turns reached. Synthesizing response...");
-            self.synthesize_from_observations(&state).await?
+            // Out of turns or budget, or answer forced - synthesize from observations
+            if let Some(ref budget) = budget_exceeded {
+                self.reporter.status(&format!(
+                    "[Agent] {} exceeded. Synthesizing response...",
+                    budget
+                ));
+            } else if state.answer_forced() {
+                self.reporter
+                    .status("[Agent] Answering now as requested. Synthesizing response...");
+            } else {
+                self.reporter
+                    .status("[Agent] Max turns reached. Synthesizing response...");
+            }
+            let answer = tokio::select! {
+                r = self.synthesize_from_observations(&state) => Some(r),
+                _ = wait_for_cancel(&cancel) => None,
+            };
+            let Some(answer) = answer else {
+                return Err(self.abort_cancelled());
+            };
+            match budget_exceeded {
+                Some(budget) => format!("{}\n\n(Stopped early: {} exceeded.)", answer?, budget),
+                None if state.answer_forced() => {
+                    format!("{}\n\n(Stopped early as requested.)", answer?)
+                }
+                None => answer?,
+            }
         };
 
         // Add to conversation history
+        for message in trace {
+            match message.tool_calls {
+                Some(calls) => self
+                    .conversation
+                    .add_assistant_with_tools(message.content, calls),
+                None => self
+                    .conversation
+                    .add_tool_result(message.tool_name.unwrap_or_default(), message.content),
+            }
+        }
         self.conversation.add_assistant(&answer);
+        self.summarize_history().await;
 
-        println!(
-            "\n[Agent] Complete ({} turns, {} observations)",
-            state.turn,
-            state.observations.len()
-        );
+        self.reporter
+            .final_answer(&answer, state.turn, state.observations.len());
 
         Ok(answer)
     }
 
+    /// Condense old history once it crosses the configured threshold,
+    /// keeping the last `context_window` messages verbatim
+    async fn summarize_history(&mut self) {
+        let agent = &self.config.agent;
+        if !agent.summarize_history || self.conversation.len() <= agent.summarize_threshold {
+            return;
+        }
+
+        let count = self.conversation.len().saturating_sub(agent.context_window);
+        match self
+            .conversation
+            .summarize_oldest(self.llm.as_ref(), &self.config.models.executor, count)
+            .await
+        {
+            Ok(usage) => {
+                if let Ok(mut total) = self.run_usage.lock() {
+                    total.add(usage.as_ref());
+                }
+            }
+            Err(e) => self
+                .reporter
+                .status(&format!("[Agent] Couldn't summarize history: {}", e)),
+        }
+    }
+
+    /// Ask the running `process` call to stop after the current turn and
+    /// answer from the observations collected so far
+    pub fn request_final_answer(&self) {
+        self.force_answer.store(true, Ordering::SeqCst);
+    }
+
+    /// Get a handle that can force a final answer from another task
+    pub fn force_answer_handle(&self) -> Arc<AtomicBool> {
+        self.force_answer.clone()
+    }
+
+    /// Describe the configured budget this run has used up, if any
+    fn exceeded_budget(&self, started: Instant) -> Option<String> {
+        let agent = &self.config.agent;
+
+        if let Some(max) = agent.max_wall_secs {
+            if started.elapsed().as_secs() >= max {
+                return Some(format!("Time budget of {}s", max));
+            }
+        }
+
+        if let Some(max) = agent.max_total_tokens {
+            let used = self
+                .run_usage
+                .lock()
+                .map(|usage| usage.total_tokens)
+                .unwrap_or(0);
+            if used >= max {
+                return Some(format!("Token budget of {} ({} used)", max, used));
+            }
+        }
+
+        None
+    }
+
+    /// Turn an observation into a history message, capped like prompt observations
+    fn tool_message(&self, obs: &Observation) -> Message {
+        let output = truncate_middle(&obs.output, self.config.agent.max_observation_bytes);
+        if obs.success {
+            Message::tool(&obs.tool_name, output)
+        } else {
+            Message::tool(&obs.tool_name, format!("Error: {}", output))
+        }
+    }
+
+    /// Roll back the pending user message after a cancelled run
+    fn abort_cancelled(&mut self) -> PraxisError {
+        if self
+            .conversation
+            .last_n(1)
+            .first()
+            .is_some_and(|m| m.role == "user")
+        {
+            self.conversation.pop_last();
+        }
+        self.reporter.status("[Agent] Cancelled");
+        PraxisError::Cancelled
+    }
+
     /// Call the orchestrator model with context from previous observations
+    ///
+    /// `repair_note` is appended after the observations when re-prompting
+    /// the model about an invalid tool call.
     async fn call_orchestrator_with_context(
         &self,
         user_input: &str,
         state: &AgentLoopState,
+        repair_note: Option<&str>,
+    ) -> Result<crate::llm::LLMResponse> {
+        self.call_orchestrator_at(
+            user_input,
+            state,
+            repair_note,
+            self.config.models.orchestrator_temperature,
+        )
+        .await
+    }
+
+    /// `call_orchestrator_with_context` at a specific sampling temperature
+    async fn call_orchestrator_at(
+        &self,
+        user_input: &str,
+        state: &AgentLoopState,
+        repair_note: Option<&str>,
+        temperature: f32,
     ) -> Result<crate::llm::LLMResponse> {
         // Build system prompt with ReAct instructions and ref usage guidance
         let browser_instructions = if self.browser_available {
@@ -223,12 +928,20 @@ impl Agent {
 - `browser_snapshot`: Get interactive elements. Returns elements with [ref=eN] tags.
 - `browser_fill`: Type text into an element. Args: {"ref": "e5", "text": "search query"}
 - `browser_click`: Click an element. Args: {"ref": "e8"}
+- `browser_submit`: Fill a field and press Enter in one step, e.g. for search boxes. Args: {"ref": "e5", "text": "search query"}
+- `browser_select`: Choose a dropdown (combobox) option. Args: {"ref": "e4", "value": "Canada"}
+- `browser_wait`: Wait for dynamic content before snapshotting. Args: {"text": "Results"} or {"selector": "e7", "timeout_ms": 5000}
+- `browser_hover`: Hover to open a menu or tooltip; revealed refs appear in the returned snapshot. Args: {"ref": "e3"}
+- `browser_press`: Press a key or combo, e.g. Escape to close a modal or Tab to move focus. Args: {"key": "Escape"} or {"key": "Control+A"}
+- `browser_get_attribute`: Read an attribute that isn't visible text, like a link's URL. Args: {"ref": "e9", "attribute": "href"}
+- `browser_extract_links`: List every link on the page with its URL, to pick where to go next. Args: {}
+- `browser_list_tabs` / `browser_switch_tab`: See open tabs and switch. Args: {"index": 1}
 
 ## Optimal Browser Workflow:
 1. `browser_url`: Navigate to the site.
 2. **OBSERVE**: Identify the target element's ref (e.g., `e5`) from the snapshot provided in the observation.
 3. **ACT**: Use the EXACT ref (e.g., `e5`) with `browser_fill` or `browser_click`.
-4. **REPEAT**: Each action returns an updated snapshot. Always check the LATEST observation before selecting the next ref.
+4. **REPEAT**: Each action returns the updated page, often as a list of changed elements (refs not listed are unchanged). Always check the LATEST observation before selecting the next ref.
 
 ## CRITICAL: Element References
 When a snapshot returns: `link "Sign in" [ref=e12]`, use `{"ref": "e12"}`.
@@ -237,123 +950,333 @@ The system automatically handles the `@` prefix for you. DO NOT use descriptions
             ""
         };
 
-        let system_prompt = format!(
-            r#"You are an AI agent that uses tools to accomplish tasks. Follow the ReAct pattern:
-1. THINK about what you need to do.
-2. ACT by calling appropriate tools.
-3. OBSERVE the results and continue or provide final answer.
+        // Tune the wording for the orchestrator's model family
+        let template = prompt_template(
+            &self.config.models.orchestrator,
+            &self.config.agent.model_templates,
+        )
+        .unwrap_or_default();
+        let preamble = template.preamble.as_deref().unwrap_or(REACT_PREAMBLE);
+        // Models on the prompt-tool adapter call tools as text blocks
+        let prompt_tool_rules = if capabilities(&self.config.models.orchestrator).supports_tools {
+            &[][..]
+        } else {
+            PROMPT_TOOL_RULES
+        };
+        let extra_rules: String = template
+            .rules
+            .iter()
+            .map(String::as_str)
+            .chain(prompt_tool_rules.iter().copied())
+            .map(|rule| format!("\n- {}", rule))
+            .collect();
+
+        let mut system_prompt = format!(
+            r#"{}
 
 ## Coding Tools
 - `write_code`, `explain_code`, `debug_code`
+- To change an existing file, pass its `file_path` to `write_code` to get back a unified diff of the suggested change
+- To save generated code, pass `output_path` to `write_code` (add `"overwrite": true` to replace an existing file)
+
+## File Tools
+- `list_files`: See the project layout before guessing file names. Args: {{"dir": "src", "glob": "*.rs"}} (both optional)
+- `read_file`: Read a project file. Args: {{"path": "src/main.rs"}}
+- `write_file`: Write a project file. Args: {{"path": "notes.md", "content": "..."}}
+
+## History Tools
+- `search_history`: Find earlier messages that mention something, beyond what's in view. Args: {{"pattern": "api key"}}
 {}
 
 ## Rules
 - Respond with your final answer ONLY when the task is complete.
 - ALWAYS read the latest tool observation carefully before choosing your next action.
-- Use EXACT element refs from snapshots for all browser interactions."#,
-            browser_instructions
+- Use EXACT element refs from snapshots for all browser interactions.{}"#,
+            preamble, browser_instructions, extra_rules
         );
+        if let Some(notes) = self.project_notes() {
+            system_prompt.push_str(&format!("\n\n## Project Notes\n{}", notes));
+        }
 
-        // Build message with user input and any observations
-        let user_content = if state.observations.is_empty() {
-            user_input.to_string()
-        } else {
-            format!("{}\n{}", user_input, state.format_observations())
+        // Build messages from recent history plus this loop's observations
+        let history = match self.config.agent.context_budget_tokens {
+            Some(budget) => self.conversation.get_context_window_tokens(budget),
+            None => self
+                .conversation
+                .get_context_window(self.config.agent.context_window),
         };
+        let mut observations = state.format_observations();
+        if let Some(note) = repair_note {
+            observations.push_str(note);
+        }
+        let mut messages =
+            build_orchestrator_messages(system_prompt, history, user_input, &observations);
 
-        let messages = vec![Message::system(system_prompt), Message::user(user_content)];
+        // Let a vision model see the latest screenshots
+        if let Some(last) = messages.last_mut() {
+            last.images.extend_from_slice(state.images());
+        }
 
         // Get appropriate tool definitions
         let mut tool_defs: Vec<ToolDefinition> =
             self.tools.coding_tools().into_iter().cloned().collect();
+        tool_defs.extend(self.tools.fs_tools().into_iter().cloned());
+        tool_defs.extend(
+            self.tools
+                .context_tools()
+                .into_iter()
+                .filter(|def| def.function.name == "search_history")
+                .cloned(),
+        );
 
         if self.browser_available {
             tool_defs.extend(self.tools.browser_tools().into_iter().cloned());
         }
+        // Custom tools are offered whatever their category
+        for custom in self.tools.custom_tools() {
+            if !tool_defs
+                .iter()
+                .any(|def| def.function.name == custom.function.name)
+            {
+                tool_defs.push(custom.clone());
+            }
+        }
+
+        debug!(tools = tool_defs.len(), "Calling orchestrator");
+
+        let options = GenerateOptions {
+            temperature: Some(temperature),
+            timeout_secs: Some(self.config.agent.orchestrator_timeout_secs),
+            ..sampling_options(&self.config)
+        };
+
+        let models = &self.config.models;
+        self.with_fallback(
+            "orchestrator",
+            &models.orchestrator,
+            &models.alternatives.orchestrators,
+            |model| self.call_orchestrator(model, &messages, &tool_defs, options.clone()),
+        )
+        .await
+    }
+
+    /// Send one orchestrator request to `model`, streaming reasoning if enabled
+    ///
+    /// Models without native tool support get the tools described in the
+    /// prompt instead, via `PromptToolAdapter`.
+    async fn call_orchestrator(
+        &self,
+        model: String,
+        messages: &[Message],
+        tool_defs: &[ToolDefinition],
+        options: GenerateOptions,
+    ) -> Result<LLMResponse> {
+        if !capabilities(&model).supports_tools {
+            debug!(model = %model, "Model lacks native tools, describing them in the prompt");
+            let adapter = PromptToolAdapter::new(tool_defs);
+            let response = self
+                .call_orchestrator_native(model, &adapter.prepare(messages), &[], options)
+                .await?;
+            return Ok(adapter.parse_response(response));
+        }
+
+        self.call_orchestrator_native(model, messages, tool_defs, options)
+            .await
+    }
 
-        if self.config.agent.debug {
-            eprintln!("DEBUG: Calling orchestrator with {} tools", tool_defs.len());
+    /// Send one orchestrator request with native tool definitions
+    async fn call_orchestrator_native(
+        &self,
+        model: String,
+        messages: &[Message],
+        tool_defs: &[ToolDefinition],
+        options: GenerateOptions,
+    ) -> Result<LLMResponse> {
+        let options = Some(options);
+
+        if !self.config.streaming.orchestrator {
+            let response = self
+                .llm
+                .chat_with_tools(&model, messages, tool_defs, options)
+                .await?;
+            record_usage(&self.run_usage, &response);
+            return Ok(response);
         }
 
-        self.llm
-            .chat_with_tools(
-                &self.config.models.orchestrator,
-                &messages,
-                &tool_defs,
-                Some(GenerateOptions {
-                    temperature: Some(0.1), // Low temperature for tool selection
-                    ..Default::default()
+        // Stream the model's reasoning so users see it before tools fire
+        let streamed = Arc::new(AtomicBool::new(false));
+        let streamed_flag = streamed.clone();
+        let reporter = self.reporter.clone();
+
+        let response = self
+            .llm
+            .chat_with_tools_stream(
+                &model,
+                messages,
+                tool_defs,
+                options,
+                Box::new(move |token| {
+                    streamed_flag.store(true, Ordering::Relaxed);
+                    reporter.reasoning(token);
                 }),
             )
-            .await
+            .await?;
+
+        if streamed.load(Ordering::Relaxed) {
+            self.reporter.reasoning_done();
+        }
+
+        record_usage(&self.run_usage, &response);
+        Ok(response)
     }
 
     /// Execute tools and collect observations
     ///
-    /// Coding/context tools run in parallel for efficiency.
+    /// Coding/file/context tools run in parallel for efficiency.
     /// Browser tools run sequentially (required for proper page state).
     async fn execute_tools(&self, tool_calls: &[ToolCall]) -> Result<Vec<Observation>> {
+        use crate::core::ToolCategory;
+        use tokio::sync::Semaphore;
         use tokio::task::JoinSet;
 
-        // Separate browser tools from parallelizable tools
-        let (browser_calls, parallel_calls): (Vec<_>, Vec<_>) = tool_calls
+        let mut observations = Vec::with_capacity(tool_calls.len());
+
+        // Reject calls that can't run, telling the model why so it can fix them
+        let tool_calls: Vec<&ToolCall> = tool_calls
             .iter()
-            .partition(|call| self.is_browser_tool(&call.name));
+            .filter(|call| match self.tools.validate_call(call) {
+                Ok(()) => true,
+                Err(problem) => {
+                    observations.push(Observation::failure(
+                        &call.name,
+                        ObservationKind::ValidationError,
+                        format!("Invalid call to {}: {}", call.name, problem),
+                    ));
+                    false
+                }
+            })
+            .collect();
 
-        let mut observations = Vec::with_capacity(tool_calls.len());
+        // Separate browser tools from parallelizable tools
+        let (browser_calls, parallel_calls): (Vec<_>, Vec<_>) = tool_calls
+            .into_iter()
+            .partition(|call| self.is_browser_tool(&call.name));
 
-        // Execute parallelizable tools concurrently
+        // Execute parallelizable tools concurrently, at most
+        // `max_parallel_tools` at a time, tagging each task with its call
+        // index so results keep the order the model asked for
         if !parallel_calls.is_empty() {
-            let mut set: JoinSet<(String, std::result::Result<String, String>)> = JoinSet::new();
+            let mut set: JoinSet<(usize, Observation)> = JoinSet::new();
+            let permits = Arc::new(Semaphore::new(self.config.agent.max_parallel_tools.max(1)));
 
-            for tool_call in parallel_calls {
+            for (index, tool_call) in parallel_calls.into_iter().enumerate() {
                 let name = tool_call.name.clone();
-                let prompt = self.tools.build_coding_prompt(tool_call);
 
-                // Clone the Arc reference for the spawned task
+                // History search reads the conversation, so it's answered here
+                if name == "search_history" {
+                    let observation = Observation::from(
+                        self.tools
+                            .history_search_tool()
+                            .search(tool_call, self.conversation.get_history()),
+                    );
+                    set.spawn(async move { (index, observation) });
+                    continue;
+                }
+
+                // File system, system and custom tools run directly without
+                // the executor model
+                if self.tools.has_handler(&name)
+                    || matches!(
+                        self.tools.category(&name),
+                        Some(ToolCategory::FileSystem | ToolCategory::System)
+                    )
+                {
+                    let tools = self.tools.clone();
+                    let call = tool_call.clone();
+                    let permits = permits.clone();
+
+                    set.spawn(async move {
+                        let _permit = permits.acquire_owned().await;
+                        let observation = match tools.execute(&call).await {
+                            Ok(result) => Observation::from(result),
+                            Err(e) => Observation::from_error(&name, &e),
+                        };
+                        (index, observation)
+                    });
+                    continue;
+                }
+
+                let prompt = match self.tools.build_coding_prompt(tool_call) {
+                    Ok(prompt) => prompt,
+                    Err(e) => {
+                        set.spawn(async move { (index, Observation::from_error(&name, &e)) });
+                        continue;
+                    }
+                };
+
+                // Clone the Arc references for the spawned task
                 let llm = self.llm.clone();
-                let model = self.config.models.executor.clone();
+                let tools = self.tools.clone();
+                let call = tool_call.clone();
+                let models = &self.config.models;
+                let chain = self.fallback_chain(&models.executor, &models.alternatives.executors);
+                let usage = self.run_usage.clone();
+                let options = self.executor_options();
+                let permits = permits.clone();
 
                 set.spawn(async move {
+                    let _permit = permits.acquire_owned().await;
                     let messages = vec![crate::core::Message::user(&prompt)];
-                    match llm.chat(&model, &messages, None).await {
-                        Ok(resp) => (name, Ok(resp.content)),
-                        Err(e) => (name, Err(e.to_string())),
-                    }
+                    let response = call_with_fallback("executor", &chain, |model| {
+                        let (llm, messages, options) = (&llm, &messages, options.clone());
+                        async move { llm.chat(&model, messages, Some(options)).await }
+                    })
+                    .await;
+                    let output = match response {
+                        Ok(resp) => {
+                            record_usage(&usage, &resp);
+                            tools.finish_coding_output(&call, resp.content)
+                        }
+                        Err(e) => Err(e),
+                    };
+                    let observation = match output {
+                        Ok(output) => Observation::success(&name, output),
+                        Err(e) => Observation::from_error(&name, &e),
+                    };
+                    (index, observation)
                 });
             }
 
-            // Collect parallel results
+            // Collect parallel results, then restore call order
+            let mut results = Vec::new();
+            let mut panics = Vec::new();
             while let Some(result) = set.join_next().await {
                 match result {
-                    Ok((name, Ok(content))) => {
-                        observations.push(Observation::success(&name, content));
-                    }
-                    Ok((name, Err(e))) => {
-                        observations.push(Observation::error(&name, &e));
-                    }
+                    Ok(tagged) => results.push(tagged),
                     Err(e) => {
-                        observations.push(Observation::error(
+                        panics.push(Observation::failure(
                             "parallel_task",
+                            ObservationKind::ToolError,
                             format!("Task panic: {}", e),
                         ));
                     }
                 }
             }
+            results.sort_by_key(|(index, _)| *index);
+            observations.extend(results.into_iter().map(|(_, observation)| observation));
+            observations.extend(panics);
         }
 
         // Execute browser tools sequentially (page state dependent)
         for tool_call in browser_calls {
-            if self.config.agent.debug {
-                eprintln!("DEBUG: Executing browser tool: {}", tool_call.name);
-            }
+            debug!(tool = %tool_call.name, "Executing browser tool");
 
             match self.tools.execute(tool_call).await {
                 Ok(result) => {
                     observations.push(Observation::from(result));
                 }
                 Err(e) => {
-                    observations.push(Observation::error(&tool_call.name, e.to_string()));
+                    observations.push(Observation::from_error(&tool_call.name, &e));
                 }
             }
         }
@@ -361,6 +1284,16 @@ The system automatically handles the `@` prefix for you. DO NOT use descriptions
         Ok(observations)
     }
 
+    /// Describe the first tool call that can't be executed as given
+    fn find_invalid_tool_call(&self, tool_calls: &[ToolCall]) -> Option<String> {
+        tool_calls.iter().find_map(|call| {
+            self.tools
+                .validate_call(call)
+                .err()
+                .map(|problem| format!("previous call to {} was invalid: {}", call.name, problem))
+        })
+    }
+
     /// Check if a tool is a browser tool (requires sequential execution)
     fn is_browser_tool(&self, name: &str) -> bool {
         matches!(
@@ -368,81 +1301,135 @@ The system automatically handles the `@` prefix for you. DO NOT use descriptions
             "browser_url"
                 | "browser_click"
                 | "browser_fill"
+                | "browser_submit"
+                | "browser_select"
+                | "browser_hover"
+                | "browser_press"
+                | "browser_wait"
+                | "browser_list_tabs"
+                | "browser_switch_tab"
                 | "browser_snapshot"
                 | "browser_screenshot"
                 | "browser_close"
                 | "browser_get_text"
+                | "browser_get_attribute"
+                | "browser_extract_links"
         )
     }
 
     /// Synthesize a response from observations when max turns is reached
     async fn synthesize_from_observations(&self, state: &AgentLoopState) -> Result<String> {
         let synthesis_prompt = format!(
-            "Based on the following tool observations, provide a comprehensive answer:\n\n{}",
+            "Based on the following tool observations, provide a comprehensive answer. \
+             A step that timed out or was cancelled may work if tried again, so say so; \
+             one that failed or was an invalid call is a hard failure to report.\n\n{}",
             state.format_observations()
         );
 
         let messages = vec![Message::user(synthesis_prompt)];
+        let response = if self.config.streaming.executor {
+            let response = self.executor_chat_stream(&messages).await?;
+            *self.streamed_answer.lock().unwrap() = Some(response.content.clone());
+            response
+        } else {
+            self.executor_chat(&messages).await?
+        };
 
-        let response = self
-            .llm
-            .chat(
-                &self.config.models.executor,
-                &messages,
-                Some(GenerateOptions {
-                    temperature: Some(0.7),
-                    ..Default::default()
-                }),
-            )
-            .await?;
-
+        record_usage(&self.run_usage, &response);
         Ok(response.content)
     }
 
-    /// Call the executor model for code generation (non-streaming)
-    #[allow(dead_code)]
-    async fn call_executor(&self, prompt: &str) -> Result<String> {
-        if self.config.streaming.enabled {
-            // Use streaming for executor too
-            let messages = vec![Message::user(prompt)];
-
-            print!("\n"); // New line before streaming output
+    /// Send `messages` to the executor, with fallback models, passing the
+    /// answer to the reporter as it streams in
+    async fn executor_chat_stream(&self, messages: &[Message]) -> Result<LLMResponse> {
+        let streamed = Arc::new(AtomicBool::new(false));
+        let models = &self.config.models;
+        let response = self
+            .with_fallback(
+                "executor",
+                &models.executor,
+                &models.alternatives.executors,
+                |model| {
+                    let streamed = streamed.clone();
+                    let reporter = self.reporter.clone();
+                    async move {
+                        self.llm
+                            .chat_stream(
+                                &model,
+                                messages,
+                                Some(self.executor_options()),
+                                Box::new(move |token| {
+                                    streamed.store(true, Ordering::Relaxed);
+                                    reporter.reasoning(token);
+                                }),
+                            )
+                            .await
+                    }
+                },
+            )
+            .await;
 
-            let response = self
-                .llm
-                .chat_stream(
-                    &self.config.models.executor,
-                    &messages,
-                    Some(GenerateOptions {
-                        temperature: Some(0.7),
-                        ..Default::default()
-                    }),
-                    Box::new(|token| {
-                        print!("{}", token);
-                        let _ = io::stdout().flush();
-                    }),
-                )
-                .await?;
+        if streamed.load(Ordering::Relaxed) {
+            self.reporter.reasoning_done();
+        }
+        response
+    }
 
-            println!("\n"); // New line after streaming
-            Ok(response.content)
-        } else {
-            let messages = vec![Message::user(prompt)];
+    /// Send `messages` to the executor (non-streaming), with fallback models
+    async fn executor_chat(&self, messages: &[Message]) -> Result<LLMResponse> {
+        let models = &self.config.models;
+        self.with_fallback(
+            "executor",
+            &models.executor,
+            &models.alternatives.executors,
+            |model| async move {
+                self.llm
+                    .chat(&model, messages, Some(self.executor_options()))
+                    .await
+            },
+        )
+        .await
+    }
 
-            let response = self
-                .llm
-                .chat(
-                    &self.config.models.executor,
-                    &messages,
-                    Some(GenerateOptions {
-                        temperature: Some(0.7),
-                        ..Default::default()
-                    }),
-                )
-                .await?;
+    /// Generation options for executor calls
+    fn executor_options(&self) -> GenerateOptions {
+        GenerateOptions {
+            temperature: Some(self.config.models.executor_temperature),
+            stop: Some(self.config.models.executor_stop.clone()).filter(|s| !s.is_empty()),
+            timeout_secs: Some(self.config.agent.executor_timeout_secs),
+            ..sampling_options(&self.config)
+        }
+    }
 
-            Ok(response.content)
+    /// Models to try for a role, in order: the configured one, then (with
+    /// Ollama and fallback enabled) its alternatives
+    fn fallback_chain(&self, primary: &str, alternatives: &[String]) -> Vec<String> {
+        let mut chain = vec![primary.to_string()];
+        if self.config.provider == ProviderType::Ollama && self.config.models.enable_fallback {
+            for model in alternatives {
+                if !chain.contains(model) {
+                    chain.push(model.clone());
+                }
+            }
         }
+        chain
+    }
+
+    /// Run `call` with each model in the fallback chain until one is available
+    ///
+    /// See [`call_with_fallback`].
+    async fn with_fallback<F, Fut>(
+        &self,
+        role: &str,
+        primary: &str,
+        alternatives: &[String],
+        call: F,
+    ) -> Result<LLMResponse>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<LLMResponse>>,
+    {
+        call_with_fallback(role, &self.fallback_chain(primary, alternatives), call).await
     }
 
     /// Check if a tool is a coding tool (needs executor)
@@ -471,6 +1458,109 @@ The system automatically handles the `@` prefix for you. DO NOT use descriptions
         self.config.models.orchestrator = model.into();
     }
 
+    /// Ask for confirmation over `sender` before each shell command runs
+    pub fn set_command_confirmation(
+        &mut self,
+        sender: tokio::sync::mpsc::UnboundedSender<ConfirmRequest>,
+    ) {
+        self.tools.set_command_confirmation(sender.clone());
+        self.command_confirmation = Some(sender);
+    }
+
+    /// Add a tool implemented by `handler` (see
+    /// [`ToolRegistry::register_handler`])
+    ///
+    /// The orchestrator is offered it from the next request on, and it
+    /// survives config reloads.
+    pub fn register_tool<F>(
+        &mut self,
+        definition: ToolDefinition,
+        category: ToolCategory,
+        handler: F,
+    ) where
+        F: for<'a> Fn(&'a ToolCall) -> ToolFuture<'a> + Send + Sync + 'static,
+    {
+        self.custom_tools
+            .push((definition, category, Arc::new(handler)));
+        self.tools = Arc::new(self.build_registry(&self.config));
+    }
+
+    /// A tool registry for `config`, with this agent's command confirmation
+    /// and custom tools
+    fn build_registry(&self, config: &Config) -> ToolRegistry {
+        let mut tools = build_tools(config);
+        if let Some(ref sender) = self.command_confirmation {
+            tools.set_command_confirmation(sender.clone());
+        }
+        for (definition, category, handler) in &self.custom_tools {
+            tools.register_shared_handler(definition.clone(), *category, handler.clone());
+        }
+        tools
+    }
+
+    /// Replace the configuration, rebuilding the LLM client and tools
+    ///
+    /// Conversation history is kept. Rolls back if the new provider can't
+    /// be created or fails to initialize.
+    pub async fn apply_config(&mut self, mut config: Config) -> Result<()> {
+        config.check_workdir()?;
+        let llm = create_provider(&config).await?;
+        let tools = self.build_registry(&config);
+
+        let previous_config = std::mem::replace(&mut self.config, config);
+        let previous_llm = std::mem::replace(&mut self.llm, llm);
+        let previous_tools = std::mem::replace(&mut self.tools, Arc::new(tools));
+
+        if let Err(e) = self.initialize().await {
+            self.config = previous_config;
+            self.llm = previous_llm;
+            self.tools = previous_tools;
+            return Err(e);
+        }
+
+        if let Some(ref prompt) = self.config.agent.system_prompt {
+            self.conversation.set_system_prompt(prompt.clone());
+        }
+        // Without an explicit workdir the old root (e.g. from --cwd) stays
+        if let Some(ref workdir) = self.config.agent.workdir {
+            self.workdir = workdir.clone();
+        }
+        crate::core::logging::set_debug(self.config.agent.debug);
+        Ok(())
+    }
+
+    /// Switch to another LLM provider and re-initialize
+    ///
+    /// Fails without changing anything if the provider's prerequisites (API
+    /// keys, auth) are missing; rolls back if initialization fails.
+    pub async fn set_provider(&mut self, provider: ProviderType) -> Result<()> {
+        if let Some(missing) = self.config.missing_provider_prerequisite(&provider) {
+            return Err(PraxisError::config(format!(
+                "Can't switch to {}: {}",
+                provider.name(),
+                missing
+            )));
+        }
+
+        let previous = std::mem::replace(&mut self.config.provider, provider);
+        let result = match create_provider(&self.config).await {
+            Ok(llm) => {
+                let previous_llm = std::mem::replace(&mut self.llm, llm);
+                let result = self.initialize().await;
+                if result.is_err() {
+                    self.llm = previous_llm;
+                }
+                result
+            }
+            Err(e) => Err(e),
+        };
+
+        if result.is_err() {
+            self.config.provider = previous;
+        }
+        result
+    }
+
     /// Set the executor model
     pub fn set_executor_model(&mut self, model: impl Into<String>) {
         self.config.models.executor = model.into();
@@ -486,19 +1576,36 @@ The system automatically handles the `@` prefix for you. DO NOT use descriptions
         self.browser_available
     }
 
-    /// Check if streaming is enabled
+    /// The agent-browser version found at initialization
+    pub fn browser_version(&self) -> Option<Version> {
+        self.browser_version
+    }
+
+    /// How the browser session was attached at initialization, if
+    /// `browser.reuse_session` is on
+    pub fn browser_session(&self) -> Option<&SessionStart> {
+        self.browser_session.as_ref()
+    }
+
+    /// Why agent-browser is installed but unusable, if it is
+    pub fn browser_problem(&self) -> Option<&str> {
+        self.browser_problem.as_deref()
+    }
+
+    /// Check if streaming is enabled for either role
     pub fn is_streaming(&self) -> bool {
-        self.config.streaming.enabled
+        self.config.streaming.orchestrator || self.config.streaming.executor
     }
 
-    /// Enable or disable streaming
+    /// Enable or disable streaming for both roles
     pub fn set_streaming(&mut self, enabled: bool) {
-        self.config.streaming.enabled = enabled;
+        self.config.set_streaming(enabled);
     }
 
     /// Enable debug mode
     pub fn set_debug(&mut self, debug: bool) {
         self.config.agent.debug = debug;
+        crate::core::logging::set_debug(debug);
     }
 
     /// List available models
@@ -506,8 +1613,1174 @@ The system automatically handles the `@` prefix for you. DO NOT use descriptions
         self.llm.list_models().await
     }
 
+    /// The account or project the provider is signed in as
+    pub async fn account_info(&self) -> Result<String> {
+        self.llm.account_info().await
+    }
+
+    /// List available models, skipping the provider's cached list
+    pub async fn refresh_models(&self) -> Result<Vec<String>> {
+        self.llm.refresh_models().await
+    }
+
+    /// Download a model, reporting progress through `on_progress`
+    pub async fn pull_model(&self, model: &str, on_progress: PullProgressCallback) -> Result<()> {
+        self.llm.pull_model_with_progress(model, on_progress).await
+    }
+
     /// Save current configuration to file
     pub fn save_config(&self) -> Result<std::path::PathBuf> {
         self.config.save_and_get_path()
     }
+
+    /// Save just the settings named by `keys` (e.g. `models.executor`) to
+    /// the config file; see [`Config::save_keys`]
+    pub fn save_settings(&self, keys: &[&str]) -> Result<std::path::PathBuf> {
+        self.config.save_keys(keys)
+    }
+}
+
+/// A tool registry set up from `config`
+fn build_tools(config: &Config) -> ToolRegistry {
+    let mut tools = if config.browser.enabled {
+        ToolRegistry::with_browser(&config.browser.session_name)
+    } else {
+        ToolRegistry::new()
+    };
+    tools.set_max_file_bytes(config.agent.max_file_bytes);
+    tools.set_allow_shell(config.agent.allow_shell);
+    tools.set_browser_timeout(std::time::Duration::from_millis(config.browser.timeout_ms));
+    tools.set_snapshot_diffs(config.browser.snapshot_diffs);
+    tools.set_max_links(config.browser.max_links);
+    tools.set_workdir(config.workdir());
+    tools
+}
+
+/// Strip markdown code fences from a response, returning just the code
+///
+/// A response that is entirely one fenced block, or that contains exactly
+/// one fenced block amid prose, yields that block's contents without the
+/// fence or language hint. Anything else is returned unchanged.
+pub fn strip_code_fences(response: &str) -> String {
+    let mut blocks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in response.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+        match current.take() {
+            None if is_fence => current = Some(Vec::new()),
+            None => {}
+            Some(block) if is_fence => blocks.push(block.join("\n")),
+            Some(mut block) => {
+                block.push(line);
+                current = Some(block);
+            }
+        }
+    }
+
+    // An unclosed fence or several blocks: leave it to the reader
+    if current.is_some() || blocks.len() != 1 {
+        return response.to_string();
+    }
+
+    blocks.remove(0)
+}
+
+/// Build the prompt asking the model to explain a sequence of tool calls
+fn build_why_prompt(user_input: &str, tool_calls: &[ToolCall]) -> String {
+    let calls = tool_calls
+        .iter()
+        .enumerate()
+        .map(|(i, call)| format!("{}. {} {}", i + 1, call.name, call.arguments))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "An AI agent was given this request:\n\n{}\n\n\
+         It made these tool calls, in order:\n\n{}\n\n\
+         In plain language, briefly explain why each tool was likely chosen \
+         and how the calls build on each other.",
+        user_input, calls
+    )
+}
+
+/// Run `call` with each model in `chain` until one is available
+///
+/// Only missing or unloadable models move on to the next candidate; any
+/// other error is returned as is. The response's `model` names the model
+/// that answered.
+async fn call_with_fallback<F, Fut>(
+    role: &str,
+    chain: &[String],
+    mut call: F,
+) -> Result<LLMResponse>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<LLMResponse>>,
+{
+    let mut last_error = None;
+
+    for (i, model) in chain.iter().enumerate() {
+        match call(model.clone()).await {
+            Ok(response) => return Ok(response),
+            Err(e) if e.is_model_unavailable() && i + 1 < chain.len() => {
+                warn!(
+                    role,
+                    model = %model,
+                    next = %chain[i + 1],
+                    error = %e,
+                    "Model unavailable, falling back"
+                );
+                last_error = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(last_error
+        .unwrap_or_else(|| PraxisError::ModelNotFound(chain.first().cloned().unwrap_or_default())))
+}
+
+/// Generation options carrying the configured sampling settings
+fn sampling_options(config: &Config) -> GenerateOptions {
+    let ollama = &config.providers.ollama;
+    GenerateOptions {
+        num_ctx: ollama.num_ctx,
+        top_p: ollama.top_p,
+        top_k: ollama.top_k,
+        repeat_penalty: ollama.repeat_penalty,
+        seed: ollama.seed,
+        ..Default::default()
+    }
+}
+
+/// Add a response's token usage to a run total
+fn record_usage(total: &Mutex<TokenUsage>, response: &LLMResponse) {
+    if let Ok(mut total) = total.lock() {
+        total.add(response.usage.as_ref());
+    }
+}
+
+/// Check whether an optional cancellation token has fired
+fn is_cancelled(cancel: &Option<CancellationToken>) -> bool {
+    cancel.as_ref().is_some_and(|c| c.is_cancelled())
+}
+
+/// Resolve when an optional cancellation token fires (never, if absent)
+async fn wait_for_cancel(cancel: &Option<CancellationToken>) {
+    match cancel {
+        Some(token) => token.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Opening of the orchestrator's system prompt, unless a prompt template
+/// replaces it
+const REACT_PREAMBLE: &str =
+    "You are an AI agent that uses tools to accomplish tasks. Follow the ReAct pattern:
+1. THINK about what you need to do.
+2. ACT by calling appropriate tools.
+3. OBSERVE the results and continue or provide final answer.";
+
+/// How much warmer the retry after an empty orchestrator response runs
+const EMPTY_RETRY_TEMPERATURE_STEP: f32 = 0.2;
+
+/// Assemble the orchestrator's message list
+///
+/// System messages from history are merged into the ReAct system prompt so
+/// only one system message is sent. The current user turn is expected to be
+/// the last history entry (it's added before the loop starts); if the window
+/// excluded it, it's appended. Observations from the current loop are
+/// attached to that final user message.
+fn build_orchestrator_messages(
+    system_prompt: String,
+    history: Vec<Message>,
+    user_input: &str,
+    observations: &str,
+) -> Vec<Message> {
+    let mut system_content = system_prompt;
+    let mut messages = vec![];
+
+    for message in history {
+        if message.role == "system" {
+            system_content.push_str("\n\n");
+            system_content.push_str(&message.content);
+        } else {
+            messages.push(message);
+        }
+    }
+
+    if !matches!(messages.last(), Some(m) if m.role == "user" && m.content == user_input) {
+        messages.push(Message::user(user_input));
+    }
+
+    if !observations.is_empty() {
+        if let Some(last) = messages.last_mut() {
+            last.content.push('\n');
+            last.content.push_str(observations);
+        }
+    }
+
+    messages.insert(0, Message::system(system_content));
+    messages
+}
+
+/// Base64 contents of the screenshots captured in a batch of observations
+fn screenshot_images(observations: &[Observation]) -> Vec<String> {
+    observations
+        .iter()
+        .filter(|obs| obs.success && obs.tool_name == "browser_screenshot")
+        .filter_map(|obs| obs.data.as_ref()?.get("path")?.as_str())
+        .filter_map(|path| match std::fs::read(path) {
+            Ok(bytes) => Some(BASE64.encode(bytes)),
+            Err(e) => {
+                debug!(path, error = %e, "Couldn't read screenshot to attach");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Appended to the prompt when the model announced an action without calling a tool
+const INTENT_NUDGE: &str = "\n\nYou described what you will do but didn't call a tool. \
+Emit the tool call now. Only reply with text if the task is already complete.";
+
+/// Sentence openings that announce an action rather than report a result
+const INTENT_PHRASES: &[&str] = &[
+    "i will now",
+    "i'll now",
+    "i will first",
+    "i'll first",
+    "i will start by",
+    "i'll start by",
+    "i'm going to",
+    "i am going to",
+    "next, i will",
+    "next, i'll",
+];
+
+/// Verbs that make a sentence opening with "let me" an announced action,
+/// unlike "Let me know if you need anything else"
+const LET_ME_VERBS: &[&str] = &[
+    "open", "check", "run", "read", "search", "look", "find", "fetch", "write", "create", "list",
+    "navigate", "click", "inspect", "try",
+];
+
+/// Check if a reply announces an action it didn't take (e.g. "Let me open the page")
+///
+/// Only the start of each sentence is checked.
+fn announces_action(content: &str) -> bool {
+    let content = content.to_lowercase().replace('\u{2019}', "'");
+    content
+        .split(['.', '!', '?', '\n'])
+        .map(str::trim_start)
+        .any(|sentence| {
+            INTENT_PHRASES
+                .iter()
+                .any(|phrase| sentence.starts_with(phrase))
+                || sentence.strip_prefix("let me ").is_some_and(|rest| {
+                    rest.split_whitespace()
+                        .next()
+                        .is_some_and(|verb| LET_ME_VERBS.contains(&verb))
+                })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::progress::NullReporter;
+    use crate::llm::StreamCallback;
+    use async_trait::async_trait;
+    use std::collections::VecDeque;
+
+    /// Provider that replays canned responses and records every request
+    struct MockProvider {
+        responses: Mutex<VecDeque<LLMResponse>>,
+        requests: Mutex<Vec<Vec<Message>>>,
+        /// Options passed with each call
+        options: Mutex<Vec<Option<GenerateOptions>>>,
+        /// Models that fail with `ModelNotFound`
+        unavailable: Vec<String>,
+    }
+
+    impl MockProvider {
+        fn new(responses: Vec<LLMResponse>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into()),
+                requests: Mutex::new(Vec::new()),
+                options: Mutex::new(Vec::new()),
+                unavailable: Vec::new(),
+            }
+        }
+
+        fn with_unavailable(mut self, models: &[&str]) -> Self {
+            self.unavailable = models.iter().map(|m| m.to_string()).collect();
+            self
+        }
+
+        fn next(&self, model: &str, messages: &[Message]) -> Result<LLMResponse> {
+            if self.unavailable.iter().any(|m| m == model) {
+                return Err(PraxisError::ModelNotFound(model.to_string()));
+            }
+            self.requests.lock().unwrap().push(messages.to_vec());
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| PraxisError::Other("no more mock responses".to_string()))
+                .map(|response| LLMResponse {
+                    model: model.to_string(),
+                    ..response
+                })
+        }
+    }
+
+    #[async_trait]
+    impl LLMProvider for MockProvider {
+        async fn chat(
+            &self,
+            model: &str,
+            messages: &[Message],
+            options: Option<GenerateOptions>,
+        ) -> Result<LLMResponse> {
+            self.options.lock().unwrap().push(options);
+            self.next(model, messages)
+        }
+
+        async fn chat_with_tools(
+            &self,
+            model: &str,
+            messages: &[Message],
+            _tools: &[ToolDefinition],
+            options: Option<GenerateOptions>,
+        ) -> Result<LLMResponse> {
+            self.options.lock().unwrap().push(options);
+            self.next(model, messages)
+        }
+
+        async fn chat_stream(
+            &self,
+            model: &str,
+            messages: &[Message],
+            options: Option<GenerateOptions>,
+            on_token: StreamCallback,
+        ) -> Result<LLMResponse> {
+            self.options.lock().unwrap().push(options);
+            let response = self.next(model, messages)?;
+            on_token(&response.content);
+            Ok(response)
+        }
+
+        async fn is_model_available(&self, _model: &str) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn list_models(&self) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        async fn pull_model(&self, _model: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "mock"
+        }
+    }
+
+    fn mock_response(content: &str, tool_calls: Vec<ToolCall>) -> LLMResponse {
+        LLMResponse {
+            content: content.to_string(),
+            tool_calls,
+            usage: None,
+            model: "mock".to_string(),
+        }
+    }
+
+    fn test_agent(provider: Arc<MockProvider>) -> Agent {
+        let mut config = Config::default();
+        config.browser.enabled = false;
+        config.set_streaming(false);
+        let mut agent = Agent::with_provider(config, provider);
+        agent.set_reporter(Arc::new(NullReporter));
+        agent
+    }
+
+    /// A test agent whose project root is `workdir`
+    fn test_agent_in(provider: Arc<MockProvider>, workdir: &std::path::Path) -> Agent {
+        let mut config = Config::default();
+        config.browser.enabled = false;
+        config.set_streaming(false);
+        config.agent.workdir = Some(workdir.to_path_buf());
+        let mut agent = Agent::with_provider(config, provider);
+        agent.set_reporter(Arc::new(NullReporter));
+        agent
+    }
+
+    /// Records progress events instead of printing them
+    #[derive(Default)]
+    struct RecordingReporter {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl ProgressReporter for RecordingReporter {
+        fn turn_started(&self, turn: usize, _max_turns: usize) {
+            self.events.lock().unwrap().push(format!("turn {}", turn));
+        }
+
+        fn reasoning(&self, token: &str) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("reasoning {}", token));
+        }
+
+        fn tool_result(&self, observation: &Observation) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("tool {}", observation.tool_name));
+        }
+
+        fn final_answer(&self, answer: &str, _turns: usize, _observations: usize) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("answer {}", answer));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progress_goes_to_reporter() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response(
+                "",
+                vec![ToolCall::new(
+                    "read_file",
+                    serde_json::json!({"path": "/nonexistent/praxis_progress.txt"}),
+                )],
+            ),
+            mock_response("Done.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider);
+        let reporter = Arc::new(RecordingReporter::default());
+        agent.set_reporter(reporter.clone());
+
+        agent.process("go").await.unwrap();
+
+        let events = reporter.events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec!["turn 1", "tool read_file", "turn 2", "answer Done."]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_budget_stops_loop() {
+        let mut first = mock_response(
+            "",
+            vec![ToolCall::new(
+                "read_file",
+                serde_json::json!({"path": "/nonexistent/praxis_budget.txt"}),
+            )],
+        );
+        first.usage = Some(TokenUsage {
+            prompt_tokens: 900,
+            completion_tokens: 200,
+            total_tokens: 1100,
+            partial: false,
+        });
+        let provider = Arc::new(MockProvider::new(vec![
+            first,
+            mock_response("Partial findings.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+        agent.config.agent.max_total_tokens = Some(1000);
+
+        let answer = agent.process("go").await.unwrap();
+
+        // The second request is the synthesis call, not another loop turn
+        assert_eq!(provider.requests.lock().unwrap().len(), 2);
+        assert!(answer.starts_with("Partial findings."));
+        assert!(answer.contains("Token budget of 1000 (1100 used) exceeded"));
+    }
+
+    /// Asks for a final answer as soon as a tool finishes
+    struct ForcingReporter(Arc<AtomicBool>);
+
+    impl ProgressReporter for ForcingReporter {
+        fn turn_started(&self, _turn: usize, _max_turns: usize) {}
+
+        fn tool_result(&self, _observation: &Observation) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+
+        fn final_answer(&self, _answer: &str, _turns: usize, _observations: usize) {}
+    }
+
+    #[tokio::test]
+    async fn test_forced_answer_is_reported() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response(
+                "",
+                vec![ToolCall::new(
+                    "read_file",
+                    serde_json::json!({"path": "/nonexistent/praxis_forced.txt"}),
+                )],
+            ),
+            mock_response("Best guess.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+        agent.set_reporter(Arc::new(ForcingReporter(agent.force_answer_handle())));
+
+        let answer = agent.process("go").await.unwrap();
+
+        // The second request is the synthesis call, not another loop turn
+        assert_eq!(provider.requests.lock().unwrap().len(), 2);
+        assert_eq!(answer, "Best guess.\n\n(Stopped early as requested.)");
+    }
+
+    #[tokio::test]
+    async fn test_synthesis_streams_when_executor_streams() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response(
+                "",
+                vec![ToolCall::new(
+                    "read_file",
+                    serde_json::json!({"path": "/nonexistent/praxis_synthesis.txt"}),
+                )],
+            ),
+            mock_response("Summary so far.", Vec::new()),
+        ]));
+        let reporter = Arc::new(RecordingReporter::default());
+        let mut agent = test_agent(provider);
+        agent.set_reporter(reporter.clone());
+        agent.config.agent.max_turns = 1;
+        agent.config.streaming.executor = true;
+
+        let answer = agent.process("go").await.unwrap();
+
+        assert_eq!(answer, "Summary so far.");
+        let events = reporter.events.lock().unwrap();
+        assert!(events.contains(&"reasoning Summary so far.".to_string()));
+        // Already shown, so nothing is left to print
+        assert_eq!(agent.unstreamed_part(&answer), "");
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_alternative_model() {
+        let provider = Arc::new(
+            MockProvider::new(vec![mock_response("Hello!", Vec::new())])
+                .with_unavailable(&["missing:8b", "also-missing"]),
+        );
+        let mut agent = test_agent(provider.clone());
+        agent.config.models.orchestrator = "missing:8b".to_string();
+        agent.config.models.alternatives.orchestrators =
+            vec!["also-missing".to_string(), "fallback:4b".to_string()];
+
+        let response = agent
+            .call_orchestrator_with_context("hi", &AgentLoopState::new(1), None)
+            .await
+            .unwrap();
+        assert_eq!(response.model, "fallback:4b");
+
+        agent.config.models.enable_fallback = false;
+        let err = agent
+            .call_orchestrator_with_context("hi", &AgentLoopState::new(1), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PraxisError::ModelNotFound(m) if m == "missing:8b"));
+    }
+
+    /// Provider that answers after a delay given in the prompt as `sleep <ms>`
+    #[derive(Default)]
+    struct SleepyProvider {
+        running: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LLMProvider for SleepyProvider {
+        async fn chat(
+            &self,
+            _model: &str,
+            messages: &[Message],
+            _options: Option<GenerateOptions>,
+        ) -> Result<LLMResponse> {
+            let prompt = &messages.last().unwrap().content;
+            let ms: u64 = prompt
+                .split("sleep ")
+                .nth(1)
+                .and_then(|rest| rest.split_whitespace().next())
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(mock_response(&format!("slept {}", ms), Vec::new()))
+        }
+
+        async fn chat_with_tools(
+            &self,
+            model: &str,
+            messages: &[Message],
+            _tools: &[ToolDefinition],
+            options: Option<GenerateOptions>,
+        ) -> Result<LLMResponse> {
+            self.chat(model, messages, options).await
+        }
+
+        async fn chat_stream(
+            &self,
+            model: &str,
+            messages: &[Message],
+            options: Option<GenerateOptions>,
+            _on_token: StreamCallback,
+        ) -> Result<LLMResponse> {
+            self.chat(model, messages, options).await
+        }
+
+        async fn is_model_available(&self, _model: &str) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn list_models(&self) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        async fn pull_model(&self, _model: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "sleepy"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_observations_keep_call_order() {
+        let mut config = Config::default();
+        config.browser.enabled = false;
+        let agent = Agent::with_provider(config, Arc::new(SleepyProvider::default()));
+
+        let calls: Vec<ToolCall> = [80, 0, 40]
+            .iter()
+            .map(|ms| {
+                ToolCall::new(
+                    "write_code",
+                    serde_json::json!({"task": format!("sleep {} then finish", ms), "language": "rust"}),
+                )
+            })
+            .collect();
+
+        let observations = agent.execute_tools(&calls).await.unwrap();
+        let outputs: Vec<&str> = observations.iter().map(|o| o.output.as_str()).collect();
+        assert_eq!(outputs, vec!["slept 80", "slept 0", "slept 40"]);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_tools_respect_concurrency_limit() {
+        let mut config = Config::default();
+        config.browser.enabled = false;
+        config.agent.max_parallel_tools = 2;
+        let provider = Arc::new(SleepyProvider::default());
+        let agent = Agent::with_provider(config, provider.clone());
+
+        let calls: Vec<ToolCall> = (0..6)
+            .map(|_| {
+                ToolCall::new(
+                    "write_code",
+                    serde_json::json!({"task": "sleep 20 then finish", "language": "rust"}),
+                )
+            })
+            .collect();
+
+        let observations = agent.execute_tools(&calls).await.unwrap();
+        assert_eq!(observations.len(), 6);
+        assert_eq!(provider.peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_strip_code_fences() {
+        let fenced = "```rust\nfn main() {\n    println!(\"hi\");\n}\n```";
+        assert_eq!(
+            strip_code_fences(fenced),
+            "fn main() {\n    println!(\"hi\");\n}"
+        );
+
+        let with_prose = "Here you go:\n\n```python\nprint(1)\n```\n\nEnjoy!";
+        assert_eq!(strip_code_fences(with_prose), "print(1)");
+
+        let two_blocks = "```\na\n```\n```\nb\n```";
+        assert_eq!(strip_code_fences(two_blocks), two_blocks);
+        assert_eq!(strip_code_fences("plain text"), "plain text");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_tool_call_is_repaired() {
+        let provider = Arc::new(MockProvider::new(vec![
+            // Missing the required "content" field
+            mock_response(
+                "",
+                vec![ToolCall::new(
+                    "write_file",
+                    serde_json::json!({"path": "notes.md"}),
+                )],
+            ),
+            mock_response("All done.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+
+        let answer = agent.process("write some notes").await.unwrap();
+        assert_eq!(answer, "All done.");
+
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let retry_prompt = &requests[1].last().unwrap().content;
+        assert!(
+            retry_prompt.contains("write_file was invalid: missing required argument 'content'")
+        );
+        // The invalid call was never executed
+        assert!(agent.last_tool_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_custom_tool_is_dispatched_to_handler() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response(
+                "",
+                vec![ToolCall::new("shout", serde_json::json!({"text": "hi"}))],
+            ),
+            mock_response("Shouted.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+        agent.register_tool(
+            ToolDefinition::function(
+                "shout",
+                "Upper-case some text",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {"text": {"type": "string"}},
+                    "required": ["text"]
+                }),
+            ),
+            ToolCategory::Coding,
+            |call| {
+                let text = call.get_string("text").unwrap_or_default();
+                Box::pin(async move {
+                    Ok(crate::core::ToolResult::success(
+                        "shout",
+                        text.to_uppercase(),
+                    ))
+                })
+            },
+        );
+
+        assert_eq!(agent.process("shout hi").await.unwrap(), "Shouted.");
+
+        let requests = provider.requests.lock().unwrap();
+        assert!(requests[1].iter().any(|m| m.content.contains("HI")));
+    }
+
+    #[tokio::test]
+    async fn test_announced_action_is_nudged_once() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response("I will now read the config file.", Vec::new()),
+            mock_response(
+                "",
+                vec![ToolCall::new(
+                    "read_file",
+                    serde_json::json!({"path": "missing.toml"}),
+                )],
+            ),
+            mock_response("Let me know if you need more.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+
+        let answer = agent.process("check the config").await.unwrap();
+        assert_eq!(answer, "Let me know if you need more.");
+
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1]
+            .last()
+            .unwrap()
+            .content
+            .contains("Emit the tool call now"));
+        assert_eq!(agent.last_tool_calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_stay_in_one_directory() {
+        let dir = std::env::temp_dir().join("praxis_sessions_dir");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut agent = test_agent_in(Arc::new(MockProvider::new(Vec::new())), &dir);
+        agent
+            .enable_persistence(dir.join(".praxis/session.json"))
+            .unwrap();
+        let first = agent.save_session("first").unwrap();
+        // Saving from inside a named session goes next to it, not below it
+        let second = agent.save_session("second").unwrap();
+        assert_eq!(first, dir.join(".praxis/sessions/first.json"));
+        assert_eq!(second, dir.join(".praxis/sessions/second.json"));
+        assert_eq!(agent.list_sessions().unwrap().len(), 2);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_session_sends_only_user_inputs() {
+        let dir = std::env::temp_dir().join("praxis_replay");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response("One.", Vec::new()),
+            mock_response("Two.", Vec::new()),
+            mock_response("Uno.", Vec::new()),
+            mock_response("Dos.", Vec::new()),
+        ]));
+        let mut agent = test_agent_in(provider.clone(), &dir);
+        agent
+            .enable_persistence(dir.join(".praxis/session.json"))
+            .unwrap();
+        agent.process("first").await.unwrap();
+        agent.process("second").await.unwrap();
+        agent.save_session("before").unwrap();
+        assert_eq!(
+            agent.session_inputs("before").unwrap(),
+            vec!["first", "second"]
+        );
+
+        let mut answers = Vec::new();
+        let count = agent
+            .replay_session("before", |_, input, result| {
+                answers.push(format!("{} -> {}", input, result.as_ref().unwrap()))
+            })
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(answers, vec!["first -> Uno.", "second -> Dos."]);
+
+        // The replay starts fresh and leaves the session alone
+        let requests = provider.requests.lock().unwrap();
+        assert!(!requests[2].iter().any(|m| m.content == "One."));
+        assert_eq!(agent.conversation_length(), 4);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_empty_response_is_retried_warmer() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response("", Vec::new()),
+            mock_response("Done.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+
+        assert_eq!(agent.process("hello").await.unwrap(), "Done.");
+
+        let options = provider.options.lock().unwrap();
+        let temperatures: Vec<f32> = options
+            .iter()
+            .map(|o| o.as_ref().unwrap().temperature.unwrap())
+            .collect();
+        assert_eq!(temperatures.len(), 2);
+        assert!(temperatures[1] > temperatures[0]);
+    }
+
+    #[tokio::test]
+    async fn test_apologizes_after_second_empty_response() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response("", Vec::new()),
+            mock_response("", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+
+        let answer = agent.process("hello").await.unwrap();
+        assert!(answer.contains("couldn't generate a response"));
+        assert_eq!(provider.requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_announces_action() {
+        assert!(announces_action("Let me search for that."));
+        assert!(announces_action("I\u{2019}m going to open the page"));
+        assert!(announces_action("Found it. Let me check the tests next."));
+        assert!(!announces_action("The answer is 42."));
+        assert!(!announces_action(
+            "The answer is 42. Let me know if you need anything else."
+        ));
+        assert!(!announces_action("Say \"I will now stop\" to end."));
+    }
+
+    #[tokio::test]
+    async fn test_tool_block_rules_only_without_native_tools() {
+        for (model, expected) in [("gemma3:4b", true), ("gemma2:9b", false)] {
+            let provider = Arc::new(MockProvider::new(vec![mock_response("Hi.", Vec::new())]));
+            let mut agent = test_agent(provider.clone());
+            agent.set_orchestrator_model(model);
+            agent.process("hello").await.unwrap();
+
+            let requests = provider.requests.lock().unwrap();
+            let system = &requests[0][0].content;
+            assert_eq!(system.contains(PROMPT_TOOL_RULES[0]), expected, "{}", model);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sub_agent_uses_agent_provider() {
+        let provider = Arc::new(MockProvider::new(vec![mock_response(
+            "Sub-agent answer.",
+            Vec::new(),
+        )]));
+        let mut agent = test_agent(provider.clone());
+
+        let answer = agent.spawn_sub_agent("helper", "do a thing").await.unwrap();
+        assert_eq!(answer, "Sub-agent answer.");
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].iter().any(|m| m.content == "do a thing"));
+        assert!(agent.sub_agents().get_agent("helper").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_chat_skips_tools_and_keeps_history() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response("Paris.", Vec::new()),
+            mock_response("About 2 million.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+
+        assert_eq!(agent.chat("Capital of France?").await.unwrap(), "Paris.");
+        assert_eq!(agent.chat("Population?").await.unwrap(), "About 2 million.");
+
+        let requests = provider.requests.lock().unwrap();
+        let second = &requests[1];
+        assert!(second.iter().any(|m| m.content == "Paris."));
+        assert_eq!(second.last().unwrap().content, "Population?");
+        assert_eq!(agent.conversation_length(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_saves_session_once() {
+        let dir = std::env::temp_dir().join("praxis_shutdown");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("session.json");
+
+        let mut agent = test_agent(Arc::new(MockProvider::new(Vec::new())));
+        agent.enable_persistence(path.clone()).unwrap();
+        agent.shutdown().await;
+        assert!(path.exists());
+
+        // A second call leaves things alone
+        std::fs::remove_file(&path).unwrap();
+        agent.shutdown().await;
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_executor_stop_sequences_are_forwarded() {
+        for streaming in [false, true] {
+            let provider = Arc::new(MockProvider::new(vec![
+                mock_response(
+                    "",
+                    vec![ToolCall::new(
+                        "write_code",
+                        serde_json::json!({"task": "write main", "language": "rust"}),
+                    )],
+                ),
+                mock_response("```rust\nfn main() {}\n```", Vec::new()),
+                mock_response("Done.", Vec::new()),
+            ]));
+            let mut agent = test_agent(provider.clone());
+            agent.config.set_streaming(streaming);
+            agent
+                .config
+                .models
+                .executor_stop
+                .push("\n```\n".to_string());
+
+            agent.process("write main").await.unwrap();
+
+            // The second call is the executor writing the code
+            let requests = provider.requests.lock().unwrap();
+            assert!(requests[1][0].content.contains("write main"));
+            let options = provider.options.lock().unwrap();
+            let stop = options[1].as_ref().unwrap().stop.as_ref().unwrap();
+            assert!(stop.contains(&"<|im_end|>".to_string()), "{}", streaming);
+            assert!(stop.contains(&"\n```\n".to_string()), "{}", streaming);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_code_uses_executor_temperature() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response(
+                "",
+                vec![ToolCall::new(
+                    "write_code",
+                    serde_json::json!({"task": "write main", "language": "rust"}),
+                )],
+            ),
+            mock_response("```rust\nfn main() {}\n```", Vec::new()),
+            mock_response("Done.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+        agent.config.models.executor_temperature = 0.15;
+
+        agent.process("write main").await.unwrap();
+
+        let options = provider.options.lock().unwrap();
+        assert_eq!(options[1].as_ref().unwrap().temperature, Some(0.15));
+    }
+
+    #[tokio::test]
+    async fn test_write_code_falls_back_to_alternative_executor() {
+        let provider = Arc::new(
+            MockProvider::new(vec![
+                mock_response(
+                    "",
+                    vec![ToolCall::new(
+                        "write_code",
+                        serde_json::json!({"task": "write main", "language": "rust"}),
+                    )],
+                ),
+                mock_response("```rust\nfn main() {}\n```", Vec::new()),
+                mock_response("Done.", Vec::new()),
+            ])
+            .with_unavailable(&["missing-coder"]),
+        );
+        let mut agent = test_agent(provider.clone());
+        agent.config.provider = ProviderType::Ollama;
+        agent.config.models.executor = "missing-coder".to_string();
+        agent.config.models.alternatives.executors = vec!["backup-coder".to_string()];
+
+        agent.process("write main").await.unwrap();
+
+        // The backup wrote the code and the orchestrator saw it
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        let observations = &requests[2].last().unwrap().content;
+        assert!(observations.contains("fn main() {}"));
+        assert!(!observations.contains("missing-coder"));
+    }
+
+    #[tokio::test]
+    async fn test_initialize_reports_empty_model_list() {
+        let mut agent = test_agent(Arc::new(MockProvider::new(Vec::new())));
+        agent.config.provider = ProviderType::Ollama;
+
+        let err = agent.initialize().await.unwrap_err();
+        assert!(matches!(err, PraxisError::NoModelsInstalled(_, _)));
+        assert!(err.to_string().contains("ollama pull"));
+    }
+
+    #[tokio::test]
+    async fn test_history_summarized_past_threshold() {
+        let provider = Arc::new(MockProvider::new(vec![
+            mock_response("first answer", Vec::new()),
+            mock_response("second answer", Vec::new()),
+            mock_response("User asked two questions.", Vec::new()),
+        ]));
+        let mut agent = test_agent(provider.clone());
+        agent.config.agent.summarize_history = true;
+        agent.config.agent.summarize_threshold = 3;
+        agent.config.agent.context_window = 1;
+
+        agent.process("first question").await.unwrap();
+        assert_eq!(agent.conversation.len(), 2);
+        agent.process("second question").await.unwrap();
+
+        // Everything but the latest message was condensed
+        assert_eq!(agent.conversation.len(), 1);
+        assert_eq!(
+            agent.conversation.summary(),
+            Some("User asked two questions.")
+        );
+        let messages = agent.conversation.get_messages();
+        assert!(messages[0].content.contains("User asked two questions."));
+        assert_eq!(messages.last().unwrap().content, "second answer");
+
+        let requests = provider.requests.lock().unwrap();
+        assert!(requests[2][0].content.contains("user: first question"));
+    }
+
+    #[test]
+    fn test_why_prompt_lists_calls_in_order() {
+        let calls = vec![
+            ToolCall::new(
+                "browser_url",
+                serde_json::json!({"url": "https://example.com"}),
+            ),
+            ToolCall::new("browser_click", serde_json::json!({"ref": "e3"})),
+        ];
+        let prompt = build_why_prompt("find the docs", &calls);
+
+        assert!(prompt.contains("find the docs"));
+        let first = prompt.find("1. browser_url").unwrap();
+        let second = prompt.find("2. browser_click").unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    fn test_orchestrator_messages_include_history() {
+        let history = vec![
+            Message::system("Be terse."),
+            Message::user("first question"),
+            Message::assistant("first answer"),
+            Message::user("follow up"),
+        ];
+
+        let messages = build_orchestrator_messages("ReAct".to_string(), history, "follow up", "");
+
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages.iter().filter(|m| m.role == "system").count(), 1);
+        assert!(messages[0].content.contains("ReAct"));
+        assert!(messages[0].content.contains("Be terse."));
+        assert_eq!(messages[1].content, "first question");
+        assert_eq!(messages[3].content, "follow up");
+    }
+
+    #[test]
+    fn test_orchestrator_messages_append_observations() {
+        let history = vec![Message::user("go")];
+
+        let messages = build_orchestrator_messages(
+            "ReAct".to_string(),
+            history,
+            "go",
+            "## Tool Observations:\nok",
+        );
+
+        assert_eq!(messages.len(), 2);
+        assert!(messages[1].content.starts_with("go\n"));
+        assert!(messages[1].content.contains("## Tool Observations:"));
+    }
+
+    #[test]
+    fn test_screenshot_observations_become_images() {
+        let path = std::env::temp_dir().join("praxis_test_screenshot.png");
+        std::fs::write(&path, b"png").unwrap();
+
+        let mut shot = Observation::success("browser_screenshot", "Saved");
+        shot.data = Some(serde_json::json!({"path": path, "full_page": false}));
+        let observations = vec![shot, Observation::success("read_file", "text")];
+
+        assert_eq!(screenshot_images(&observations), vec!["cG5n".to_string()]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_orchestrator_messages_without_history() {
+        let messages = build_orchestrator_messages("ReAct".to_string(), vec![], "hello", "");
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].role, "user");
+        assert_eq!(messages[1].content, "hello");
+    }
 }
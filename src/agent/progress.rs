@@ -0,0 +1,94 @@
+//! Progress reporting
+//!
+//! The agent reports what it is doing through a [`ProgressReporter`] so the
+//! CLI can show it while library users can silence or redirect it.
+
+use std::io::{self, Write};
+
+use crate::agent::loop_state::Observation;
+
+/// Receives progress events from the agent's reasoning loop
+pub trait ProgressReporter: Send + Sync {
+    /// The reasoning loop started
+    fn loop_started(&self, _max_turns: usize) {}
+
+    /// A turn started
+    fn turn_started(&self, turn: usize, max_turns: usize);
+
+    /// The orchestrator streamed part of its reasoning, or the executor part
+    /// of an answer synthesized from observations
+    fn reasoning(&self, _token: &str) {}
+
+    /// The stream passed to `reasoning` ended
+    fn reasoning_done(&self) {}
+
+    /// A batch of tool calls is about to run
+    fn tools_started(&self, _turn: usize, _count: usize) {}
+
+    /// A tool finished
+    fn tool_result(&self, observation: &Observation);
+
+    /// Any other status line (retries, synthesis, cancellation)
+    fn status(&self, _message: &str) {}
+
+    /// The loop produced its final answer
+    fn final_answer(&self, answer: &str, turns: usize, observations: usize);
+}
+
+/// Reports progress as plain text on stderr, keeping stdout for answers
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConsoleReporter;
+
+impl ProgressReporter for ConsoleReporter {
+    fn loop_started(&self, max_turns: usize) {
+        eprintln!(
+            "\n[Agent] Starting reasoning loop (max {} turns)",
+            max_turns
+        );
+    }
+
+    fn turn_started(&self, turn: usize, max_turns: usize) {
+        eprintln!("\n[Turn {}/{}] Analyzing...", turn, max_turns);
+    }
+
+    fn reasoning(&self, token: &str) {
+        eprint!("{}", token);
+        let _ = io::stderr().flush();
+    }
+
+    fn reasoning_done(&self) {
+        eprintln!();
+    }
+
+    fn tools_started(&self, turn: usize, count: usize) {
+        eprintln!("[Turn {}] Executing {} tool(s)...", turn, count);
+    }
+
+    fn tool_result(&self, observation: &Observation) {
+        let status = if observation.success { "✓" } else { "✗" };
+        eprintln!("  {} {} ", status, observation.tool_name);
+    }
+
+    fn status(&self, message: &str) {
+        eprintln!("{}", message);
+    }
+
+    fn final_answer(&self, _answer: &str, turns: usize, observations: usize) {
+        eprintln!(
+            "\n[Agent] Complete ({} turns, {} observations)",
+            turns, observations
+        );
+    }
+}
+
+/// Discards all progress, for embedded or silent use
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullReporter;
+
+impl ProgressReporter for NullReporter {
+    fn turn_started(&self, _turn: usize, _max_turns: usize) {}
+
+    fn tool_result(&self, _observation: &Observation) {}
+
+    fn final_answer(&self, _answer: &str, _turns: usize, _observations: usize) {}
+}
@@ -4,7 +4,8 @@
 
 use std::sync::Arc;
 
-use crate::core::{Config, Message, Result, ToolDefinition};
+use crate::agent::loop_state::{AgentLoopState, Observation, ObservationKind};
+use crate::core::{Config, Message, Result, ToolCall, ToolCategory, ToolDefinition};
 use crate::llm::{GenerateOptions, LLMProvider, OllamaClient};
 use crate::tools::ToolRegistry;
 
@@ -17,8 +18,10 @@ pub struct SubAgent {
     system_prompt: String,
     /// Which tool names this sub-agent can use (empty = all)
     allowed_tools: Vec<String>,
+    /// Whether this sub-agent may call tools at all
+    use_tools: bool,
     /// LLM client
-    llm: OllamaClient,
+    llm: Arc<dyn LLMProvider>,
     /// Model to use
     model: String,
     /// Tool registry
@@ -32,7 +35,8 @@ pub struct SubAgentBuilder {
     name: String,
     system_prompt: Option<String>,
     allowed_tools: Vec<String>,
-    llm: Option<OllamaClient>,
+    use_tools: bool,
+    llm: Option<Arc<dyn LLMProvider>>,
     model: Option<String>,
     tools: Option<Arc<ToolRegistry>>,
     max_turns: usize,
@@ -45,6 +49,7 @@ impl SubAgentBuilder {
             name: name.into(),
             system_prompt: None,
             allowed_tools: Vec::new(),
+            use_tools: true,
             llm: None,
             model: None,
             tools: None,
@@ -64,8 +69,14 @@ impl SubAgentBuilder {
         self
     }
 
-    /// Set the LLM client
-    pub fn llm(mut self, llm: OllamaClient) -> Self {
+    /// Answer from the task alone, without calling tools
+    pub fn without_tools(mut self) -> Self {
+        self.use_tools = false;
+        self
+    }
+
+    /// Set the LLM client (Ollama from the default config if not set)
+    pub fn llm(mut self, llm: Arc<dyn LLMProvider>) -> Self {
         self.llm = Some(llm);
         self
     }
@@ -101,9 +112,10 @@ impl SubAgentBuilder {
                 )
             }),
             allowed_tools: self.allowed_tools,
+            use_tools: self.use_tools,
             llm: self
                 .llm
-                .unwrap_or_else(|| OllamaClient::from_config(&config)),
+                .unwrap_or_else(|| Arc::new(OllamaClient::from_config(&config))),
             model: self.model.unwrap_or_else(|| config.models.executor.clone()),
             tools: self.tools.unwrap_or_else(|| Arc::new(ToolRegistry::new())),
             max_turns: self.max_turns,
@@ -129,26 +141,40 @@ impl SubAgent {
         &self.name
     }
 
-    /// Run the sub-agent on a task
-    pub async fn run(&self, task: &str) -> Result<String> {
-        let messages = vec![Message::system(&self.system_prompt), Message::user(task)];
+    /// Get the model this sub-agent uses
+    pub fn model(&self) -> &str {
+        &self.model
+    }
 
-        // Get tool definitions if we have any
-        let tool_defs: Vec<ToolDefinition> = if self.allowed_tools.is_empty() {
-            // Use all coding tools by default
+    /// Tool definitions this sub-agent may use
+    ///
+    /// With no `allowed_tools` this is the coding tools; otherwise it's every
+    /// registered tool named in `allowed_tools`.
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        if !self.use_tools {
+            Vec::new()
+        } else if self.allowed_tools.is_empty() {
             self.tools.coding_tools().into_iter().cloned().collect()
         } else {
-            // Filter to allowed tools
             self.tools
-                .coding_tools()
+                .all_definitions()
                 .into_iter()
                 .filter(|t| self.allowed_tools.contains(&t.function.name))
                 .cloned()
                 .collect()
-        };
+        }
+    }
+
+    /// Run the sub-agent on a task
+    ///
+    /// Runs a ReAct loop for up to `max_turns`: tool calls are executed and
+    /// their observations fed back until the model answers without tools.
+    pub async fn run(&self, task: &str) -> Result<String> {
+        let tool_defs = self.tool_definitions();
 
         if tool_defs.is_empty() {
             // No tools - just get a response
+            let messages = vec![Message::system(&self.system_prompt), Message::user(task)];
             let response = self
                 .llm
                 .chat(
@@ -161,9 +187,17 @@ impl SubAgent {
                 )
                 .await?;
 
-            Ok(response.content)
-        } else {
-            // With tools - do a tool-calling loop (simplified)
+            return Ok(response.content);
+        }
+
+        let mut state = AgentLoopState::new(self.max_turns);
+
+        while state.should_continue() {
+            let messages = vec![
+                Message::system(&self.system_prompt),
+                Message::user(format!("{}{}", task, state.format_observations())),
+            ];
+
             let response = self
                 .llm
                 .chat_with_tools(
@@ -177,8 +211,82 @@ impl SubAgent {
                 )
                 .await?;
 
-            // For now, just return the content (full loop would execute tools)
-            Ok(response.content)
+            if response.tool_calls.is_empty() {
+                state.final_answer = Some(response.content);
+                break;
+            }
+
+            for call in &response.tool_calls {
+                let observation = self.execute_tool(call, &tool_defs).await;
+                state.add_observations(vec![observation]);
+            }
+            state.next_turn();
+        }
+
+        if let Some(answer) = state.final_answer {
+            return Ok(answer);
+        }
+
+        // Out of turns - answer from what was observed
+        let messages = vec![
+            Message::system(&self.system_prompt),
+            Message::user(format!(
+                "{}{}\n\nUsing these observations, give your final answer now.",
+                task,
+                state.format_observations()
+            )),
+        ];
+        let response = self
+            .llm
+            .chat(
+                &self.model,
+                &messages,
+                Some(GenerateOptions {
+                    temperature: Some(0.7),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+
+        Ok(response.content)
+    }
+
+    /// Execute one tool call, refusing tools this sub-agent may not use
+    async fn execute_tool(&self, call: &ToolCall, allowed: &[ToolDefinition]) -> Observation {
+        if !allowed.iter().any(|t| t.function.name == call.name) {
+            return Observation::failure(
+                &call.name,
+                ObservationKind::ValidationError,
+                format!(
+                    "Tool '{}' is not available to sub-agent '{}'",
+                    call.name, self.name
+                ),
+            );
+        }
+
+        // Coding tools produce a prompt for the model rather than a result,
+        // unless they're custom tools with their own handler
+        if !self.tools.has_handler(&call.name)
+            && self.tools.category(&call.name) == Some(ToolCategory::Coding)
+        {
+            let prompt = match self.tools.build_coding_prompt(call) {
+                Ok(prompt) => prompt,
+                Err(e) => return Observation::from_error(&call.name, &e),
+            };
+            let messages = vec![Message::user(prompt)];
+            let output = match self.llm.chat(&self.model, &messages, None).await {
+                Ok(response) => self.tools.finish_coding_output(call, response.content),
+                Err(e) => Err(e),
+            };
+            return match output {
+                Ok(output) => Observation::success(&call.name, output),
+                Err(e) => Observation::from_error(&call.name, &e),
+            };
+        }
+
+        match self.tools.execute(call).await {
+            Ok(result) => Observation::from(result),
+            Err(e) => Observation::from_error(&call.name, &e),
         }
     }
 
@@ -234,6 +342,11 @@ impl SubAgentManager {
     pub fn get_agent(&self, name: &str) -> Option<&SubAgent> {
         self.agents.iter().find(|a| a.name() == name)
     }
+
+    /// Get all registered sub-agents
+    pub fn agents(&self) -> &[SubAgent] {
+        &self.agents
+    }
 }
 
 impl Default for SubAgentManager {
@@ -258,6 +371,57 @@ mod tests {
         assert_eq!(agent.max_turns, 3);
     }
 
+    #[tokio::test]
+    async fn test_disallowed_tool_returns_error_observation() {
+        let agent = SubAgent::builder("reader")
+            .allowed_tools(vec!["read_file".to_string()])
+            .build()
+            .unwrap();
+        let tool_defs = agent.tool_definitions();
+        assert_eq!(tool_defs.len(), 1);
+
+        let call = ToolCall::new(
+            "write_file",
+            serde_json::json!({"path": "x.txt", "content": "hi"}),
+        );
+        let observation = agent.execute_tool(&call, &tool_defs).await;
+        assert!(!observation.success);
+        assert!(observation.output.contains("not available"));
+    }
+
+    #[tokio::test]
+    async fn test_custom_coding_tool_runs_its_handler() {
+        let mut tools = ToolRegistry::new();
+        for name in ["shout", "echo"] {
+            tools.register_handler(
+                ToolDefinition::function(name, "Custom tool", serde_json::json!({})),
+                ToolCategory::Coding,
+                move |call| {
+                    let text = call.get_string("text").unwrap_or_default();
+                    Box::pin(async move {
+                        Ok(crate::core::ToolResult::success(name, text.to_uppercase()))
+                    })
+                },
+            );
+        }
+        let custom: Vec<&str> = tools
+            .custom_tools()
+            .iter()
+            .map(|t| t.function.name.as_str())
+            .collect();
+        assert_eq!(custom, vec!["echo", "shout"]);
+
+        let agent = SubAgent::builder("helper")
+            .tools(Arc::new(tools))
+            .build()
+            .unwrap();
+        let tool_defs = agent.tool_definitions();
+        let call = ToolCall::new("shout", serde_json::json!({"text": "hi"}));
+        let observation = agent.execute_tool(&call, &tool_defs).await;
+        assert!(observation.success);
+        assert_eq!(observation.output, "HI");
+    }
+
     #[test]
     fn test_subagent_manager() {
         let mut manager = SubAgentManager::new();
@@ -3,8 +3,11 @@
 //! Special commands that can be executed in the REPL.
 
 use crate::agent::Agent;
-use crate::core::Result;
-use crate::llm::models::{recommended_executors, recommended_orchestrators};
+use crate::cli::doctor;
+use crate::core::config::{ProviderType, CONFIG_TEMPLATE};
+use crate::core::{Config, PraxisError, Result};
+use crate::llm::models::{recommended_executors, recommended_orchestrators, ModelPreset};
+use crate::llm::TokenUsage;
 
 /// Result of parsing a command
 pub enum CommandResult {
@@ -38,7 +41,15 @@ pub async fn handle_command(input: &str, agent: &mut Agent) -> Result<CommandRes
         "help" | "?" => Ok(CommandResult::Handled(help_text())),
 
         "models" => {
-            let models = agent.list_models().await?;
+            let models = match args {
+                "" => agent.list_models().await?,
+                "--refresh" | "refresh" => agent.refresh_models().await?,
+                _ => {
+                    return Ok(CommandResult::Handled(
+                        "Usage: models [--refresh]".to_string(),
+                    ))
+                }
+            };
             let output = format!(
                 "Available models:\n{}\n\nCurrent:\n  Orchestrator: {}\n  Executor: {}",
                 models
@@ -54,21 +65,37 @@ pub async fn handle_command(input: &str, agent: &mut Agent) -> Result<CommandRes
 
         "set" => handle_set_command(args, agent).await,
 
+        "pull" => {
+            if args.is_empty() {
+                return Ok(CommandResult::Handled("Usage: pull <model>".to_string()));
+            }
+
+            agent
+                .pull_model(args, Box::new(render_pull_progress))
+                .await?;
+            println!();
+            Ok(CommandResult::Handled(format!("Pulled {}", args)))
+        }
+
         "status" => {
             let status = format!(
                 "Praxis Status:\n\
                  ─────────────────────────────\n\
+                 Provider:     {}\n\
                  Orchestrator: {}\n\
                  Executor:     {}\n\
                  Browser:      {}\n\
                  History:      {} messages\n\
                  Debug:        {}",
+                agent.config().provider.name(),
                 agent.config().models.orchestrator,
                 agent.config().models.executor,
-                if agent.has_browser() {
-                    "enabled"
-                } else {
-                    "disabled"
+                match agent.browser_version() {
+                    Some(version) if agent.has_browser() => {
+                        format!("enabled (agent-browser {})", version)
+                    }
+                    _ if agent.has_browser() => "enabled".to_string(),
+                    _ => "disabled".to_string(),
                 },
                 agent.conversation_length(),
                 if agent.config().agent.debug {
@@ -89,7 +116,132 @@ pub async fn handle_command(input: &str, agent: &mut Agent) -> Result<CommandRes
             )))
         }
 
-        "recommend" => Ok(CommandResult::Handled(recommend_models())),
+        "recommend" => {
+            let installed_only = matches!(args, "--installed-only" | "--installed" | "installed");
+            if !args.is_empty() && !installed_only {
+                return Ok(CommandResult::Handled(
+                    "Usage: recommend [--installed-only]".to_string(),
+                ));
+            }
+
+            let output = match agent.list_models().await {
+                Ok(installed) => recommend_models(&installed, installed_only),
+                Err(e) => format!(
+                    "Couldn't check installed models ({}).\n\n{}",
+                    e,
+                    recommend_models(&[], false)
+                ),
+            };
+            Ok(CommandResult::Handled(output))
+        }
+
+        "whoami" => {
+            let provider = agent.config().provider.name();
+            let output = match agent.account_info().await {
+                Ok(account) => format!("{}: {}", provider, account),
+                Err(e) => format!("{}: couldn't get account info ({})", provider, e),
+            };
+            Ok(CommandResult::Handled(output))
+        }
+
+        "doctor" => {
+            let checks = doctor::diagnose(agent.config()).await;
+            Ok(CommandResult::Handled(doctor::format_report(&checks)))
+        }
+
+        "spawn" => {
+            let mut parts = args.splitn(2, ' ');
+            let name = parts.next().unwrap_or("").trim();
+            let task = parts.next().unwrap_or("").trim();
+
+            if name.is_empty() || task.is_empty() {
+                return Ok(CommandResult::Handled(
+                    "Usage: spawn <name> <task>".to_string(),
+                ));
+            }
+
+            println!("[{}] Working...", name);
+            let result = agent.spawn_sub_agent(name, task).await?;
+            Ok(CommandResult::Handled(format!("[{}]\n{}", name, result)))
+        }
+
+        "index" => {
+            let report = agent.index_project().await?;
+            Ok(CommandResult::Handled(report.to_string()))
+        }
+
+        "agents" => {
+            let agents = agent.sub_agents().agents();
+            if agents.is_empty() {
+                return Ok(CommandResult::Handled(
+                    "No sub-agents yet. Use 'spawn <name> <task>' to create one.".to_string(),
+                ));
+            }
+
+            let output = format!(
+                "Sub-agents:\n{}",
+                agents
+                    .iter()
+                    .map(|a| format!("  - {} ({})", a.name(), a.model()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+            Ok(CommandResult::Handled(output))
+        }
+
+        "session" | "sessions" => handle_session_command(args, agent),
+
+        "replay" => {
+            let name = args.trim();
+            if name.is_empty() {
+                return Ok(CommandResult::Handled(
+                    "Usage: replay <session-name>".to_string(),
+                ));
+            }
+
+            let count = agent
+                .replay_session(name, |index, input, result| {
+                    println!("\n[{}] You: {}", index + 1, input);
+                    match result {
+                        Ok(response) => println!("\nAssistant:\n{}", response),
+                        Err(e) => eprintln!("\nError: {}", e),
+                    }
+                })
+                .await?;
+            Ok(CommandResult::Handled(format!(
+                "\nReplayed {} input{} from session '{}'",
+                count,
+                if count == 1 { "" } else { "s" },
+                name
+            )))
+        }
+
+        "config" => handle_config_command(args, agent).await,
+
+        "why" => Ok(CommandResult::Handled(agent.explain_last_run().await?)),
+
+        // Only meaningful while a request runs, where the REPL handles them
+        "/done" | "/answer" => Ok(CommandResult::Handled(
+            "Nothing is running. Type /done while the agent is working to get its answer right away."
+                .to_string(),
+        )),
+
+        // Slash-only, so ordinary prompts starting with "chat" still reach the agent
+        "/chat" => {
+            if args.is_empty() {
+                return Ok(CommandResult::Handled("Usage: /chat <message>".to_string()));
+            }
+            let answer = agent.chat(args).await?;
+            Ok(CommandResult::Handled(format!("\nAssistant:\n{}", answer)))
+        }
+
+        "tokens" => {
+            let mut output = format!("Session:  {}", format_usage(agent.session_usage()));
+            if let Some(usage) = agent.last_usage() {
+                output.push_str(&format!("\nLast run: {}", format_usage(&usage)));
+            }
+            Ok(CommandResult::Handled(output))
+        }
 
         _ => {
             // Not a command, treat as normal input
@@ -111,8 +263,9 @@ async fn handle_set_command(args: &str, agent: &mut Agent) -> Result<CommandResu
 
     if parts.is_empty() || parts[0].is_empty() {
         return Ok(CommandResult::Handled(
-            "Usage: set <orchestrator|executor|debug> <value>\n\
+            "Usage: set <provider|orchestrator|executor|debug> <value>\n\
              Examples:\n\
+               set provider openrouter\n\
                set orchestrator functiongemma\n\
                set executor gemma3:4b\n\
                set debug on"
@@ -124,6 +277,27 @@ async fn handle_set_command(args: &str, agent: &mut Agent) -> Result<CommandResu
     let value = parts.get(1).map(|s| s.trim()).unwrap_or("");
 
     match key.as_str() {
+        "provider" => {
+            if value.is_empty() {
+                return Ok(CommandResult::Handled(format!(
+                    "Current provider: {}",
+                    agent.config().provider.name()
+                )));
+            }
+            let Some(provider) = ProviderType::from_name(value) else {
+                return Ok(CommandResult::Handled(format!(
+                    "Unknown provider: {}. Available: ollama, openrouter, gemini, antigravity",
+                    value
+                )));
+            };
+
+            agent.set_provider(provider).await?;
+            Ok(CommandResult::Handled(format!(
+                "Provider set to: {}",
+                agent.config().provider.name()
+            )))
+        }
+
         "orchestrator" | "orch" => {
             if value.is_empty() {
                 return Ok(CommandResult::Handled(format!(
@@ -133,8 +307,9 @@ async fn handle_set_command(args: &str, agent: &mut Agent) -> Result<CommandResu
             }
             agent.set_orchestrator_model(value);
             Ok(CommandResult::Handled(format!(
-                "Orchestrator model set to: {}",
-                value
+                "Orchestrator model set to: {}{}",
+                value,
+                autosave(agent, "models.orchestrator")
             )))
         }
 
@@ -147,8 +322,9 @@ async fn handle_set_command(args: &str, agent: &mut Agent) -> Result<CommandResu
             }
             agent.set_executor_model(value);
             Ok(CommandResult::Handled(format!(
-                "Executor model set to: {}",
-                value
+                "Executor model set to: {}{}",
+                value,
+                autosave(agent, "models.executor")
             )))
         }
 
@@ -162,12 +338,245 @@ async fn handle_set_command(args: &str, agent: &mut Agent) -> Result<CommandResu
         }
 
         _ => Ok(CommandResult::Handled(format!(
-            "Unknown setting: {}. Available: orchestrator, executor, debug",
+            "Unknown setting: {}. Available: provider, orchestrator, executor, debug",
             key
         ))),
     }
 }
 
+/// Handle 'config' subcommands
+async fn handle_config_command(args: &str, agent: &mut Agent) -> Result<CommandResult> {
+    let path = agent.config().file_path();
+
+    match args.to_lowercase().as_str() {
+        "" | "show" => Ok(CommandResult::Handled(format!(
+            "# {}\n{}",
+            path.display(),
+            show_config(agent.config())?
+        ))),
+
+        "edit" => {
+            if !path.exists() {
+                write_config_template(&path)?;
+            }
+
+            let Some(mut editor) = editor_command() else {
+                return Ok(CommandResult::Handled(format!(
+                    "$EDITOR isn't set. Edit {} yourself, then run 'config reload'.",
+                    path.display()
+                )));
+            };
+            let program = editor.remove(0);
+            match std::process::Command::new(&program)
+                .args(editor)
+                .arg(&path)
+                .status()
+            {
+                Ok(status) if status.success() => reload_config(agent, &path).await,
+                Ok(status) => Ok(CommandResult::Handled(format!(
+                    "{} exited with {}; config not reloaded.",
+                    program, status
+                ))),
+                Err(e) => Ok(CommandResult::Handled(format!(
+                    "Couldn't run {} ({}). Edit {} yourself, then run 'config reload'.",
+                    program,
+                    e,
+                    path.display()
+                ))),
+            }
+        }
+
+        "reload" => reload_config(agent, &path).await,
+
+        _ => Ok(CommandResult::Handled(
+            "Usage: config [show|edit|reload]".to_string(),
+        )),
+    }
+}
+
+/// `$VISUAL` or `$EDITOR`, split into program and arguments
+fn editor_command() -> Option<Vec<String>> {
+    ["VISUAL", "EDITOR"].iter().find_map(|var| {
+        let words: Vec<String> = std::env::var(var)
+            .ok()?
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        (!words.is_empty()).then_some(words)
+    })
+}
+
+/// Start a config file at `path` from the commented template
+fn write_config_template(path: &std::path::Path) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, CONFIG_TEMPLATE)?;
+    Ok(())
+}
+
+/// Re-read the config files and apply them to the running agent
+///
+/// The project file and command-line settings are layered on top as at
+/// startup.
+async fn reload_config(agent: &mut Agent, path: &std::path::Path) -> Result<CommandResult> {
+    let applied = match agent.config().reload() {
+        Ok(config) => agent.apply_config(config).await,
+        Err(e) => Err(e),
+    };
+    Ok(CommandResult::Handled(match applied {
+        Ok(()) => format!("Reloaded {}", path.display()),
+        Err(e) => format!("Kept the previous config: {}", e),
+    }))
+}
+
+/// The config as TOML, with credentials hidden
+fn show_config(config: &Config) -> Result<String> {
+    const HIDDEN: &[&str] = &["api_key", "access_token", "refresh_token"];
+
+    let mut value = toml::Value::try_from(config)
+        .map_err(|e| PraxisError::config(format!("Failed to serialize config: {}", e)))?;
+    if let toml::Value::Table(sections) = &mut value {
+        for (_, section) in sections.iter_mut() {
+            let toml::Value::Table(section) = section else {
+                continue;
+            };
+            for (_, provider) in section.iter_mut() {
+                let toml::Value::Table(fields) = provider else {
+                    continue;
+                };
+                for key in HIDDEN {
+                    if let Some(secret) = fields.get_mut(*key) {
+                        *secret = toml::Value::String("[hidden]".to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    toml::to_string_pretty(&value)
+        .map_err(|e| PraxisError::config(format!("Failed to serialize config: {}", e)))
+}
+
+/// Handle 'session' subcommands
+fn handle_session_command(args: &str, agent: &mut Agent) -> Result<CommandResult> {
+    let mut parts = args.split_whitespace();
+    let sub = parts.next().unwrap_or("list").to_lowercase();
+    let name = parts.next().unwrap_or("");
+
+    match sub.as_str() {
+        "save" if !name.is_empty() => {
+            let path = agent.save_session(name)?;
+            Ok(CommandResult::Handled(format!(
+                "Saved session '{}' to {}",
+                name,
+                path.display()
+            )))
+        }
+
+        "load" if !name.is_empty() => {
+            let count = agent.load_session(name)?;
+            Ok(CommandResult::Handled(format!(
+                "Switched to session '{}' ({} messages)",
+                name, count
+            )))
+        }
+
+        "list" => {
+            let sessions = agent.list_sessions()?;
+            if sessions.is_empty() {
+                return Ok(CommandResult::Handled(
+                    "No saved sessions. Use 'session save <name>' to create one.".to_string(),
+                ));
+            }
+
+            let now = std::time::SystemTime::now();
+            let output = format!(
+                "Sessions:\n{}",
+                sessions
+                    .iter()
+                    .map(|s| format!(
+                        "  - {} ({} messages, {})",
+                        s.name,
+                        s.messages,
+                        s.modified
+                            .and_then(|m| now.duration_since(m).ok())
+                            .map(format_age)
+                            .unwrap_or_else(|| "unknown".to_string())
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+            Ok(CommandResult::Handled(output))
+        }
+
+        _ => Ok(CommandResult::Handled(
+            "Usage: session <save|load> <name>, or session list".to_string(),
+        )),
+    }
+}
+
+/// Format how long ago something happened, e.g. "5m ago"
+fn format_age(age: std::time::Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86_399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+/// Save the changed setting `key` if autosave is on, describing the outcome
+fn autosave(agent: &Agent, key: &str) -> String {
+    if !agent.config().agent.autosave {
+        return String::new();
+    }
+
+    match agent.save_settings(&[key]) {
+        Ok(path) => format!(" (saved to {})", path.display()),
+        Err(e) => format!(" (not saved: {})", e),
+    }
+}
+
+/// Draw a one-line progress bar for a model download
+pub(crate) fn render_pull_progress(status: &str, percent: Option<f32>) {
+    const WIDTH: usize = 30;
+
+    let line = match percent {
+        Some(percent) => {
+            let filled = ((percent / 100.0) * WIDTH as f32).round() as usize;
+            format!(
+                "[{}{}] {:5.1}%  {}",
+                "#".repeat(filled.min(WIDTH)),
+                "-".repeat(WIDTH - filled.min(WIDTH)),
+                percent,
+                status
+            )
+        }
+        None => status.to_string(),
+    };
+
+    // Pad to overwrite any longer previous line
+    print!("\r{:<80}", line);
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+}
+
+/// Format a token usage total for display
+fn format_usage(usage: &TokenUsage) -> String {
+    format!(
+        "{} tokens ({} prompt, {} completion){}",
+        usage.total_tokens,
+        usage.prompt_tokens,
+        usage.completion_tokens,
+        if usage.partial {
+            " - partial, some responses didn't report usage"
+        } else {
+            ""
+        }
+    )
+}
+
 /// Generate help text
 fn help_text() -> String {
     r#"Praxis Commands:
@@ -176,16 +585,44 @@ fn help_text() -> String {
   exit, quit, q    Exit Praxis
   clear, reset     Clear conversation history
   status           Show current configuration
+  doctor           Check Ollama, models, agent-browser and config
+  whoami           Show the account/project the provider is signed in as
   models           List available Ollama models
+                   (--refresh to skip the 30s cache)
   debug            Toggle debug mode
-  recommend        Show recommended models
+  recommend        Show recommended models, installed ones first
+                   (--installed-only to hide the rest)
+  tokens           Show token usage for this session
+  why              Explain the tools chosen for the last request
+  agents           List sub-agents spawned this session
+  index            Summarize key project files into the agent's notes
+                   (cached in .praxis/index.json)
+
+  /chat <message>            Ask the executor directly, without tools
+  spawn <name> <task>        Run a task on a named sub-agent
+  pull <model>               Download a model with progress
+
+  session save <name>        Save the conversation as a named session
+  session load <name>        Switch to a saved session
+  session list               List saved sessions
+  replay <name>              Re-run a saved session's inputs on a fresh
+                             history (pair with a seed to compare models)
 
+  config show                Show the current config (secrets hidden)
+  config edit                Open the config file in $EDITOR, then reload it
+  config reload              Re-read the config file
+
+  set provider <name>        Switch provider (ollama, openrouter,
+                             gemini, antigravity)
   set orchestrator <model>   Set the orchestrator model
   set executor <model>       Set the executor model
   set debug <on|off>         Enable/disable debug output
 
 Keyboard Shortcuts:
-  Ctrl+C           Cancel current operation
+  Ctrl+C           Cancel current operation (at the prompt: clear the line)
+  Up/Down          Recall earlier input (.praxis/history.txt)
+  Enter, /done     While the agent is working: stop exploring and
+                   answer from what it has found so far
   Ctrl+D           Exit Praxis
 
 Tips:
@@ -196,25 +633,108 @@ Tips:
         .to_string()
 }
 
-/// Generate model recommendations
-fn recommend_models() -> String {
+/// Generate model recommendations, marking which are in `installed`
+///
+/// Installed models are listed first; the rest get a pull hint, or are
+/// hidden when `installed_only` is set.
+fn recommend_models(installed: &[String], installed_only: bool) -> String {
     let mut output = String::from("Recommended Models:\n\n");
 
     output.push_str("Orchestrators (for function calling):\n");
-    for model in recommended_orchestrators() {
-        output.push_str(&format!(
-            "  {} ({})\n    {}\n",
-            model.name, model.parameters, model.description
-        ));
-    }
+    output.push_str(&recommend_section(
+        recommended_orchestrators(),
+        installed,
+        installed_only,
+    ));
 
     output.push_str("\nExecutors (for code generation):\n");
-    for model in recommended_executors() {
+    output.push_str(&recommend_section(
+        recommended_executors(),
+        installed,
+        installed_only,
+    ));
+
+    output
+}
+
+/// Format one group of presets for `recommend_models`
+fn recommend_section(
+    presets: Vec<ModelPreset>,
+    installed: &[String],
+    installed_only: bool,
+) -> String {
+    let mut presets: Vec<(ModelPreset, bool)> = presets
+        .into_iter()
+        .map(|p| {
+            let present = is_installed(&p.name, installed);
+            (p, present)
+        })
+        .filter(|(_, present)| *present || !installed_only)
+        .collect();
+    presets.sort_by_key(|(_, present)| !present);
+
+    if presets.is_empty() {
+        return "  (none installed)\n".to_string();
+    }
+
+    let mut output = String::new();
+    for (model, present) in presets {
+        let status = if present {
+            "installed"
+        } else {
+            "not installed"
+        };
         output.push_str(&format!(
-            "  {} ({})\n    {}\n",
-            model.name, model.parameters, model.description
+            "  {} ({}) - {}\n    {}\n",
+            model.name, model.parameters, status, model.description
         ));
+        if !present {
+            output.push_str(&format!("    Install: ollama pull {}\n", model.name));
+        }
     }
-
     output
 }
+
+/// Check if `model` is in the installed list, treating a missing tag as `:latest`
+pub(crate) fn is_installed(model: &str, installed: &[String]) -> bool {
+    let with_tag = |name: &str| {
+        if name.contains(':') {
+            name.to_string()
+        } else {
+            format!("{}:latest", name)
+        }
+    };
+    let wanted = with_tag(model);
+    installed.iter().any(|m| with_tag(m) == wanted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommend_marks_installed_first() {
+        let installed = vec!["gemma3:12b".to_string(), "functiongemma:latest".to_string()];
+        let output = recommend_models(&installed, false);
+
+        assert!(output.contains("functiongemma (2B) - installed"));
+        assert!(output.contains("Install: ollama pull gemma3:4b"));
+        assert!(!output.contains("Install: ollama pull gemma3:12b"));
+        assert!(output.find("gemma3:12b").unwrap() < output.find("gemma3:4b").unwrap());
+
+        let only = recommend_models(&installed, true);
+        assert!(!only.contains("not installed"));
+        assert!(only.contains("gemma3:12b"));
+    }
+
+    #[test]
+    fn test_show_config_hides_credentials() {
+        let mut config = Config::default();
+        config.providers.openrouter.api_key = Some("sk-or-secret".to_string());
+
+        let shown = show_config(&config).unwrap();
+        assert!(!shown.contains("sk-or-secret"));
+        assert!(shown.contains("api_key = \"[hidden]\""));
+        assert!(shown.contains(&format!("executor = \"{}\"", config.models.executor)));
+    }
+}
@@ -0,0 +1,193 @@
+//! Environment health check
+//!
+//! Backs the `doctor` command: checks the config file, the LLM provider,
+//! the configured models and agent-browser, with a hint for each failure.
+
+use crate::cli::commands::is_installed;
+use crate::core::config::ProviderType;
+use crate::core::{Config, PraxisError};
+use crate::llm::provider::create_provider;
+use crate::tools::browser::{BrowserExecutor, MIN_AGENT_BROWSER_VERSION};
+
+/// The outcome of one check
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// What was checked
+    pub name: String,
+    /// Whether it passed
+    pub ok: bool,
+    /// What was found
+    pub detail: String,
+    /// How to fix a failure
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: true,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: false,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Run every check against `config`
+pub async fn diagnose(config: &Config) -> Vec<Check> {
+    let mut checks = vec![check_config_file(config)];
+    checks.extend(check_provider(config).await);
+    checks.push(check_browser(config).await);
+    checks
+}
+
+/// Render checks as a ✓/✗ list, with hints under failures
+pub fn format_report(checks: &[Check]) -> String {
+    let mut output = String::from("Praxis Doctor:\n─────────────────────────────\n");
+    for check in checks {
+        let mark = if check.ok { "✓" } else { "✗" };
+        output.push_str(&format!("{} {}: {}\n", mark, check.name, check.detail));
+        if let Some(ref hint) = check.hint {
+            output.push_str(&format!("    → {}\n", hint));
+        }
+    }
+
+    let failures = checks.iter().filter(|c| !c.ok).count();
+    if failures == 0 {
+        output.push_str("\nEverything looks good.");
+    } else {
+        output.push_str(&format!(
+            "\n{} problem{} found.",
+            failures,
+            if failures == 1 { "" } else { "s" }
+        ));
+    }
+    output
+}
+
+/// The config files parse
+fn check_config_file(config: &Config) -> Check {
+    let path = config.file_path();
+    match Config::load_layered(&path, &Config::project_config_file(&config.workdir())) {
+        Ok(_) if path.exists() => Check::pass("Config", path.display().to_string()),
+        Ok(_) => Check::pass(
+            "Config",
+            format!("{} not found, using defaults", path.display()),
+        ),
+        Err(e) => Check::fail(
+            "Config",
+            e.to_string(),
+            format!(
+                "Fix the file, or delete {} to start from the defaults",
+                path.display()
+            ),
+        ),
+    }
+}
+
+/// The provider answers, and has the orchestrator and executor models
+async fn check_provider(config: &Config) -> Vec<Check> {
+    let name = config.provider.name();
+    let is_ollama = config.provider == ProviderType::Ollama;
+
+    if let Some(missing) = config.missing_provider_prerequisite(&config.provider) {
+        return vec![Check::fail(
+            name,
+            missing,
+            "Set it up, or switch provider with `set provider ollama`",
+        )];
+    }
+
+    let models = match create_provider(config).await {
+        Ok(llm) => llm.list_models().await,
+        Err(e) => Err(e),
+    };
+    let models = match models {
+        Ok(models) => models,
+        Err(e) if is_ollama => {
+            return vec![Check::fail(
+                "Ollama",
+                e.to_string(),
+                "Start it with `ollama serve`, or set OLLAMA_HOST",
+            )]
+        }
+        Err(e) => return vec![Check::fail(name, e.to_string(), "Check your credentials")],
+    };
+
+    let mut checks = vec![Check::pass(
+        name,
+        format!("reachable, {} models available", models.len()),
+    )];
+    for (role, model) in [
+        ("Orchestrator", &config.models.orchestrator),
+        ("Executor", &config.models.executor),
+    ] {
+        checks.push(if is_installed(model, &models) {
+            Check::pass(role, model.as_str())
+        } else if is_ollama {
+            Check::fail(
+                role,
+                format!("{} not installed", model),
+                format!("Run `ollama pull {}` (or `pull {}` here)", model, model),
+            )
+        } else {
+            Check::fail(
+                role,
+                format!("{} not offered by {}", model, name),
+                "Run `models` to see what's available",
+            )
+        });
+    }
+    checks
+}
+
+/// A supported agent-browser is installed, if browser tools are on
+async fn check_browser(config: &Config) -> Check {
+    if !config.browser.enabled {
+        return Check::pass("agent-browser", "not needed (browser tools disabled)");
+    }
+
+    match BrowserExecutor::check_version().await {
+        Ok(version) => Check::pass("agent-browser", format!("{} installed", version)),
+        Err(PraxisError::AgentBrowserNotFound) => Check::fail(
+            "agent-browser",
+            "not found on PATH",
+            "Run `npm install -g agent-browser && agent-browser install`, or start with --no-browser",
+        ),
+        Err(e) => Check::fail(
+            "agent-browser",
+            e.to_string(),
+            format!(
+                "Run `npm install -g agent-browser@latest` (needs {} or later), or start with --no-browser",
+                MIN_AGENT_BROWSER_VERSION
+            ),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report_shows_hints_for_failures() {
+        let checks = vec![
+            Check::pass("Config", "defaults"),
+            Check::fail("Executor", "gemma3:12b not installed", "Run `ollama pull`"),
+        ];
+        let report = format_report(&checks);
+
+        assert!(report.contains("✓ Config: defaults"));
+        assert!(report.contains("✗ Executor: gemma3:12b not installed\n    → Run `ollama pull`"));
+        assert!(report.ends_with("1 problem found."));
+    }
+}
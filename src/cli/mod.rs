@@ -3,6 +3,7 @@
 //! Contains the REPL and command parsing.
 
 pub mod commands;
+pub mod doctor;
 pub mod repl;
 
 pub use repl::Repl;
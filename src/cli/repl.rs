@@ -2,11 +2,22 @@
 //!
 //! Provides the main user interaction loop.
 
-use std::io::{self, BufRead, Write};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
-use crate::agent::Agent;
-use crate::cli::commands::{handle_command, CommandResult};
-use crate::core::{Config, Result};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::agent::{Agent, ConsoleReporter, Observation, ProgressReporter};
+use crate::cli::commands::{handle_command, render_pull_progress, CommandResult};
+use crate::core::config::ProviderType;
+use crate::core::{Config, PraxisError, Result};
+use crate::tools::browser::SessionStart;
+use crate::tools::system::ConfirmRequest;
 
 /// Interactive REPL (Read-Eval-Print Loop)
 pub struct Repl {
@@ -16,53 +27,174 @@ pub struct Repl {
 impl Repl {
     /// Create a new REPL with default configuration
     pub async fn new() -> Result<Self> {
-        Ok(Self {
-            agent: Agent::new().await?,
-        })
+        Self::with_agent(Agent::new().await?)
     }
 
     /// Create a REPL with custom configuration
     pub async fn with_config(config: Config) -> Result<Self> {
-        Ok(Self {
-            agent: Agent::with_config(config).await?,
-        })
+        Self::with_agent(Agent::with_config(config).await?)
+    }
+
+    fn with_agent(mut agent: Agent) -> Result<Self> {
+        if io::stderr().is_terminal() {
+            agent.set_reporter(Arc::new(ColorReporter));
+        }
+        Ok(Self { agent })
+    }
+
+    /// Initialize the agent, offering to pull any model that isn't installed
+    async fn initialize(&mut self) -> Result<()> {
+        let mut pulled = Vec::new();
+
+        loop {
+            let model = match self.agent.initialize().await {
+                Err(PraxisError::ModelNotFound(model))
+                    if io::stdin().is_terminal()
+                        && self.agent.config().provider == ProviderType::Ollama
+                        && !pulled.contains(&model) =>
+                {
+                    model
+                }
+                result => return result,
+            };
+
+            print!("\n\nModel {} not found. Pull it now? [y/N]: ", model);
+            io::stdout().flush()?;
+
+            let mut choice = String::new();
+            io::stdin().read_line(&mut choice)?;
+            if !matches!(choice.trim().to_lowercase().as_str(), "y" | "yes") {
+                return Err(PraxisError::ModelNotFound(model));
+            }
+
+            self.agent
+                .pull_model(&model, Box::new(render_pull_progress))
+                .await?;
+            println!("\nPulled {}.", model);
+            pulled.push(model);
+
+            print!("Initializing...");
+            io::stdout().flush()?;
+        }
+    }
+
+    /// Let the user choose models from those installed, then save the config
+    ///
+    /// Only runs when there's no config file yet and stdin is a terminal.
+    async fn first_run_setup(&mut self) -> Result<()> {
+        let config = self.agent.config();
+        if config.file_path().exists()
+            || config.provider != ProviderType::Ollama
+            || !io::stdin().is_terminal()
+        {
+            return Ok(());
+        }
+
+        let installed = match self.agent.list_models().await {
+            Ok(models) if !models.is_empty() => models,
+            // Nothing to choose from; initialization will explain what's missing
+            _ => return Ok(()),
+        };
+
+        println!("\nWelcome to Praxis! Choose the models to use (Enter keeps the default).\n");
+        println!("Installed models:");
+        for (i, model) in installed.iter().enumerate() {
+            println!("  {}. {}", i + 1, model);
+        }
+        println!();
+
+        let orchestrator = prompt_for_model(
+            "Orchestrator (function calling)",
+            &installed,
+            &self.agent.config().models.orchestrator,
+        )?;
+        let executor = prompt_for_model(
+            "Executor (code generation)",
+            &installed,
+            &self.agent.config().models.executor,
+        )?;
+
+        self.agent.set_orchestrator_model(orchestrator);
+        self.agent.set_executor_model(executor);
+        match self
+            .agent
+            .save_settings(&["models.orchestrator", "models.executor"])
+        {
+            Ok(path) => println!("Saved to {}\n", path.display()),
+            Err(e) => eprintln!("⚠️  Warning: Failed to save config: {}\n", e),
+        }
+        Ok(())
     }
 
     /// Run the REPL
     pub async fn run(&mut self) -> Result<()> {
+        self.first_run_setup().await?;
         self.print_banner();
+        // Without the banner, the summary line is all that's printed at startup
+        let quiet = !self.agent.config().agent.show_banner;
 
         // Initialize agent
-        print!("Initializing...");
-        io::stdout().flush()?;
+        if !quiet {
+            print!("Initializing...");
+            io::stdout().flush()?;
+        }
 
-        match self.agent.initialize().await {
-            Ok(()) => println!(" Ready!\n"),
-            Err(e) => {
-                println!("\n\n❌ Initialization Error: {}\n", e);
-                return Ok(());
-            }
+        if let Err(e) = self.initialize().await {
+            println!("\n\n❌ Initialization Error: {}\n", e);
+            return Ok(());
+        }
+        if !quiet {
+            println!(" Ready!\n");
         }
 
         // Enable session persistence
-        let cwd = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-        let session_path = cwd.join(".praxis").join("session.json");
+        let workdir = self.agent.workdir().to_path_buf();
+        let session_path = workdir.join(".praxis").join("session.json");
 
         // Notify user about session persistence
-        if session_path.exists() {
-            println!("📂 Loaded previous session from .praxis/session.json");
-        } else {
-            println!("💾 Session will be saved to .praxis/session.json");
+        if !quiet {
+            if session_path.exists() {
+                println!("📂 Loaded previous session from .praxis/session.json");
+            } else {
+                println!("💾 Session will be saved to .praxis/session.json");
+            }
         }
 
         if let Err(e) = self.agent.enable_persistence(session_path) {
             eprintln!("⚠️  Warning: Failed to enable session persistence: {}", e);
         }
 
+        let session_name = &self.agent.config().browser.session_name;
+        match self.agent.browser_session().filter(|_| !quiet) {
+            Some(SessionStart::Reused(url)) => {
+                println!("🌐 Reusing browser session '{}' at {}", session_name, url)
+            }
+            Some(SessionStart::Started) => {
+                println!("🌐 Started browser session '{}'", session_name)
+            }
+            None => {}
+        }
+
+        if self.agent.config().agent.index_on_start {
+            match self.agent.start_indexing() {
+                Ok(_) if !quiet => println!("📇 Indexing project files in the background"),
+                Ok(_) => {}
+                Err(e) => eprintln!("⚠️  Warning: Failed to start indexing: {}", e),
+            }
+        }
+
         // Check for agent-browser if enabled but not found
         if self.agent.config().browser.enabled && !self.agent.has_browser() {
-            println!("⚠️  agent-browser not found. Browser automation disabled.");
-            println!("   To enable: npm install -g agent-browser && agent-browser install");
+            match self.agent.browser_problem() {
+                Some(problem) => {
+                    println!("⚠️  {}", problem);
+                    println!("   Browser automation disabled.");
+                }
+                None => {
+                    println!("⚠️  agent-browser not found. Browser automation disabled.");
+                    println!("   To enable: npm install -g agent-browser && agent-browser install");
+                }
+            }
             print!("\nContinue without browser tools? [Y/n]: ");
             io::stdout().flush()?;
 
@@ -71,33 +203,42 @@ impl Repl {
             let choice = choice.trim().to_lowercase();
             if !choice.is_empty() && choice != "y" && choice != "yes" {
                 println!("Goodbye!");
+                self.agent.shutdown().await;
                 return Ok(());
             }
             println!();
         }
 
-        let stdin = io::stdin();
-        let mut stdout = io::stdout();
+        let mut lines = InputReader::spawn(workdir.join(".praxis").join("history.txt"));
+
+        // Shell commands requested by the model are confirmed at the prompt
+        let (confirm_tx, mut confirms) = mpsc::unbounded_channel::<ConfirmRequest>();
+        self.agent.set_command_confirmation(confirm_tx);
 
         loop {
-            // Print prompt
-            print!("You: ");
-            stdout.flush()?;
-
-            // Read input
-            let mut input = String::new();
-            match stdin.lock().read_line(&mut input) {
-                Ok(0) => {
+            // Read input. Without a line editor, Ctrl+C here is a signal
+            // rather than an interrupted read, and ends the session.
+            let input = tokio::select! {
+                input = lines.prompt("You: ") => input,
+                _ = tokio::signal::ctrl_c() => {
+                    println!("\nGoodbye!");
+                    break;
+                }
+            };
+            let input = match input {
+                None => {
                     // EOF (Ctrl+D)
                     println!("\nGoodbye!");
                     break;
                 }
-                Ok(_) => {}
-                Err(e) => {
+                Some(Ok(input)) => input,
+                // Ctrl+C at the prompt just clears the line
+                Some(Err(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Some(Err(e)) => {
                     eprintln!("Error reading input: {}", e);
                     continue;
                 }
-            }
+            };
 
             let input = input.trim();
 
@@ -121,10 +262,63 @@ impl Repl {
                 }
                 Ok(CommandResult::None) => continue,
                 Ok(CommandResult::Continue(input)) => {
-                    // Process as normal input
-                    match self.agent.process(&input).await {
+                    // Process as normal input. Ctrl+C cancels the run; Enter
+                    // (or /answer, /done) asks for an answer from what was found so far.
+                    let cancel = CancellationToken::new();
+                    let force_answer = self.agent.force_answer_handle();
+                    let result = {
+                        let process = self.agent.process_with_cancel(&input, Some(cancel.clone()));
+                        tokio::pin!(process);
+
+                        loop {
+                            tokio::select! {
+                                r = &mut process => break r,
+                                _ = tokio::signal::ctrl_c() => {
+                                    cancel.cancel();
+                                    break process.await;
+                                }
+                                Some(request) = confirms.recv() => {
+                                    print!("\n[Agent] Run `{}`? [y/N]: ", request.command);
+                                    let _ = io::stdout().flush();
+
+                                    let approved = tokio::select! {
+                                        line = lines.next_line() => line
+                                            .and_then(|l| l.ok())
+                                            .is_some_and(|l| matches!(l.trim().to_lowercase().as_str(), "y" | "yes")),
+                                        _ = tokio::signal::ctrl_c() => {
+                                            cancel.cancel();
+                                            false
+                                        }
+                                    };
+                                    let _ = request.reply.send(approved);
+                                }
+                                Some(line) = lines.next_line() => {
+                                    let line = line.unwrap_or_default();
+                                    if is_force_answer_input(&line) {
+                                        force_answer.store(true, Ordering::SeqCst);
+                                        println!("\n[Agent] Will answer after the current step...");
+                                    } else {
+                                        println!("\n[Agent] Busy. Press Enter or type /done to get an answer now, Ctrl+C to cancel.");
+                                    }
+                                }
+                            }
+                        }
+                    };
+
+                    match result {
                         Ok(response) => {
-                            println!("\nAssistant:\n{}\n", response);
+                            // A synthesized answer may already have streamed
+                            let unseen = self.agent.unstreamed_part(&response);
+                            if unseen.len() == response.len() {
+                                println!("\nAssistant:\n{}\n", response);
+                            } else if !unseen.is_empty() {
+                                println!("\n{}\n", unseen);
+                            } else {
+                                println!();
+                            }
+                        }
+                        Err(PraxisError::Cancelled) => {
+                            println!("Cancelled. Conversation history is unchanged.\n");
                         }
                         Err(e) => {
                             eprintln!("\nError: {}\n", e);
@@ -137,35 +331,299 @@ impl Repl {
             }
         }
 
+        self.agent.shutdown().await;
         Ok(())
     }
 
     /// Print the startup banner
     fn print_banner(&self) {
         let config = self.agent.config();
+        if !config.agent.show_banner {
+            println!("{}", summary_line(config));
+            return;
+        }
 
         println!(
             r#"
-╔═══════════════════════════════════════════════════════════╗
-║                                                           ║
-║   ██████╗ ██████╗  █████╗ ██╗  ██╗██╗███████╗             ║
-║   ██╔══██╗██╔══██╗██╔══██╗╚██╗██╔╝██║██╔════╝             ║
-║   ██████╔╝██████╔╝███████║ ╚███╔╝ ██║███████╗             ║
-║   ██╔═══╝ ██╔══██╗██╔══██║ ██╔██╗ ██║╚════██║             ║
-║   ██║     ██║  ██║██║  ██║██╔╝ ██╗██║███████║             ║
-║   ╚═╝     ╚═╝  ╚═╝╚═╝  ╚═╝╚═╝  ╚═╝╚═╝╚══════╝             ║
-║                                                           ║
-║   Offline-First AI Coding Agent                           ║
-║                                                           ║
-╚═══════════════════════════════════════════════════════════╝
++-----------------------------------------------------------+
+|                                                           |
+|   ____  ____      _    __  __ ___ ____                    |
+|  |  _ \|  _ \    / \   \ \/ /|_ _/ ___|                   |
+|  | |_) | |_) |  / _ \   \  /  | |\___ \                   |
+|  |  __/|  _ <  / ___ \  /  \  | | ___) |                  |
+|  |_|   |_| \_\/_/   \_\/_/\_\|___|____/                   |
+|                                                           |
+|   Offline-First AI Coding Agent                           |
+|                                                           |
++-----------------------------------------------------------+
 "#
         );
-        println!("Ollama:     {}", config.ollama_url());
-        println!("Models:");
-        println!("  Orchestrator: {}", config.models.orchestrator);
-        println!("  Executor:     {}", config.models.executor);
-        println!();
+        println!("{}", summary_line(config));
         println!("Commands: help, clear, models, status, exit");
-        println!("─────────────────────────────────────────────────────────────");
+        println!("-------------------------------------------------------------");
+    }
+}
+
+/// Progress reporter that colors turn headers and tool results
+struct ColorReporter;
+
+impl ColorReporter {
+    const DIM: &'static str = "\x1b[2m";
+    const CYAN: &'static str = "\x1b[36m";
+    const GREEN: &'static str = "\x1b[32m";
+    const RED: &'static str = "\x1b[31m";
+    const RESET: &'static str = "\x1b[0m";
+}
+
+impl ProgressReporter for ColorReporter {
+    fn loop_started(&self, max_turns: usize) {
+        ConsoleReporter.loop_started(max_turns);
+    }
+
+    fn turn_started(&self, turn: usize, max_turns: usize) {
+        eprintln!(
+            "\n{}[Turn {}/{}]{} Analyzing...",
+            Self::CYAN,
+            turn,
+            max_turns,
+            Self::RESET
+        );
+    }
+
+    fn reasoning(&self, token: &str) {
+        eprint!("{}{}{}", Self::DIM, token, Self::RESET);
+        let _ = io::stderr().flush();
+    }
+
+    fn reasoning_done(&self) {
+        ConsoleReporter.reasoning_done();
+    }
+
+    fn tools_started(&self, turn: usize, count: usize) {
+        ConsoleReporter.tools_started(turn, count);
+    }
+
+    fn tool_result(&self, observation: &Observation) {
+        let (color, status) = if observation.success {
+            (Self::GREEN, "✓")
+        } else {
+            (Self::RED, "✗")
+        };
+        eprintln!(
+            "  {}{}{} {}",
+            color,
+            status,
+            Self::RESET,
+            observation.tool_name
+        );
+    }
+
+    fn status(&self, message: &str) {
+        eprintln!("{}{}{}", Self::DIM, message, Self::RESET);
+    }
+
+    fn final_answer(&self, answer: &str, turns: usize, observations: usize) {
+        ConsoleReporter.final_answer(answer, turns, observations);
+    }
+}
+
+/// What the input thread should read next
+enum ReadRequest {
+    /// A line at the prompt, with line editing and history
+    Prompt(String),
+    /// A plain line, read while the agent is working so Ctrl+C still
+    /// reaches the cancel handler as a signal
+    Plain,
+}
+
+/// Reads stdin lines on a dedicated thread so input typed while the agent is
+/// working can be observed without stealing the next prompt's line.
+///
+/// The thread only reads when a line is asked for, so between lines stdin is
+/// free for child processes such as `config edit`'s editor. Ctrl+C at the
+/// prompt comes back as an `Interrupted` error; Ctrl+D closes the channel.
+struct InputReader {
+    lines: mpsc::UnboundedReceiver<io::Result<String>>,
+    wanted: std::sync::mpsc::Sender<ReadRequest>,
+    /// Whether the thread has been asked for a line it hasn't sent yet
+    pending: bool,
+}
+
+impl InputReader {
+    /// Start the thread, loading and saving prompt history at `history`
+    fn spawn(history: PathBuf) -> Self {
+        let (tx, lines) = mpsc::unbounded_channel();
+        let (wanted, requests) = std::sync::mpsc::channel::<ReadRequest>();
+
+        std::thread::spawn(move || {
+            let mut editor = DefaultEditor::new().ok();
+            if let Some(ref mut editor) = editor {
+                let _ = editor.load_history(&history);
+            }
+
+            while let Ok(request) = requests.recv() {
+                let result = match (request, editor.as_mut()) {
+                    (ReadRequest::Prompt(prompt), Some(editor)) => match editor.readline(&prompt) {
+                        Ok(line) => {
+                            if !line.trim().is_empty() {
+                                let _ = editor.add_history_entry(line.as_str());
+                                save_history(editor, &history);
+                            }
+                            Ok(line)
+                        }
+                        Err(ReadlineError::Eof) => break,
+                        Err(ReadlineError::Interrupted) => {
+                            Err(io::Error::from(io::ErrorKind::Interrupted))
+                        }
+                        Err(e) => Err(io::Error::other(e)),
+                    },
+                    (request, _) => {
+                        if let ReadRequest::Prompt(prompt) = request {
+                            print!("{}", prompt);
+                            let _ = io::stdout().flush();
+                        }
+                        let mut line = String::new();
+                        match io::stdin().lock().read_line(&mut line) {
+                            Ok(0) => break,
+                            Ok(_) => Ok(line),
+                            Err(e) => Err(e),
+                        }
+                    }
+                };
+                if tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            lines,
+            wanted,
+            pending: false,
+        }
+    }
+
+    /// Show `prompt` and read the user's next input
+    ///
+    /// If a plain read is still outstanding from the last run, that one
+    /// answers instead, without line editing.
+    async fn prompt(&mut self, prompt: &str) -> Option<io::Result<String>> {
+        if self.pending {
+            print!("{}", prompt);
+            let _ = io::stdout().flush();
+        }
+        self.read(ReadRequest::Prompt(prompt.to_string())).await
+    }
+
+    /// The next plain line, or `None` on EOF; safe to use in `select!`
+    async fn next_line(&mut self) -> Option<io::Result<String>> {
+        self.read(ReadRequest::Plain).await
+    }
+
+    async fn read(&mut self, request: ReadRequest) -> Option<io::Result<String>> {
+        if !self.pending {
+            self.pending = self.wanted.send(request).is_ok();
+        }
+        let line = self.lines.recv().await;
+        self.pending = false;
+        line
+    }
+}
+
+/// Write prompt history, creating its directory if needed
+fn save_history(editor: &mut DefaultEditor, path: &Path) {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(e) = editor.save_history(path) {
+        tracing::debug!(error = %e, "Failed to save prompt history");
+    }
+}
+
+/// One line naming the provider and models, shown at startup
+fn summary_line(config: &Config) -> String {
+    let provider = match config.provider {
+        ProviderType::Ollama => format!("{} ({})", config.provider.name(), config.ollama_url()),
+        _ => config.provider.name().to_string(),
+    };
+    format!(
+        "Provider: {} | Orchestrator: {} | Executor: {}",
+        provider, config.models.orchestrator, config.models.executor
+    )
+}
+
+/// Ask for a model by number or name, returning `default` on an empty answer
+fn prompt_for_model(label: &str, installed: &[String], default: &str) -> Result<String> {
+    loop {
+        print!("{} [{}]: ", label, default);
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer)? == 0 {
+            return Ok(default.to_string());
+        }
+        match choose_model(&answer, installed, default) {
+            Some(model) => return Ok(model),
+            None => println!(
+                "  Enter a number from 1 to {} or a model name.",
+                installed.len()
+            ),
+        }
+    }
+}
+
+/// Interpret a first-run answer: empty for `default`, a list number, or a name
+fn choose_model(answer: &str, installed: &[String], default: &str) -> Option<String> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Some(default.to_string());
+    }
+    match answer.parse::<usize>() {
+        Ok(n) => installed.get(n.checked_sub(1)?).cloned(),
+        Err(_) => Some(answer.to_string()),
+    }
+}
+
+/// Check if a line typed during a run asks for an immediate answer
+fn is_force_answer_input(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line == "/answer" || line == "/done"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_line() {
+        let mut config = Config::default();
+        config.models.orchestrator = "qwen3:8b".to_string();
+        config.models.executor = "gemma3:12b".to_string();
+        config.provider = ProviderType::OpenRouter;
+
+        assert_eq!(
+            summary_line(&config),
+            "Provider: openrouter | Orchestrator: qwen3:8b | Executor: gemma3:12b"
+        );
+    }
+
+    #[test]
+    fn test_choose_model() {
+        let installed = vec!["qwen3:8b".to_string(), "gemma3:12b".to_string()];
+
+        assert_eq!(
+            choose_model("\n", &installed, "qwen3:8b").unwrap(),
+            "qwen3:8b"
+        );
+        assert_eq!(
+            choose_model("2", &installed, "qwen3:8b").unwrap(),
+            "gemma3:12b"
+        );
+        assert_eq!(
+            choose_model(" llama3.2 ", &installed, "x").unwrap(),
+            "llama3.2"
+        );
+        assert!(choose_model("0", &installed, "x").is_none());
+        assert!(choose_model("3", &installed, "x").is_none());
     }
 }
@@ -3,14 +3,21 @@
 //! Supports environment variables, config files, and runtime overrides.
 //! Models are interchangeable via settings.
 //!
-//! Config file location: ~/.config/praxis/config.toml
+//! Config file location: ~/.config/praxis/config.toml, or the path in
+//! `PRAXIS_CONFIG` / `--config`.
+//!
+//! A project may also have `.praxis/config.toml` in its working directory.
+//! Precedence: project file > global file > env vars > defaults.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::agent::ObservationFormat;
 use crate::core::error::{PraxisError, Result};
+use crate::llm::PromptTemplate;
 
 /// Main configuration for Praxis
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,19 +35,124 @@ pub struct Config {
     /// Streaming configuration
     #[serde(default)]
     pub streaming: StreamingConfig,
+    /// File the global settings were loaded from and are saved to
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Command-line settings, re-applied when the config is reloaded
+    #[serde(skip)]
+    overrides: Overrides,
+}
+
+/// Settings given on the command line, which win over every config file
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    pub orchestrator: Option<String>,
+    pub executor: Option<String>,
+    pub debug: bool,
+    pub seed: Option<i64>,
+    pub no_stream: bool,
+    pub no_browser: bool,
+    pub headed: bool,
+    pub quiet: bool,
+    pub workdir: Option<PathBuf>,
 }
 
+impl Overrides {
+    /// Apply these settings on top of `config`
+    fn apply(&self, config: &mut Config) {
+        if let Some(ref orchestrator) = self.orchestrator {
+            config.models.orchestrator = orchestrator.clone();
+        }
+        if let Some(ref executor) = self.executor {
+            config.models.executor = executor.clone();
+        }
+        if self.debug {
+            config.agent.debug = true;
+        }
+        if self.seed.is_some() {
+            config.providers.ollama.seed = self.seed;
+        }
+        if self.no_stream {
+            config.set_streaming(false);
+        }
+        if self.no_browser {
+            config.browser.enabled = false;
+        }
+        if self.headed {
+            config.browser.headed = true;
+        }
+        if self.quiet {
+            config.agent.show_banner = false;
+        }
+        if self.workdir.is_some() {
+            config.agent.workdir = self.workdir.clone();
+        }
+    }
+}
+
+/// Written by `config edit` when there is no config file yet
+pub const CONFIG_TEMPLATE: &str = r#"# Praxis configuration
+#
+# Uncomment and change the settings you need; anything left out keeps its
+# default. Run `config show` in Praxis to see every setting.
+
+# provider = "ollama"
+
+# [models]
+# orchestrator = "qwen3-vl:8b"
+# executor = "qwen3:8b"
+
+# [providers.ollama]
+# host = "localhost"
+# port = 11434
+
+# [browser]
+# enabled = true
+# headed = false
+
+# [agent]
+# max_turns = 10
+# autosave = true
+"#;
+
 /// Type of LLM provider
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ProviderType {
     Ollama,
+    /// OpenRouter or any OpenAI-compatible endpoint
+    #[serde(alias = "openai")]
     OpenRouter,
     GoogleGeminiCli,
     GoogleAntigravity,
     Kolaborate,
 }
 
+impl ProviderType {
+    /// Parse the short name used on the command line (e.g. `gemini`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "ollama" => Some(Self::Ollama),
+            "openrouter" | "openai" | "openai-compatible" => Some(Self::OpenRouter),
+            "gemini" | "googlegeminicli" => Some(Self::GoogleGeminiCli),
+            "antigravity" | "googleantigravity" => Some(Self::GoogleAntigravity),
+            "kolaborate" => Some(Self::Kolaborate),
+            _ => None,
+        }
+    }
+
+    /// Short display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ollama => "ollama",
+            Self::OpenRouter => "openrouter",
+            Self::GoogleGeminiCli => "gemini",
+            Self::GoogleAntigravity => "antigravity",
+            Self::Kolaborate => "kolaborate",
+        }
+    }
+}
+
 /// Helper struct for provider-specific settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderConfig {
@@ -60,11 +172,68 @@ pub struct OllamaConfig {
     pub port: u16,
     /// Request timeout in seconds
     pub timeout_secs: u64,
+    /// Retries after a connection error or 5xx response (default: 3)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each further retry (default: 500)
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Context window size in tokens (default: the model's own)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_ctx: Option<u32>,
+    /// Nucleus sampling threshold
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Sample only from the k most likely tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Penalty applied to repeated tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat_penalty: Option<f32>,
+    /// Random seed; set it to make runs reproducible
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    /// How long the installed-model list is reused before asking the server
+    /// again; 0 disables caching (default: 30)
+    #[serde(default = "default_model_cache_secs")]
+    pub model_cache_secs: u64,
 }
 
+fn default_model_cache_secs() -> u64 {
+    30
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+
+/// OpenRouter, or any OpenAI-compatible endpoint (LM Studio, vLLM, LiteLLM)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenRouterConfig {
+    /// Bearer token; optional for local endpoints
     pub api_key: Option<String>,
+    /// API root, ending before `/chat/completions`
+    /// Default: https://openrouter.ai/api/v1
+    #[serde(default = "default_openrouter_base_url")]
+    pub base_url: String,
+}
+
+/// OpenRouter's own API root
+pub const OPENROUTER_BASE_URL: &str = "https://openrouter.ai/api/v1";
+
+fn default_openrouter_base_url() -> String {
+    OPENROUTER_BASE_URL.to_string()
+}
+
+impl OpenRouterConfig {
+    /// Whether requests go to OpenRouter itself rather than a custom gateway
+    pub fn is_openrouter(&self) -> bool {
+        self.base_url.trim_end_matches('/') == OPENROUTER_BASE_URL
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,6 +267,77 @@ pub struct ModelConfig {
     /// Alternative models that can be switched to
     #[serde(default)]
     pub alternatives: ModelAlternatives,
+    /// With Ollama, try the alternatives in order when a model is missing
+    /// or fails to load
+    /// Default: true
+    #[serde(default = "default_enable_fallback")]
+    pub enable_fallback: bool,
+    /// Sampling temperature for tool selection (0.0–2.0)
+    /// Default: 0.1
+    #[serde(default = "default_orchestrator_temperature")]
+    pub orchestrator_temperature: f32,
+    /// Sampling temperature for code generation and answers (0.0–2.0)
+    /// Default: 0.7
+    #[serde(default = "default_executor_temperature")]
+    pub executor_temperature: f32,
+    /// Stop sequences for code generation and answers
+    ///
+    /// These replace the model's own stops, so keep the turn delimiters and
+    /// append language-specific ones as needed.
+    #[serde(default = "default_executor_stop")]
+    pub executor_stop: Vec<String>,
+}
+
+fn default_enable_fallback() -> bool {
+    true
+}
+
+fn default_orchestrator_temperature() -> f32 {
+    0.1
+}
+
+fn default_executor_temperature() -> f32 {
+    0.7
+}
+
+/// End-of-turn markers of the common chat templates
+fn default_executor_stop() -> Vec<String> {
+    ["<|im_end|>", "<|endoftext|>", "<|eot_id|>", "<end_of_turn>"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Highest temperature accepted in the config
+const MAX_TEMPERATURE: f32 = 2.0;
+
+impl ModelConfig {
+    /// Pull out-of-range temperatures back into 0.0–2.0, with a warning
+    fn clamp_temperatures(&mut self) {
+        for (key, value) in [
+            (
+                "orchestrator_temperature",
+                &mut self.orchestrator_temperature,
+            ),
+            ("executor_temperature", &mut self.executor_temperature),
+        ] {
+            let clamped = if value.is_nan() {
+                0.0
+            } else {
+                value.clamp(0.0, MAX_TEMPERATURE)
+            };
+            if clamped != *value {
+                tracing::warn!(
+                    "models.{} = {} is outside 0.0-{}; using {}",
+                    key,
+                    value,
+                    MAX_TEMPERATURE,
+                    clamped
+                );
+                *value = clamped;
+            }
+        }
+    }
 }
 
 /// Alternative model configurations
@@ -120,6 +360,28 @@ pub struct BrowserConfig {
     pub headed: bool,
     /// Default timeout for browser operations in ms
     pub timeout_ms: u64,
+    /// After clicks, fills and similar actions, show the model only what
+    /// changed since the last snapshot; false returns full snapshots
+    /// Default: true
+    #[serde(default = "default_snapshot_diffs")]
+    pub snapshot_diffs: bool,
+    /// Most links `browser_extract_links` returns
+    /// Default: 100
+    #[serde(default = "default_max_links")]
+    pub max_links: usize,
+    /// At startup, reuse the page already open in the session, or start the
+    /// browser right away so the first browser tool doesn't wait for it
+    /// Default: false
+    #[serde(default)]
+    pub reuse_session: bool,
+}
+
+fn default_snapshot_diffs() -> bool {
+    true
+}
+
+fn default_max_links() -> usize {
+    crate::tools::browser::DEFAULT_MAX_LINKS
 }
 
 /// Agent behavior configuration
@@ -131,6 +393,21 @@ pub struct AgentConfig {
     /// Number of recent messages to include in context window
     /// Default: 20
     pub context_window: usize,
+    /// Estimated tokens of history to send the orchestrator; when set, the
+    /// most recent messages that fit are sent instead of `context_window`
+    /// messages
+    /// Default: none
+    #[serde(default)]
+    pub context_budget_tokens: Option<usize>,
+    /// Summarize old messages with the executor instead of dropping them
+    /// Default: false
+    #[serde(default)]
+    pub summarize_history: bool,
+    /// History length that triggers summarization of all but the last
+    /// `context_window` messages
+    /// Default: 100
+    #[serde(default = "default_summarize_threshold")]
+    pub summarize_threshold: usize,
     /// Maximum reasoning loop turns before stopping
     /// Default: 10
     pub max_turns: usize,
@@ -138,6 +415,141 @@ pub struct AgentConfig {
     pub debug: bool,
     /// System prompt prefix
     pub system_prompt: Option<String>,
+    /// Maximum size in bytes of a file the `read_file` tool will return
+    /// Default: 1048576 (1MB)
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Maximum characters of a failed tool's output included in prompts
+    /// Default: 300
+    #[serde(default = "default_max_failed_observation_chars")]
+    pub max_failed_observation_chars: usize,
+    /// Collapse failed observations to a one-line reference after one turn
+    /// Default: true
+    #[serde(default = "default_collapse_failed_observations")]
+    pub collapse_failed_observations: bool,
+    /// How tool observations are shown to the model: `markdown` sections or
+    /// a `json` array, which some models follow more reliably
+    /// Default: markdown
+    #[serde(default)]
+    pub observation_format: ObservationFormat,
+    /// Maximum bytes of a tool's output included in prompts; the middle is cut
+    /// Default: 8192
+    #[serde(default = "default_max_observation_bytes")]
+    pub max_observation_bytes: usize,
+    /// Times per turn to re-prompt the model after an invalid tool call
+    /// Default: 1
+    #[serde(default = "default_max_repair_retries")]
+    pub max_repair_retries: usize,
+    /// Before any tool has run, re-prompt once when the orchestrator
+    /// describes an action ("I will now...") instead of calling a tool
+    /// Default: true
+    #[serde(default = "default_nudge_on_intent")]
+    pub nudge_on_intent: bool,
+    /// Most non-browser tool calls run at once in one turn; browser tools
+    /// always run one at a time
+    /// Default: 3
+    #[serde(default = "default_max_parallel_tools")]
+    pub max_parallel_tools: usize,
+    /// Let the model run programs with the `run_command` tool
+    /// Default: false
+    #[serde(default)]
+    pub allow_shell: bool,
+    /// Strip markdown code fences from single-prompt (`-p`) answers
+    /// Default: false
+    #[serde(default)]
+    pub strip_code_fences: bool,
+    /// Project root for session storage, file tools and shell commands
+    /// Default: the directory praxis was started in
+    #[serde(default)]
+    pub workdir: Option<PathBuf>,
+    /// Save the global config file when models are changed with `set`
+    /// Default: true
+    #[serde(default = "default_autosave")]
+    pub autosave: bool,
+    /// Show the banner when the REPL starts; false prints just the provider
+    /// and models (also set by `--quiet`)
+    /// Default: true
+    #[serde(default = "default_show_banner")]
+    pub show_banner: bool,
+    /// Timeout in seconds for each orchestrator (tool selection) call
+    /// Default: 60
+    #[serde(default = "default_orchestrator_timeout_secs")]
+    pub orchestrator_timeout_secs: u64,
+    /// Timeout in seconds for each executor (generation) call
+    /// Default: 600
+    #[serde(default = "default_executor_timeout_secs")]
+    pub executor_timeout_secs: u64,
+    /// Write every LLM request and response to `.praxis/logs/requests-<date>.jsonl`
+    /// Default: false
+    #[serde(default)]
+    pub log_requests: bool,
+    /// Stop the loop and answer from observations after this many seconds
+    /// Default: no limit
+    #[serde(default)]
+    pub max_wall_secs: Option<u64>,
+    /// Stop the loop and answer from observations once a run has used this
+    /// many tokens (as reported by the provider)
+    /// Default: no limit
+    #[serde(default)]
+    pub max_total_tokens: Option<u32>,
+    /// Summarize the project's key files in the background when the REPL
+    /// starts (see the `index` command)
+    /// Default: false
+    #[serde(default)]
+    pub index_on_start: bool,
+    /// Orchestrator prompt changes keyed by model name prefix, on top of
+    /// the built-in ones (see `llm::default_prompt_templates`)
+    /// Default: none
+    #[serde(default)]
+    pub model_templates: BTreeMap<String, PromptTemplate>,
+}
+
+fn default_max_file_bytes() -> u64 {
+    crate::tools::fs::DEFAULT_MAX_FILE_BYTES
+}
+
+fn default_max_failed_observation_chars() -> usize {
+    300
+}
+
+fn default_collapse_failed_observations() -> bool {
+    true
+}
+
+fn default_summarize_threshold() -> usize {
+    100
+}
+
+fn default_max_observation_bytes() -> usize {
+    8192
+}
+
+fn default_max_repair_retries() -> usize {
+    1
+}
+
+fn default_nudge_on_intent() -> bool {
+    true
+}
+
+fn default_max_parallel_tools() -> usize {
+    3
+}
+
+fn default_show_banner() -> bool {
+    true
+}
+
+fn default_autosave() -> bool {
+    true
+}
+
+fn default_orchestrator_timeout_secs() -> u64 {
+    60
+}
+
+fn default_executor_timeout_secs() -> u64 {
+    600
 }
 
 impl Default for AgentConfig {
@@ -145,24 +557,87 @@ impl Default for AgentConfig {
         Self {
             max_history: 1000,
             context_window: 20,
+            context_budget_tokens: None,
+            summarize_history: false,
+            summarize_threshold: default_summarize_threshold(),
             max_turns: 10,
             debug: env::var("PRAXIS_DEBUG")
                 .map(|v| v == "true" || v == "1")
                 .unwrap_or(false),
             system_prompt: None,
+            max_file_bytes: default_max_file_bytes(),
+            max_failed_observation_chars: default_max_failed_observation_chars(),
+            collapse_failed_observations: default_collapse_failed_observations(),
+            observation_format: ObservationFormat::default(),
+            max_observation_bytes: default_max_observation_bytes(),
+            max_repair_retries: default_max_repair_retries(),
+            nudge_on_intent: default_nudge_on_intent(),
+            max_parallel_tools: default_max_parallel_tools(),
+            allow_shell: false,
+            strip_code_fences: false,
+            workdir: None,
+            autosave: default_autosave(),
+            show_banner: default_show_banner(),
+            orchestrator_timeout_secs: default_orchestrator_timeout_secs(),
+            executor_timeout_secs: default_executor_timeout_secs(),
+            log_requests: false,
+            max_wall_secs: None,
+            max_total_tokens: None,
+            index_on_start: false,
+            model_templates: BTreeMap::new(),
         }
     }
 }
 
 /// Streaming configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "RawStreamingConfig")]
 pub struct StreamingConfig {
-    /// Whether to stream responses in real-time
-    pub enabled: bool,
+    /// Stream orchestrator turns (reasoning shown as it arrives)
+    pub orchestrator: bool,
+    /// Stream executor output (generated code and answers)
+    pub executor: bool,
     /// Print tokens as they arrive (vs buffering)
     pub print_tokens: bool,
 }
 
+/// Streaming settings as written in config files
+///
+/// Older files have a single `enabled` switch; `enabled = false` turns
+/// off streaming for both roles.
+#[derive(Deserialize)]
+struct RawStreamingConfig {
+    #[serde(default = "default_streaming")]
+    orchestrator: bool,
+    #[serde(default = "default_streaming")]
+    executor: bool,
+    #[serde(default)]
+    enabled: Option<bool>,
+    #[serde(default = "default_print_tokens")]
+    print_tokens: bool,
+}
+
+impl From<RawStreamingConfig> for StreamingConfig {
+    fn from(raw: RawStreamingConfig) -> Self {
+        let enabled = raw.enabled.unwrap_or(true);
+        Self {
+            orchestrator: raw.orchestrator && enabled,
+            executor: raw.executor && enabled,
+            print_tokens: raw.print_tokens,
+        }
+    }
+}
+
+fn default_streaming() -> bool {
+    env::var("PRAXIS_STREAMING")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(true) // Streaming enabled by default
+}
+
+fn default_print_tokens() -> bool {
+    true
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -172,6 +647,8 @@ impl Default for Config {
             browser: BrowserConfig::default(),
             agent: AgentConfig::default(),
             streaming: StreamingConfig::default(),
+            path: None,
+            overrides: Overrides::default(),
         }
     }
 }
@@ -185,6 +662,14 @@ impl Default for OllamaConfig {
                 .and_then(|p| p.parse().ok())
                 .unwrap_or(11434),
             timeout_secs: 120,
+            max_retries: default_max_retries(),
+            retry_backoff_ms: default_retry_backoff_ms(),
+            num_ctx: None,
+            top_p: None,
+            top_k: None,
+            repeat_penalty: None,
+            seed: None,
+            model_cache_secs: default_model_cache_secs(),
         }
     }
 }
@@ -195,6 +680,7 @@ impl Default for ProviderConfig {
             ollama: OllamaConfig::default(),
             openrouter: OpenRouterConfig {
                 api_key: env::var("OPENROUTER_API_KEY").ok(),
+                base_url: default_openrouter_base_url(),
             },
             google_antigravity: AntigravityConfig {
                 project_id: env::var("ANTIGRAVITY_PROJECT_ID").ok(),
@@ -218,6 +704,10 @@ impl Default for ModelConfig {
                 .unwrap_or_else(|_| "qwen3-vl:8b".to_string()),
             executor: env::var("PRAXIS_EXECUTOR_MODEL").unwrap_or_else(|_| "qwen3:8b".to_string()),
             alternatives: ModelAlternatives::default(),
+            enable_fallback: default_enable_fallback(),
+            orchestrator_temperature: default_orchestrator_temperature(),
+            executor_temperature: default_executor_temperature(),
+            executor_stop: default_executor_stop(),
         }
     }
 }
@@ -253,6 +743,9 @@ impl Default for BrowserConfig {
                 .map(|v| v == "true" || v == "1")
                 .unwrap_or(false),
             timeout_ms: 30000,
+            snapshot_diffs: default_snapshot_diffs(),
+            max_links: default_max_links(),
+            reuse_session: false,
         }
     }
 }
@@ -260,10 +753,9 @@ impl Default for BrowserConfig {
 impl Default for StreamingConfig {
     fn default() -> Self {
         Self {
-            enabled: env::var("PRAXIS_STREAMING")
-                .map(|v| v == "true" || v == "1")
-                .unwrap_or(true), // Streaming enabled by default
-            print_tokens: true,
+            orchestrator: default_streaming(),
+            executor: default_streaming(),
+            print_tokens: default_print_tokens(),
         }
     }
 }
@@ -276,51 +768,191 @@ impl Config {
             .join("praxis")
     }
 
-    /// Get the config file path
+    /// Get the default config file path
+    ///
+    /// `PRAXIS_CONFIG` overrides the usual `config.toml` in `config_dir`.
     pub fn config_file() -> PathBuf {
-        Self::config_dir().join("config.toml")
+        match env::var_os("PRAXIS_CONFIG") {
+            Some(path) if !path.is_empty() => PathBuf::from(path),
+            _ => Self::config_dir().join("config.toml"),
+        }
+    }
+
+    /// Get the config file this configuration loads from and saves to
+    pub fn file_path(&self) -> PathBuf {
+        self.path.clone().unwrap_or_else(Self::config_file)
+    }
+
+    /// Get the project-local config file path under the project root
+    pub fn project_config_file(workdir: &Path) -> PathBuf {
+        workdir.join(".praxis").join("config.toml")
     }
 
-    /// Load configuration from file, environment, and defaults
-    /// Priority: CLI args > env vars > config file > defaults
-    pub fn load() -> Self {
+    /// Load configuration from files, environment, and defaults
+    /// Priority: CLI args > project file > global file > env vars > defaults
+    ///
+    /// Missing files fall back to the defaults; a file that can't be read,
+    /// parsed or validated is an error.
+    pub fn load() -> Result<Self> {
+        Self::load_with_path(None)
+    }
+
+    /// Load configuration like `load`, using `path` as the global config
+    /// file instead of the default
+    pub fn load_with_path(path: Option<PathBuf>) -> Result<Self> {
+        Self::load_with_overrides(path, Overrides::default())
+    }
+
+    /// Load configuration like `load_with_path`, then apply command-line
+    /// settings
+    ///
+    /// The project file is read from `overrides.workdir` when given, and
+    /// from the current directory otherwise.
+    pub fn load_with_overrides(path: Option<PathBuf>, overrides: Overrides) -> Result<Self> {
         // Try to load .env file if it exists
         let _ = dotenvy::dotenv();
 
-        // Try to load from config file
-        if let Ok(config) = Self::load_from_file() {
-            return config;
+        let path = path.unwrap_or_else(Self::config_file);
+        let workdir = overrides
+            .workdir
+            .clone()
+            .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        let mut config = Self::load_layered(&path, &Self::project_config_file(&workdir))?;
+        config.path = Some(path);
+        config.apply_overrides(overrides);
+        Ok(config)
+    }
+
+    /// Merge the global and project config files over the defaults
+    ///
+    /// Either file may be missing. Tables are merged key by key, so a
+    /// project file only needs the settings it overrides.
+    pub fn load_layered(global: &Path, project: &Path) -> Result<Self> {
+        let mut merged = toml::Value::try_from(Self::default())
+            .map_err(|e| PraxisError::config(format!("Failed to serialize defaults: {}", e)))?;
+
+        for path in [global, project] {
+            if !path.exists() {
+                continue;
+            }
+
+            let content = fs::read_to_string(path).map_err(|e| {
+                PraxisError::config(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            let layer: toml::Value = toml::from_str(&content).map_err(|e| {
+                PraxisError::config(format!("Failed to parse {}: {}", path.display(), e))
+            })?;
+            merge_toml(&mut merged, layer);
         }
 
-        // Fall back to defaults (which respect env vars)
-        Self::default()
+        let mut config: Config = merged
+            .try_into()
+            .map_err(|e| PraxisError::config(format!("Invalid configuration: {}", e)))?;
+        config.models.clamp_temperatures();
+        config.validate()?;
+        Ok(config)
     }
 
-    /// Load configuration from file only
-    pub fn load_from_file() -> Result<Self> {
-        let config_path = Self::config_file();
+    /// Apply command-line settings, keeping them for `reload`
+    pub fn apply_overrides(&mut self, overrides: Overrides) {
+        overrides.apply(self);
+        self.overrides = overrides;
+    }
 
-        if !config_path.exists() {
+    /// Load the config files again, with the same command-line settings
+    /// applied on top
+    pub fn reload(&self) -> Result<Self> {
+        let path = self.file_path();
+        let project = Self::project_config_file(&self.workdir());
+        let mut config = Self::load_layered(&path, &project)?;
+        config.path = Some(path);
+        config.apply_overrides(self.overrides.clone());
+        config.check_workdir()?;
+        Ok(config)
+    }
+
+    /// Load configuration from a single file only
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
             return Err(PraxisError::config("Config file not found"));
         }
 
-        let content = fs::read_to_string(&config_path)
+        let content = fs::read_to_string(path)
             .map_err(|e| PraxisError::config(format!("Failed to read config: {}", e)))?;
 
-        let config: Config = toml::from_str(&content)
+        let mut config: Config = toml::from_str(&content)
             .map_err(|e| PraxisError::config(format!("Failed to parse config: {}", e)))?;
+        config.models.clamp_temperatures();
+        config.validate()?;
+        config.path = Some(path.to_path_buf());
 
         Ok(config)
     }
 
-    /// Save configuration to file
+    /// Check for values that parse but can't work, naming the offending field
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |field: &str, problem: &str| {
+            Err(PraxisError::config(format!(
+                "Invalid configuration: {} {}",
+                field, problem
+            )))
+        };
+
+        if self.providers.ollama.port == 0 {
+            return invalid(
+                "providers.ollama.port",
+                "must be between 1 and 65535 (got 0)",
+            );
+        }
+        for (field, value) in [
+            (
+                "providers.ollama.timeout_secs",
+                self.providers.ollama.timeout_secs,
+            ),
+            (
+                "agent.orchestrator_timeout_secs",
+                self.agent.orchestrator_timeout_secs,
+            ),
+            (
+                "agent.executor_timeout_secs",
+                self.agent.executor_timeout_secs,
+            ),
+            ("browser.timeout_ms", self.browser.timeout_ms),
+        ] {
+            if value == 0 {
+                return invalid(field, "must be greater than 0");
+            }
+        }
+        for (field, model) in [
+            ("models.orchestrator", &self.models.orchestrator),
+            ("models.executor", &self.models.executor),
+        ] {
+            if model.trim().is_empty() {
+                return invalid(field, "must name a model");
+            }
+        }
+        if self.agent.max_turns == 0 {
+            return invalid("agent.max_turns", "must be at least 1 (got 0)");
+        }
+        if self.agent.max_parallel_tools == 0 {
+            return invalid("agent.max_parallel_tools", "must be at least 1 (got 0)");
+        }
+        if self.agent.context_budget_tokens == Some(0) {
+            return invalid("agent.context_budget_tokens", "must be greater than 0");
+        }
+        Ok(())
+    }
+
+    /// Save configuration to the file it was loaded from
     pub fn save(&self) -> Result<()> {
-        let config_dir = Self::config_dir();
-        let config_path = Self::config_file();
+        self.save_to(&self.file_path())
+    }
 
+    /// Save configuration to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
         // Create config directory if it doesn't exist
-        if !config_dir.exists() {
-            fs::create_dir_all(&config_dir)
+        if let Some(config_dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(config_dir)
                 .map_err(|e| PraxisError::config(format!("Failed to create config dir: {}", e)))?;
         }
 
@@ -329,7 +961,7 @@ impl Config {
             .map_err(|e| PraxisError::config(format!("Failed to serialize config: {}", e)))?;
 
         // Write to file
-        fs::write(&config_path, content)
+        fs::write(path, content)
             .map_err(|e| PraxisError::config(format!("Failed to write config: {}", e)))?;
 
         Ok(())
@@ -338,7 +970,47 @@ impl Config {
     /// Save configuration and return the path
     pub fn save_and_get_path(&self) -> Result<PathBuf> {
         self.save()?;
-        Ok(Self::config_file())
+        Ok(self.file_path())
+    }
+
+    /// Write the current values of `keys` (dotted, e.g. `models.executor`)
+    /// into the config file, leaving everything else in it as it was
+    ///
+    /// Only the file itself is read and patched, so project settings, CLI
+    /// overrides and API keys from the environment never end up in it.
+    /// Returns the path written.
+    pub fn save_keys(&self, keys: &[&str]) -> Result<PathBuf> {
+        let path = self.file_path();
+        let mut file = if path.exists() {
+            let content = fs::read_to_string(&path).map_err(|e| {
+                PraxisError::config(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            toml::from_str(&content).map_err(|e| {
+                PraxisError::config(format!("Failed to parse {}: {}", path.display(), e))
+            })?
+        } else {
+            toml::Table::new()
+        };
+
+        let current = toml::Value::try_from(self)
+            .map_err(|e| PraxisError::config(format!("Failed to serialize config: {}", e)))?;
+        for key in keys {
+            let value = key
+                .split('.')
+                .try_fold(&current, |value, part| value.get(part))
+                .ok_or_else(|| PraxisError::config(format!("Unknown setting: {}", key)))?;
+            set_toml_key(&mut file, key, value.clone());
+        }
+
+        if let Some(dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|e| PraxisError::config(format!("Failed to create config dir: {}", e)))?;
+        }
+        let content = toml::to_string_pretty(&file)
+            .map_err(|e| PraxisError::config(format!("Failed to serialize config: {}", e)))?;
+        fs::write(&path, content)
+            .map_err(|e| PraxisError::config(format!("Failed to write config: {}", e)))?;
+        Ok(path)
     }
 
     /// Check if a config file exists
@@ -356,6 +1028,72 @@ impl Config {
         Ok(())
     }
 
+    /// The project root: `agent.workdir`, or the current directory
+    pub fn workdir(&self) -> PathBuf {
+        self.agent
+            .workdir
+            .clone()
+            .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+
+    /// Check that `agent.workdir`, if set, is a writable directory, and make
+    /// it absolute
+    pub fn check_workdir(&mut self) -> Result<()> {
+        let Some(ref dir) = self.agent.workdir else {
+            return Ok(());
+        };
+
+        let resolved = dir.canonicalize().map_err(|e| {
+            PraxisError::config(format!("Working directory {}: {}", dir.display(), e))
+        })?;
+        if !resolved.is_dir() {
+            return Err(PraxisError::config(format!(
+                "Working directory {} is not a directory",
+                dir.display()
+            )));
+        }
+
+        // Permission bits don't tell the whole story, so try a write
+        let probe = resolved.join(format!(".praxis-write-check-{}", std::process::id()));
+        fs::write(&probe, b"").map_err(|e| {
+            PraxisError::config(format!(
+                "Working directory {} is not writable: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        let _ = fs::remove_file(&probe);
+
+        self.agent.workdir = Some(resolved);
+        Ok(())
+    }
+
+    /// Describe what's missing before `provider` can be used, if anything
+    pub fn missing_provider_prerequisite(&self, provider: &ProviderType) -> Option<String> {
+        match provider {
+            ProviderType::Ollama => None,
+            // Local OpenAI-compatible gateways usually don't need a key
+            ProviderType::OpenRouter => {
+                let openrouter = &self.providers.openrouter;
+                let has_key = openrouter.api_key.as_deref().is_some_and(|k| !k.is_empty());
+                (openrouter.is_openrouter() && !has_key)
+                    .then(|| "OPENROUTER_API_KEY not set".to_string())
+            }
+            ProviderType::GoogleGeminiCli => env::var("GOOGLE_PROJECT_ID").is_err().then(|| {
+                "GOOGLE_PROJECT_ID not set (and run `gcloud auth login` if you haven't)".to_string()
+            }),
+            ProviderType::GoogleAntigravity => self
+                .providers
+                .google_antigravity
+                .access_token
+                .is_none()
+                .then(|| "Not authenticated with Antigravity; run praxis with --auth".to_string()),
+            ProviderType::Kolaborate => {
+                Some("The Kolaborate provider is not available yet".to_string())
+            }
+        }
+    }
+
     /// Get the full Ollama API URL
     pub fn ollama_url(&self) -> String {
         format!(
@@ -394,9 +1132,10 @@ impl Config {
             || model == self.models.executor
     }
 
-    /// Set streaming enabled/disabled
+    /// Set streaming enabled/disabled for both orchestrator and executor
     pub fn set_streaming(&mut self, enabled: bool) {
-        self.streaming.enabled = enabled;
+        self.streaming.orchestrator = enabled;
+        self.streaming.executor = enabled;
     }
 
     /// Generate a default config file content for display
@@ -407,6 +1146,40 @@ impl Config {
     }
 }
 
+/// Recursively merge `layer` into `base`, with `layer` taking precedence
+fn merge_toml(base: &mut toml::Value, layer: toml::Value) {
+    match (base, layer) {
+        (toml::Value::Table(base), toml::Value::Table(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+/// Set the dotted `key` in `table`, creating tables along the way
+fn set_toml_key(table: &mut toml::Table, key: &str, value: toml::Value) {
+    let Some((first, rest)) = key.split_once('.') else {
+        table.insert(key.to_string(), value);
+        return;
+    };
+    let entry = table
+        .entry(first)
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    if !entry.is_table() {
+        *entry = toml::Value::Table(toml::Table::new());
+    }
+    if let toml::Value::Table(inner) = entry {
+        set_toml_key(inner, rest, value);
+    }
+}
+
 impl OllamaConfig {
     /// Get the socket address
     pub fn addr(&self) -> String {
@@ -424,10 +1197,95 @@ mod tests {
         assert_eq!(config.models.orchestrator, "qwen3-vl:8b");
         assert_eq!(config.models.executor, "qwen3:8b");
         assert_eq!(config.providers.ollama.port, 11434);
-        assert!(config.streaming.enabled);
+        assert!(config.streaming.orchestrator && config.streaming.executor);
         assert_eq!(config.agent.max_turns, 10);
     }
 
+    #[test]
+    fn test_project_config_overrides_global() {
+        let dir = std::env::temp_dir().join("praxis_test_layered");
+        let _ = fs::create_dir_all(&dir);
+        let global = dir.join("global.toml");
+        let project = dir.join("project.toml");
+
+        fs::write(
+            &global,
+            "[models]\norchestrator = \"global-orch\"\nexecutor = \"global-exec\"\n\n[agent]\nmax_turns = 4\n",
+        )
+        .unwrap();
+        fs::write(&project, "[models]\nexecutor = \"project-exec\"\n").unwrap();
+
+        let config = Config::load_layered(&global, &project).unwrap();
+        assert_eq!(config.models.orchestrator, "global-orch");
+        assert_eq!(config.models.executor, "project-exec");
+        assert_eq!(config.agent.max_turns, 4);
+
+        // Missing files fall back to defaults
+        let config = Config::load_layered(&dir.join("none.toml"), &dir.join("none2.toml")).unwrap();
+        assert_eq!(config.agent.max_turns, AgentConfig::default().max_turns);
+    }
+
+    #[test]
+    fn test_streaming_legacy_enabled() {
+        let streaming: StreamingConfig =
+            toml::from_str("enabled = false\nprint_tokens = true").unwrap();
+        assert!(!streaming.orchestrator && !streaming.executor);
+
+        let streaming: StreamingConfig =
+            toml::from_str("orchestrator = false\nexecutor = true").unwrap();
+        assert!(!streaming.orchestrator && streaming.executor);
+    }
+
+    #[test]
+    fn test_temperatures_are_clamped_on_load() {
+        let dir = std::env::temp_dir().join("praxis_test_config_temperature");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        fs::write(
+            &path,
+            "[models]\norchestrator_temperature = -1.0\nexecutor_temperature = 3.5\n",
+        )
+        .unwrap();
+
+        let config = Config::load_layered(&path, &dir.join("none.toml")).unwrap();
+        assert_eq!(config.models.orchestrator_temperature, 0.0);
+        assert_eq!(config.models.executor_temperature, 2.0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_provider_prerequisites() {
+        assert_eq!(
+            ProviderType::from_name("Gemini"),
+            Some(ProviderType::GoogleGeminiCli)
+        );
+        assert_eq!(ProviderType::from_name("bogus"), None);
+
+        let mut config = Config::default();
+        config.providers.openrouter.api_key = None;
+        assert_eq!(
+            config.missing_provider_prerequisite(&ProviderType::OpenRouter),
+            Some("OPENROUTER_API_KEY not set".to_string())
+        );
+        assert_eq!(
+            config.missing_provider_prerequisite(&ProviderType::Ollama),
+            None
+        );
+
+        // A local OpenAI-compatible gateway doesn't need a key
+        config.providers.openrouter.base_url = "http://localhost:1234/v1".to_string();
+        assert_eq!(
+            config.missing_provider_prerequisite(&ProviderType::OpenRouter),
+            None
+        );
+        assert_eq!(
+            ProviderType::from_name("openai-compatible"),
+            Some(ProviderType::OpenRouter)
+        );
+    }
+
     #[test]
     fn test_ollama_url() {
         let config = Config::default();
@@ -442,6 +1300,196 @@ mod tests {
         assert!(toml_str.contains("executor"));
     }
 
+    #[test]
+    fn test_save_and_load_explicit_path() {
+        let dir = std::env::temp_dir().join("praxis_test_config_path");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join("custom.toml");
+
+        let mut config = Config::default();
+        config.agent.max_turns = 7;
+        config.save_to(&path).unwrap();
+
+        let loaded = Config::load_from_file(&path).unwrap();
+        assert_eq!(loaded.agent.max_turns, 7);
+        assert_eq!(loaded.file_path(), path);
+
+        let layered = Config::load_with_path(Some(path.clone())).unwrap();
+        assert_eq!(layered.agent.max_turns, 7);
+        assert_eq!(layered.save_and_get_path().unwrap(), path);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_save_keys_patches_only_those_keys() {
+        let dir = std::env::temp_dir().join("praxis_test_save_keys");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        fs::write(
+            &path,
+            "[models]\nexecutor = \"mine\"\n\n[agent]\nmax_turns = 3\n",
+        )
+        .unwrap();
+
+        let mut config = Config::load_with_path(Some(path.clone())).unwrap();
+        config.models.orchestrator = "picked".to_string();
+        config.models.executor = "from-cli".to_string();
+        config.providers.openrouter.api_key = Some("sk-from-env".to_string());
+        assert_eq!(config.save_keys(&["models.orchestrator"]).unwrap(), path);
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("orchestrator = \"picked\""));
+        assert!(saved.contains("executor = \"mine\""));
+        assert!(saved.contains("max_turns = 3"));
+        assert!(!saved.contains("sk-from-env"));
+        assert!(!saved.contains("[providers"));
+        assert!(config.save_keys(&["models.nope"]).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_reload_keeps_overrides() {
+        let dir = std::env::temp_dir().join("praxis_test_reload");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        fs::write(&path, "[models]\nexecutor = \"first\"\n").unwrap();
+
+        let mut config = Config::load_with_path(Some(path.clone())).unwrap();
+        config.apply_overrides(Overrides {
+            orchestrator: Some("cli-model".to_string()),
+            no_browser: true,
+            ..Overrides::default()
+        });
+
+        fs::write(&path, "[models]\nexecutor = \"second\"\n").unwrap();
+        let reloaded = config.reload().unwrap();
+        assert_eq!(reloaded.models.executor, "second");
+        assert_eq!(reloaded.models.orchestrator, "cli-model");
+        assert!(!reloaded.browser.enabled);
+        assert_eq!(reloaded.file_path(), path);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_project_config_comes_from_workdir() {
+        let dir = std::env::temp_dir().join("praxis_test_project_workdir");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("project/.praxis")).unwrap();
+        fs::write(
+            dir.join("project/.praxis/config.toml"),
+            "[agent]\nmax_turns = 4\n",
+        )
+        .unwrap();
+
+        let config = Config::load_with_overrides(
+            Some(dir.join("global.toml")),
+            Overrides {
+                workdir: Some(dir.join("project")),
+                ..Overrides::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(config.agent.max_turns, 4);
+        assert_eq!(config.reload().unwrap().agent.max_turns, 4);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_invalid_config_file_fails_to_load() {
+        let dir = std::env::temp_dir().join("praxis_test_invalid_load");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+
+        // A missing file still means defaults
+        let config = Config::load_with_path(Some(path.clone())).unwrap();
+        assert_eq!(config.agent.max_turns, Config::default().agent.max_turns);
+
+        fs::write(&path, "[agent]\nmax_turns = 0\n").unwrap();
+        let err = Config::load_with_path(Some(path.clone())).unwrap_err();
+        assert!(err.to_string().contains("agent.max_turns"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_validate_names_the_invalid_field() {
+        assert!(Config::default().validate().is_ok());
+
+        type Case = (&'static str, fn(&mut Config));
+        let cases: Vec<Case> = vec![
+            ("providers.ollama.port", |c| c.providers.ollama.port = 0),
+            ("providers.ollama.timeout_secs", |c| {
+                c.providers.ollama.timeout_secs = 0
+            }),
+            ("agent.orchestrator_timeout_secs", |c| {
+                c.agent.orchestrator_timeout_secs = 0
+            }),
+            ("agent.executor_timeout_secs", |c| {
+                c.agent.executor_timeout_secs = 0
+            }),
+            ("browser.timeout_ms", |c| c.browser.timeout_ms = 0),
+            ("models.orchestrator", |c| c.models.orchestrator.clear()),
+            ("models.executor", |c| c.models.executor = "  ".to_string()),
+            ("agent.max_turns", |c| c.agent.max_turns = 0),
+            ("agent.max_parallel_tools", |c| {
+                c.agent.max_parallel_tools = 0
+            }),
+        ];
+        for (field, break_it) in cases {
+            let mut config = Config::default();
+            break_it(&mut config);
+            let error = config.validate().unwrap_err().to_string();
+            assert!(error.contains(field), "{}: {}", field, error);
+        }
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_values() {
+        let dir = std::env::temp_dir().join("praxis_test_config_validate");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        fs::write(&path, "[agent]\nmax_turns = 0\n").unwrap();
+
+        let error = Config::load_layered(&path, &dir.join("missing.toml"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("agent.max_turns must be at least 1"));
+
+        let mut config = Config::default();
+        config.providers.ollama.port = 0;
+        config.save_to(&path).unwrap();
+        let error = Config::load_from_file(&path).unwrap_err().to_string();
+        assert!(error.contains("providers.ollama.port"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_check_workdir() {
+        let dir = std::env::temp_dir().join("praxis_test_workdir");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("project")).unwrap();
+
+        let mut config = Config::default();
+        config.agent.workdir = Some(dir.join("project").join(".."));
+        config.check_workdir().unwrap();
+        assert_eq!(config.workdir(), dir.canonicalize().unwrap());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        config.agent.workdir = Some(dir.join("missing"));
+        assert!(config.check_workdir().is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_config_dir() {
         let dir = Config::config_dir();
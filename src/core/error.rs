@@ -43,6 +43,10 @@ pub enum PraxisError {
     #[error("Cannot connect to Ollama at {0}. \n\nSetup steps:\n  1. Install: https://ollama.ai\n  2. Start: ollama serve\n  3. Pull models: ollama pull {1} && ollama pull {2}")]
     OllamaNotReachable(String, String, String),
 
+    /// Ollama is reachable but has no models installed
+    #[error("Ollama is running but has no models installed. Pull some with: ollama pull {0} && ollama pull {1}")]
+    NoModelsInstalled(String, String),
+
     /// Model not available
     #[error("Model '{0}' not available in Ollama. Run: ollama pull {0}")]
     ModelNotFound(String),
@@ -66,6 +70,14 @@ pub enum PraxisError {
     /// Provider API error
     #[error("Provider error: {0}")]
     ProviderError(String),
+
+    /// Operation cancelled by the user
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    /// The provider kept answering 429 Too Many Requests
+    #[error("Rate limited by the provider; try again in {}s", retry_after.as_secs())]
+    RateLimited { retry_after: std::time::Duration },
 }
 
 /// Convenience Result type for Praxis operations
@@ -112,4 +124,19 @@ impl PraxisError {
     pub fn provider(msg: impl Into<String>) -> Self {
         Self::ProviderError(msg.into())
     }
+
+    /// Whether the requested model is missing or couldn't be loaded, so a
+    /// different model might still work
+    pub fn is_model_unavailable(&self) -> bool {
+        match self {
+            Self::ModelNotFound(_) => true,
+            Self::Ollama(msg) => {
+                let msg = msg.to_lowercase();
+                msg.contains("failed to load")
+                    || msg.contains("error loading model")
+                    || msg.contains("requires more system memory")
+            }
+            _ => false,
+        }
+    }
 }
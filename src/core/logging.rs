@@ -0,0 +1,58 @@
+//! Logging setup
+//!
+//! Diagnostics are emitted as `tracing` events. The CLI installs a subscriber
+//! that writes them to stderr, filtered by `RUST_LOG` and the debug setting.
+
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+use tracing_subscriber::filter::Directive;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// Handle for changing the filter after startup (e.g. the REPL's `debug` command)
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Install the stderr subscriber
+///
+/// Does nothing if a global subscriber is already set.
+pub fn init(debug: bool) {
+    let (filter, handle) = reload::Layer::new(build_filter(debug));
+    let output = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .without_time();
+
+    if tracing_subscriber::registry()
+        .with(filter)
+        .with(output)
+        .try_init()
+        .is_ok()
+    {
+        let _ = FILTER.set(handle);
+    }
+}
+
+/// Turn debug-level output for Praxis on or off
+pub fn set_debug(enabled: bool) {
+    if let Some(handle) = FILTER.get() {
+        let _ = handle.reload(build_filter(enabled));
+    }
+}
+
+/// `RUST_LOG` (default: Praxis info, everything else warn), with Praxis
+/// raised to debug when `debug` is set
+fn build_filter(debug: bool) -> EnvFilter {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn,praxis=info"));
+
+    if debug {
+        filter.add_directive(praxis_debug())
+    } else {
+        filter
+    }
+}
+
+fn praxis_debug() -> Directive {
+    "praxis=debug".parse().expect("valid directive")
+}
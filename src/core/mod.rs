@@ -5,8 +5,10 @@
 
 pub mod config;
 pub mod error;
+pub mod logging;
+pub mod tokens;
 pub mod types;
 
-pub use config::Config;
+pub use config::{Config, Overrides};
 pub use error::{PraxisError, Result};
 pub use types::*;
@@ -0,0 +1,167 @@
+//! Token estimation
+//!
+//! A single, pluggable token estimator plus a memoizing cache so repeated
+//! context assembly doesn't re-estimate the same messages.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, RwLock};
+
+use crate::core::Message;
+
+/// Function that estimates the number of tokens in a piece of text
+pub type Estimator = fn(&str) -> usize;
+
+/// Maximum number of cached estimates before the cache is reset
+const MAX_CACHE_ENTRIES: usize = 4096;
+
+static ESTIMATOR: RwLock<Estimator> = RwLock::new(chars_per_four);
+
+/// Default heuristic: roughly four characters per token
+pub fn chars_per_four(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Replace the estimator used by [`estimate`] (e.g. with a real tokenizer)
+pub fn set_estimator(estimator: Estimator) {
+    if let Ok(mut current) = ESTIMATOR.write() {
+        *current = estimator;
+    }
+}
+
+/// Estimate the number of tokens in `text`
+pub fn estimate(text: &str) -> usize {
+    let estimator = ESTIMATOR.read().map(|e| *e).unwrap_or(chars_per_four);
+    estimator(text)
+}
+
+/// Memoizes token estimates by content hash
+pub struct TokenCache {
+    estimator: Estimator,
+    entries: Mutex<HashMap<u64, usize>>,
+}
+
+impl TokenCache {
+    /// Create a cache backed by the global [`estimate`] function
+    pub fn new() -> Self {
+        Self::with_estimator(estimate)
+    }
+
+    /// Create a cache backed by a specific estimator
+    pub fn with_estimator(estimator: Estimator) -> Self {
+        Self {
+            estimator,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the (possibly cached) estimate for `text`
+    pub fn estimate(&self, text: &str) -> usize {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let Ok(mut entries) = self.entries.lock() else {
+            return (self.estimator)(text);
+        };
+
+        if let Some(&tokens) = entries.get(&key) {
+            return tokens;
+        }
+
+        if entries.len() >= MAX_CACHE_ENTRIES {
+            entries.clear();
+        }
+
+        let tokens = (self.estimator)(text);
+        entries.insert(key, tokens);
+        tokens
+    }
+
+    /// Get the (possibly cached) estimate for a message's content
+    pub fn estimate_message(&self, message: &Message) -> usize {
+        self.estimate(&message.content)
+    }
+
+    /// Number of cached estimates
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached estimates
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+impl Default for TokenCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for TokenCache {
+    fn clone(&self) -> Self {
+        let entries = self.entries.lock().map(|e| e.clone()).unwrap_or_default();
+        Self {
+            estimator: self.estimator,
+            entries: Mutex::new(entries),
+        }
+    }
+}
+
+impl std::fmt::Debug for TokenCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenCache")
+            .field("entries", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counting_estimator(text: &str) -> usize {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        chars_per_four(text)
+    }
+
+    #[test]
+    fn test_estimate_is_stable() {
+        assert_eq!(chars_per_four(""), 0);
+        assert_eq!(chars_per_four("abcd"), 1);
+        assert_eq!(chars_per_four("abcde"), 2);
+        assert_eq!(estimate("hello world"), estimate("hello world"));
+    }
+
+    #[test]
+    fn test_cache_memoizes_by_content() {
+        let cache = TokenCache::with_estimator(counting_estimator);
+        let message = Message::user("the same content every time");
+
+        let first = cache.estimate_message(&message);
+        let calls = CALLS.load(Ordering::SeqCst);
+
+        for _ in 0..10 {
+            assert_eq!(cache.estimate_message(&message), first);
+        }
+
+        assert_eq!(CALLS.load(Ordering::SeqCst), calls);
+        assert_eq!(cache.len(), 1);
+
+        cache.estimate("different content");
+        assert_eq!(cache.len(), 2);
+    }
+}
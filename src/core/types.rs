@@ -7,22 +7,41 @@ use serde::{Deserialize, Serialize};
 /// A message in a conversation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
-    /// Role of the message sender (user, assistant, system)
+    /// Role of the message sender (user, assistant, system, tool)
     pub role: String,
     /// Content of the message
     pub content: String,
     /// Optional tool calls made by the assistant
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCall>>,
+    /// Name of the tool that produced a `tool` message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+    /// Base64-encoded images for vision models
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
 }
 
 impl Message {
+    /// Create a message with any role (e.g. `developer`)
+    pub fn new(role: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            role: role.into(),
+            content: content.into(),
+            tool_calls: None,
+            tool_name: None,
+            images: Vec::new(),
+        }
+    }
+
     /// Create a new user message
     pub fn user(content: impl Into<String>) -> Self {
         Self {
             role: "user".to_string(),
             content: content.into(),
             tool_calls: None,
+            tool_name: None,
+            images: Vec::new(),
         }
     }
 
@@ -32,6 +51,8 @@ impl Message {
             role: "assistant".to_string(),
             content: content.into(),
             tool_calls: None,
+            tool_name: None,
+            images: Vec::new(),
         }
     }
 
@@ -41,12 +62,36 @@ impl Message {
             role: "system".to_string(),
             content: content.into(),
             tool_calls: None,
+            tool_name: None,
+            images: Vec::new(),
+        }
+    }
+
+    /// Create an assistant message that calls tools
+    pub fn assistant_with_tools(content: impl Into<String>, tool_calls: Vec<ToolCall>) -> Self {
+        Self {
+            role: "assistant".to_string(),
+            content: content.into(),
+            tool_calls: Some(tool_calls),
+            tool_name: None,
+            images: Vec::new(),
+        }
+    }
+
+    /// Create a tool result message
+    pub fn tool(name: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            role: "tool".to_string(),
+            content: output.into(),
+            tool_calls: None,
+            tool_name: Some(name.into()),
+            images: Vec::new(),
         }
     }
 }
 
 /// A tool call made by the LLM
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ToolCall {
     /// Name of the tool to invoke
     pub name: String,
@@ -71,10 +116,55 @@ impl ToolCall {
             .map(|s| s.to_string())
     }
 
+    /// Get a string argument by key, or `default` if it's missing
+    pub fn get_string_or(&self, key: &str, default: &str) -> String {
+        self.get_string(key).unwrap_or_else(|| default.to_string())
+    }
+
     /// Get a boolean argument by key
     pub fn get_bool(&self, key: &str) -> Option<bool> {
         self.arguments.get(key).and_then(|v| v.as_bool())
     }
+
+    /// Get an integer argument by key
+    ///
+    /// Accepts numbers sent as strings (`"42"`) and whole floats (`42.0`).
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        match self.arguments.get(key)? {
+            serde_json::Value::Number(n) => n
+                .as_i64()
+                .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64)),
+            serde_json::Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Get a non-negative integer argument by key, with the same coercion as `get_i64`
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        match self.arguments.get(key)? {
+            serde_json::Value::String(s) => s.trim().parse().ok(),
+            _ => self.get_i64(key).and_then(|n| u64::try_from(n).ok()),
+        }
+    }
+
+    /// Get a number argument by key, accepting numbers sent as strings
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        match self.arguments.get(key)? {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Get an array argument by key
+    pub fn get_array(&self, key: &str) -> Option<&Vec<serde_json::Value>> {
+        self.arguments.get(key).and_then(|v| v.as_array())
+    }
+
+    /// Get an object argument by key
+    pub fn get_object(&self, key: &str) -> Option<&serde_json::Map<String, serde_json::Value>> {
+        self.arguments.get(key).and_then(|v| v.as_object())
+    }
 }
 
 /// Definition of a tool that can be called by the LLM
@@ -192,3 +282,41 @@ impl std::fmt::Display for ToolCategory {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_accessors() {
+        let call = ToolCall::new(
+            "browser_scroll",
+            serde_json::json!({
+                "pixels": 300,
+                "start_index": "4",
+                "end_index": " 12 ",
+                "ratio": "0.5",
+                "whole": 7.0,
+                "negative": -3,
+                "items": ["a", "b"],
+                "options": {"x": 1},
+                "bad": "ten",
+            }),
+        );
+
+        assert_eq!(call.get_i64("pixels"), Some(300));
+        assert_eq!(call.get_u64("start_index"), Some(4));
+        assert_eq!(call.get_u64("end_index"), Some(12));
+        assert_eq!(call.get_f64("ratio"), Some(0.5));
+        assert_eq!(call.get_i64("whole"), Some(7));
+        assert_eq!(call.get_i64("negative"), Some(-3));
+        assert_eq!(call.get_u64("negative"), None);
+        assert_eq!(call.get_i64("bad"), None);
+        assert_eq!(call.get_i64("missing"), None);
+
+        assert_eq!(call.get_array("items").map(|a| a.len()), Some(2));
+        assert!(call.get_object("options").is_some());
+        assert!(call.get_array("options").is_none());
+        assert_eq!(call.get_string_or("language", "rust"), "rust");
+    }
+}
@@ -18,7 +18,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let mut agent = Agent::new();
+//!     let mut agent = Agent::new().await.unwrap();
 //!     agent.initialize().await.unwrap();
 //!     
 //!     let response = agent.process("Write a hello world in Rust").await.unwrap();
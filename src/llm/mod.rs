@@ -4,12 +4,16 @@
 
 pub mod models;
 pub mod ollama;
+pub mod prompt_tools;
 pub mod provider;
+pub mod request_log;
 pub mod traits;
 
 pub use models::*;
 pub use ollama::OllamaClient;
+pub use prompt_tools::PromptToolAdapter;
 pub use provider::create_provider;
 pub use traits::{
-    GenerateOptions, LLMProvider, LLMResponse, StreamCallback, StreamChunk, TokenUsage,
+    text_events, GenerateOptions, LLMProvider, LLMResponse, PullProgressCallback, StreamCallback,
+    StreamChunk, StreamEvent, StreamEventCallback, TokenUsage,
 };
@@ -2,6 +2,8 @@
 //!
 //! Contains model configurations and recommended settings.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Model preset with recommended settings
@@ -113,6 +115,107 @@ pub fn find_preset(name: &str) -> Option<ModelPreset> {
     get_model_presets().into_iter().find(|p| p.name == name)
 }
 
+/// What a model can do, as far as Praxis knows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    /// Whether the model accepts native tool definitions
+    pub supports_tools: bool,
+}
+
+/// Capabilities of `model`, from its preset (or any preset of the same
+/// family, e.g. `gemma3:27b` from `gemma3:4b`)
+///
+/// Models without a preset are assumed to support native tools.
+pub fn capabilities(model: &str) -> ModelCapabilities {
+    let family = |name: &str| name.split(':').next().unwrap_or(name).to_string();
+    let preset = find_preset(model).or_else(|| {
+        get_model_presets()
+            .into_iter()
+            .find(|p| family(&p.name) == family(model))
+    });
+
+    ModelCapabilities {
+        supports_tools: preset.is_none_or(|p| p.supports_tools),
+    }
+}
+
+/// Changes to the orchestrator's system prompt for one model family
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    /// Replaces the opening ReAct instructions
+    #[serde(default)]
+    pub preamble: Option<String>,
+    /// Rules added after the built-in ones
+    #[serde(default)]
+    pub rules: Vec<String>,
+}
+
+impl PromptTemplate {
+    fn rules(rules: &[&str]) -> Self {
+        Self {
+            preamble: None,
+            rules: rules.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+}
+
+/// Extra orchestrator rules for models that call tools through
+/// `<tool_call>` blocks (see `prompt_tools`), i.e. those whose
+/// [`capabilities`] lack native tools
+pub const PROMPT_TOOL_RULES: &[&str] = &[
+    "Put each <tool_call> block on its own line, with only the JSON inside and no code fences.",
+    "Never write code in place of a tool call; pass the task to write_code.",
+];
+
+/// Built-in prompt templates for the preset model families, keyed by model
+/// name prefix
+pub fn default_prompt_templates() -> BTreeMap<String, PromptTemplate> {
+    BTreeMap::from([
+        (
+            "functiongemma".to_string(),
+            PromptTemplate {
+                preamble: Some(
+                    "You route tasks to tools. Pick the tool that fits, call it with exact \
+                     arguments, and answer in plain text once the observations contain the answer."
+                        .to_string(),
+                ),
+                rules: vec!["Make tool calls, never describe them in prose.".to_string()],
+            },
+        ),
+        (
+            "qwen".to_string(),
+            PromptTemplate::rules(&[
+                "Call tools through the tool interface, not as JSON in your reply.",
+                "Keep your thinking to a few sentences before acting.",
+            ]),
+        ),
+        (
+            "mistral".to_string(),
+            PromptTemplate::rules(&["Only pass the arguments a tool's schema lists."]),
+        ),
+    ])
+}
+
+/// The prompt template for `model`: the entry in `overrides` or the
+/// built-in ones whose key is the longest prefix of the model name
+///
+/// Matching ignores case and any provider prefix (`qwen/qwen-2.5` matches
+/// `qwen`). An override replaces a built-in entry with the same key.
+pub fn prompt_template(
+    model: &str,
+    overrides: &BTreeMap<String, PromptTemplate>,
+) -> Option<PromptTemplate> {
+    let name = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+    let mut templates = default_prompt_templates();
+    templates.extend(overrides.clone());
+
+    templates
+        .into_iter()
+        .filter(|(prefix, _)| name.starts_with(&prefix.to_lowercase()))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, template)| template)
+}
+
 /// Get recommended orchestrator models
 pub fn recommended_orchestrators() -> Vec<ModelPreset> {
     get_model_presets()
@@ -131,3 +234,35 @@ pub fn recommended_executors() -> Vec<ModelPreset> {
         .filter(|p| p.use_case == ModelUseCase::Coding || p.use_case == ModelUseCase::Hybrid)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_template_matches_longest_prefix() {
+        assert_eq!(
+            prompt_template("qwen2.5-coder:7b", &BTreeMap::new()),
+            default_prompt_templates().remove("qwen")
+        );
+        assert!(prompt_template("FunctionGemma", &BTreeMap::new())
+            .unwrap()
+            .preamble
+            .is_some());
+        assert_eq!(prompt_template("llama3.2", &BTreeMap::new()), None);
+
+        let custom = PromptTemplate::rules(&["Be brief."]);
+        let overrides = BTreeMap::from([
+            ("gemma".to_string(), custom.clone()),
+            ("gemma3:12b".to_string(), PromptTemplate::default()),
+        ]);
+        assert_eq!(
+            prompt_template("google/gemma3:4b", &overrides),
+            Some(custom)
+        );
+        assert_eq!(
+            prompt_template("gemma3:12b", &overrides),
+            Some(PromptTemplate::default())
+        );
+    }
+}
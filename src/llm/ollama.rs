@@ -4,20 +4,43 @@
 
 use async_trait::async_trait;
 use futures::StreamExt;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
 use std::io::{self, Write};
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
 
+use crate::core::config::OllamaConfig;
 use crate::core::{Config, Message, PraxisError, Result, ToolCall, ToolDefinition};
-use crate::llm::traits::{GenerateOptions, LLMProvider, LLMResponse, StreamCallback, TokenUsage};
+use crate::llm::request_log::RequestLog;
+use crate::llm::traits::{
+    text_events, usage_or_partial, GenerateOptions, LLMProvider, LLMResponse, PullProgressCallback,
+    StreamCallback, StreamEvent, StreamEventCallback, TokenUsage,
+};
 
 /// Ollama API client
 #[derive(Clone)]
 pub struct OllamaClient {
     client: Client,
     base_url: String,
-    debug: bool,
+    max_retries: u32,
+    retry_backoff_ms: u64,
+    /// Transcript of requests and responses, when `agent.log_requests` is on
+    request_log: Option<RequestLog>,
+    /// Recently listed models, shared by clones of this client
+    model_cache: Arc<Mutex<Option<ModelCache>>>,
+    /// How long a cached model list stays fresh
+    model_cache_ttl: Duration,
+}
+
+/// A model list and when it was fetched
+#[derive(Debug, Clone)]
+struct ModelCache {
+    base_url: String,
+    fetched: Instant,
+    models: Vec<String>,
 }
 
 /// Ollama chat request
@@ -32,6 +55,19 @@ struct ChatRequest<'a> {
     stream: bool,
 }
 
+/// Ollama embeddings request (one input per request)
+#[derive(Debug, Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+/// Ollama embeddings response
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponse {
+    embedding: Vec<f32>,
+}
+
 /// Ollama message format
 #[derive(Debug, Serialize, Deserialize)]
 struct OllamaMessage {
@@ -39,6 +75,11 @@ struct OllamaMessage {
     content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_calls: Option<Vec<OllamaToolCall>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool_name: Option<String>,
+    /// Base64-encoded images, for vision models
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
 }
 
 /// Ollama tool call format
@@ -50,7 +91,12 @@ struct OllamaToolCall {
 /// Ollama function in tool call
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct OllamaFunction {
+    /// Position of the call; lets streamed fragments of one call be merged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    #[serde(default)]
     name: String,
+    #[serde(default)]
     arguments: serde_json::Value,
 }
 
@@ -63,6 +109,31 @@ struct OllamaOptions {
     num_predict: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_ctx: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repeat_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<i64>,
+}
+
+impl From<GenerateOptions> for OllamaOptions {
+    fn from(opts: GenerateOptions) -> Self {
+        Self {
+            temperature: opts.temperature,
+            num_predict: opts.max_tokens,
+            stop: opts.stop,
+            num_ctx: opts.num_ctx,
+            top_p: opts.top_p,
+            top_k: opts.top_k,
+            repeat_penalty: opts.repeat_penalty,
+            seed: opts.seed,
+        }
+    }
 }
 
 /// Ollama chat response (non-streaming)
@@ -99,6 +170,34 @@ struct StreamMessage {
     tool_calls: Option<Vec<OllamaToolCall>>,
 }
 
+/// Ollama pull progress line
+#[derive(Debug, Deserialize)]
+struct PullProgressResponse {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    total: Option<u64>,
+    #[serde(default)]
+    completed: Option<u64>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl PullProgressResponse {
+    /// Percent complete, when sizes are reported
+    fn percent(&self) -> Option<f32> {
+        match (self.total, self.completed) {
+            (Some(total), Some(completed)) if total > 0 => {
+                Some((completed as f64 / total as f64 * 100.0) as f32)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Upper bound on a model download (the client timeout is far too short)
+const PULL_TIMEOUT: Duration = Duration::from_secs(6 * 60 * 60);
+
 /// Ollama models list response
 #[derive(Debug, Deserialize)]
 struct ModelsResponse {
@@ -127,7 +226,14 @@ impl OllamaClient {
         Self {
             client,
             base_url: config.ollama_url(),
-            debug: config.agent.debug,
+            max_retries: config.providers.ollama.max_retries,
+            retry_backoff_ms: config.providers.ollama.retry_backoff_ms,
+            request_log: config
+                .agent
+                .log_requests
+                .then(|| RequestLog::in_workdir(&config.workdir())),
+            model_cache: Arc::default(),
+            model_cache_ttl: Duration::from_secs(config.providers.ollama.model_cache_secs),
         }
     }
 
@@ -138,16 +244,120 @@ impl OllamaClient {
             .build()
             .expect("Failed to create HTTP client");
 
+        let defaults = OllamaConfig::default();
+
         Self {
             client,
             base_url: base_url.into(),
-            debug: false,
+            max_retries: defaults.max_retries,
+            retry_backoff_ms: defaults.retry_backoff_ms,
+            request_log: None,
+            model_cache: Arc::default(),
+            model_cache_ttl: Duration::from_secs(defaults.model_cache_secs),
+        }
+    }
+
+    /// The cached model list, if it's for this server and still fresh
+    fn cached_models(&self) -> Option<Vec<String>> {
+        let cache = self.model_cache.lock().ok()?;
+        cache
+            .as_ref()
+            .filter(|c| c.base_url == self.base_url && c.fetched.elapsed() < self.model_cache_ttl)
+            .map(|c| c.models.clone())
+    }
+
+    /// Forget the cached model list, e.g. after a pull
+    fn invalidate_models(&self) {
+        if let Ok(mut cache) = self.model_cache.lock() {
+            *cache = None;
+        }
+    }
+
+    /// Ask the server for its installed models and cache the answer
+    async fn fetch_models(&self) -> Result<Vec<String>> {
+        let response = self
+            .send_with_retry(self.client.get(format!("{}/api/tags", self.base_url)))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(PraxisError::ollama("Failed to list models"));
+        }
+
+        let models_response: ModelsResponse = response.json().await?;
+        let models: Vec<String> = models_response.models.into_iter().map(|m| m.name).collect();
+
+        if let Ok(mut cache) = self.model_cache.lock() {
+            *cache = Some(ModelCache {
+                base_url: self.base_url.clone(),
+                fetched: Instant::now(),
+                models: models.clone(),
+            });
         }
+        Ok(models)
     }
 
     /// Enable or disable debug output
+    ///
+    /// Debug output is global `tracing` output, so this affects every client.
     pub fn set_debug(&mut self, debug: bool) {
-        self.debug = debug;
+        crate::core::logging::set_debug(debug);
+    }
+
+    /// Set the retry policy for transient failures
+    pub fn set_retry_policy(&mut self, max_retries: u32, retry_backoff_ms: u64) {
+        self.max_retries = max_retries;
+        self.retry_backoff_ms = retry_backoff_ms;
+    }
+
+    /// Send a request, retrying connection errors and 5xx responses with
+    /// exponential backoff
+    ///
+    /// The client timeout applies to each attempt. Other failures, including
+    /// 4xx responses such as model-not-found, are returned immediately. Once
+    /// retries are exhausted the last error or response is returned as-is.
+    async fn send_with_retry(&self, request: RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+
+        loop {
+            // Bodies that can't be cloned (streams) get a single attempt
+            let Some(this_attempt) = request.try_clone() else {
+                return request.send().await.map_err(|e| self.map_send_error(e));
+            };
+
+            let result = this_attempt.send().await;
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect() || (e.is_request() && !e.is_timeout()),
+            };
+
+            if !retryable || attempt >= self.max_retries {
+                return result.map_err(|e| self.map_send_error(e));
+            }
+
+            let delay = self
+                .retry_backoff_ms
+                .saturating_mul(1u64 << attempt.min(16));
+            attempt += 1;
+            debug!(
+                attempt,
+                max_retries = self.max_retries,
+                delay_ms = delay,
+                "Ollama request failed, retrying"
+            );
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+    }
+
+    /// Map a transport error, explaining connection failures
+    fn map_send_error(&self, e: reqwest::Error) -> PraxisError {
+        if e.is_connect() {
+            PraxisError::ollama(format!(
+                "Cannot connect to Ollama at {}. Is it running?",
+                self.base_url
+            ))
+        } else {
+            PraxisError::from(e)
+        }
     }
 
     /// Convert internal Message to Ollama format
@@ -160,26 +370,46 @@ impl OllamaClient {
                     .iter()
                     .map(|tc| OllamaToolCall {
                         function: OllamaFunction {
+                            index: None,
                             name: tc.name.clone(),
                             arguments: tc.arguments.clone(),
                         },
                     })
                     .collect()
             }),
+            tool_name: msg.tool_name.clone(),
+            images: msg.images.clone(),
         }
     }
 
+    /// Convert an Ollama tool call, unwrapping arguments sent as a JSON string
+    ///
+    /// Weaker models sometimes encode the arguments object as a string
+    /// (`"{\"url\": \"...\"}"`), which would otherwise make every argument
+    /// lookup miss.
+    fn to_tool_call(tc: &OllamaToolCall) -> ToolCall {
+        let arguments = match &tc.function.arguments {
+            serde_json::Value::String(s) => match serde_json::from_str::<serde_json::Value>(s) {
+                Ok(parsed) => {
+                    debug!(tool = %tc.function.name, "Tool arguments arrived as a JSON string");
+                    parsed
+                }
+                Err(_) => tc.function.arguments.clone(),
+            },
+            other => other.clone(),
+        };
+
+        ToolCall::new(tc.function.name.clone(), arguments)
+    }
+
     /// Convert Ollama response to LLMResponse
     fn to_llm_response(response: ChatResponse) -> LLMResponse {
         let tool_calls = response
             .message
             .tool_calls
             .unwrap_or_default()
-            .into_iter()
-            .map(|tc| ToolCall {
-                name: tc.function.name,
-                arguments: tc.function.arguments,
-            })
+            .iter()
+            .map(Self::to_tool_call)
             .collect();
 
         let usage = match (response.prompt_eval_count, response.eval_count) {
@@ -187,6 +417,7 @@ impl OllamaClient {
                 prompt_tokens: prompt,
                 completion_tokens: completion,
                 total_tokens: prompt + completion,
+                partial: false,
             }),
             _ => None,
         };
@@ -199,38 +430,44 @@ impl OllamaClient {
         }
     }
 
-    /// Debug print if enabled
+    /// Debug print if enabled, and add to the request log if there is one
     fn debug_print(&self, label: &str, content: &str) {
-        if self.debug {
-            if content.len() > 500 {
-                eprintln!("DEBUG {}: {}...", label, &content[..500]);
-            } else {
-                eprintln!("DEBUG {}: {}", label, content);
-            }
+        if let Some(ref log) = self.request_log {
+            log.record(label, content);
+        }
+
+        match content.char_indices().nth(500) {
+            Some((end, _)) => debug!("{}: {}...", label, &content[..end]),
+            None => debug!("{}: {}", label, content),
         }
     }
 
     /// Internal streaming implementation
+    ///
+    /// Text tokens and the start of each tool call are passed to `on_event`
+    /// as they arrive, then `Done` with the usage; tool calls are collected
+    /// from the chunks that carry them (usually the final one). If `cancel`
+    /// fires, the response body is dropped (aborting the download) and the
+    /// content streamed so far is returned.
     async fn chat_stream_internal(
         &self,
         model: &str,
         messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
         options: Option<GenerateOptions>,
-        on_token: Option<&StreamCallback>,
+        on_event: Option<&StreamEventCallback>,
+        cancel: Option<&CancellationToken>,
     ) -> Result<LLMResponse> {
         let ollama_messages: Vec<OllamaMessage> =
             messages.iter().map(Self::to_ollama_message).collect();
 
-        let ollama_options = options.as_ref().map(|opts| OllamaOptions {
-            temperature: opts.temperature,
-            num_predict: opts.max_tokens,
-            stop: opts.stop.clone(),
-        });
+        let timeout = request_timeout(options.as_ref());
+        let ollama_options = options.map(OllamaOptions::from);
 
         let request = ChatRequest {
             model,
             messages: ollama_messages,
-            tools: None,
+            tools: tools.filter(|t| !t.is_empty()),
             options: ollama_options,
             stream: true,
         };
@@ -239,21 +476,13 @@ impl OllamaClient {
         self.debug_print("Stream Request", &request_json);
 
         let response = self
-            .client
-            .post(format!("{}/api/chat", self.base_url))
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_connect() {
-                    PraxisError::ollama(format!(
-                        "Cannot connect to Ollama at {}. Is it running?",
-                        self.base_url
-                    ))
-                } else {
-                    PraxisError::from(e)
-                }
-            })?;
+            .send_with_retry(with_timeout(
+                self.client
+                    .post(format!("{}/api/chat", self.base_url))
+                    .json(&request),
+                timeout,
+            ))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -270,97 +499,84 @@ impl OllamaClient {
         }
 
         // Process the streaming response
-        let mut full_content = String::new();
-        let mut final_model = model.to_string();
-        let mut prompt_tokens: Option<u32> = None;
-        let mut completion_tokens: Option<u32> = None;
-        let mut tool_calls: Vec<ToolCall> = Vec::new();
-
+        let mut state = StreamState::default();
         let mut stream = response.bytes_stream();
-        let mut buffer = String::new();
-
-        while let Some(chunk_result) = stream.next().await {
+        let mut cancelled = false;
+
+        loop {
+            let next = match cancel {
+                Some(token) => tokio::select! {
+                    chunk = stream.next() => chunk,
+                    _ = token.cancelled() => {
+                        cancelled = true;
+                        None
+                    }
+                },
+                None => stream.next().await,
+            };
+            let Some(chunk_result) = next else {
+                break;
+            };
             let chunk =
                 chunk_result.map_err(|e| PraxisError::ollama(format!("Stream error: {}", e)))?;
-            let chunk_str = String::from_utf8_lossy(&chunk);
-            buffer.push_str(&chunk_str);
 
-            // Process complete JSON lines from buffer
-            while let Some(newline_pos) = buffer.find('\n') {
-                let line = buffer[..newline_pos].trim().to_string();
-                buffer = buffer[newline_pos + 1..].to_string();
-
-                if line.is_empty() {
-                    continue;
-                }
-
-                // Parse the JSON chunk
-                match serde_json::from_str::<StreamChunkResponse>(&line) {
-                    Ok(chunk_response) => {
-                        final_model = chunk_response.model;
-
-                        if let Some(ref msg) = chunk_response.message {
-                            if !msg.content.is_empty() {
-                                full_content.push_str(&msg.content);
-
-                                // Call the callback if provided
-                                if let Some(callback) = on_token {
-                                    callback(&msg.content);
-                                }
-
-                                // Flush stdout for real-time display
-                                let _ = io::stdout().flush();
-                            }
-
-                            // Collect tool calls from final message
-                            if let Some(ref calls) = msg.tool_calls {
-                                for tc in calls {
-                                    tool_calls.push(ToolCall {
-                                        name: tc.function.name.clone(),
-                                        arguments: tc.function.arguments.clone(),
-                                    });
-                                }
-                            }
-                        }
-
-                        // Capture token counts from final chunk
-                        if chunk_response.done {
-                            prompt_tokens = chunk_response.prompt_eval_count;
-                            completion_tokens = chunk_response.eval_count;
-                        }
-                    }
-                    Err(e) => {
-                        self.debug_print("Parse Error", &format!("{}: {}", e, line));
-                    }
+            for event in state.push(&chunk) {
+                if let Some(callback) = on_event {
+                    callback(&event);
                 }
+                // Flush stdout for real-time display
+                let _ = io::stdout().flush();
+            }
+            for error in state.errors.drain(..) {
+                self.debug_print("Parse Error", &error);
             }
         }
 
-        // Process any remaining buffer content
-        if !buffer.trim().is_empty() {
-            if let Ok(chunk_response) = serde_json::from_str::<StreamChunkResponse>(buffer.trim()) {
-                if let Some(ref msg) = chunk_response.message {
-                    if !msg.content.is_empty() {
-                        full_content.push_str(&msg.content);
-                        if let Some(callback) = on_token {
-                            callback(&msg.content);
-                        }
-                    }
+        if cancelled {
+            // Stop the download now rather than when the response goes out of scope
+            drop(stream);
+            debug!(chars = state.content.len(), "Stream cancelled");
+        } else {
+            // The last line may not end with a newline
+            for event in state.finish() {
+                if let Some(callback) = on_event {
+                    callback(&event);
                 }
             }
         }
+        for error in state.errors.drain(..) {
+            self.debug_print("Parse Error", &error);
+        }
 
-        let usage = match (prompt_tokens, completion_tokens) {
+        let usage = match (state.prompt_tokens, state.completion_tokens) {
             (Some(prompt), Some(completion)) => Some(TokenUsage {
                 prompt_tokens: prompt,
                 completion_tokens: completion,
                 total_tokens: prompt + completion,
+                partial: false,
             }),
             _ => None,
         };
+        if let Some(callback) = on_event {
+            callback(&StreamEvent::Done(usage_or_partial(usage.as_ref())));
+        }
+        let final_model = state.model.unwrap_or_else(|| model.to_string());
+        let tool_calls: Vec<ToolCall> = state.tool_calls.iter().map(Self::to_tool_call).collect();
+
+        self.debug_print(
+            "Stream Response",
+            &serde_json::json!({
+                "model": final_model,
+                "content": state.content,
+                "tool_calls": tool_calls,
+                "prompt_eval_count": state.prompt_tokens,
+                "eval_count": state.completion_tokens,
+            })
+            .to_string(),
+        );
 
         Ok(LLMResponse {
-            content: full_content,
+            content: state.content,
             tool_calls,
             usage,
             model: final_model,
@@ -368,6 +584,111 @@ impl OllamaClient {
     }
 }
 
+/// Reassembles a streamed `/api/chat` response from raw byte chunks
+///
+/// Bytes are buffered until a whole line arrives, so JSON objects (and UTF-8
+/// characters) split across chunks survive. Tool calls carrying an `index`
+/// are merged across chunks, with string argument fragments appended in
+/// order, as in OpenAI-style deltas.
+#[derive(Debug, Default)]
+struct StreamState {
+    buffer: Vec<u8>,
+    content: String,
+    model: Option<String>,
+    tool_calls: Vec<OllamaToolCall>,
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+    /// Lines that didn't parse, waiting to be logged
+    errors: Vec<String>,
+}
+
+impl StreamState {
+    /// Add a chunk, returning the events from each line it completed
+    fn push(&mut self, chunk: &[u8]) -> Vec<StreamEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(newline_pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline_pos).collect();
+            events.extend(self.apply_line(&line));
+        }
+        events
+    }
+
+    /// Parse whatever is left once the stream ends
+    fn finish(&mut self) -> Vec<StreamEvent> {
+        let rest = std::mem::take(&mut self.buffer);
+        self.apply_line(&rest)
+    }
+
+    fn apply_line(&mut self, line: &[u8]) -> Vec<StreamEvent> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        if line.is_empty() {
+            return Vec::new();
+        }
+
+        let chunk = match serde_json::from_str::<StreamChunkResponse>(line) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                self.errors.push(format!("{}: {}", e, line));
+                return Vec::new();
+            }
+        };
+
+        self.model = Some(chunk.model);
+        // Token counts come with the final chunk
+        if chunk.done {
+            self.prompt_tokens = chunk.prompt_eval_count;
+            self.completion_tokens = chunk.eval_count;
+        }
+
+        let Some(message) = chunk.message else {
+            return Vec::new();
+        };
+        let mut events: Vec<StreamEvent> = message
+            .tool_calls
+            .into_iter()
+            .flatten()
+            .filter_map(|call| self.add_tool_call(call))
+            .map(StreamEvent::ToolCallStarted)
+            .collect();
+        if !message.content.is_empty() {
+            self.content.push_str(&message.content);
+            events.push(StreamEvent::Token(message.content));
+        }
+        events
+    }
+
+    /// Add or merge a tool call, returning its name once it is first known
+    fn add_tool_call(&mut self, call: OllamaToolCall) -> Option<String> {
+        let existing = call.function.index.and_then(|index| {
+            self.tool_calls
+                .iter_mut()
+                .find(|c| c.function.index == Some(index))
+        });
+        let Some(existing) = existing else {
+            let started = Some(call.function.name.clone()).filter(|n| !n.is_empty());
+            self.tool_calls.push(call);
+            return started;
+        };
+
+        let mut started = None;
+        if existing.function.name.is_empty() && !call.function.name.is_empty() {
+            existing.function.name = call.function.name;
+            started = Some(existing.function.name.clone());
+        }
+        match (&mut existing.function.arguments, call.function.arguments) {
+            (serde_json::Value::String(so_far), serde_json::Value::String(more)) => {
+                so_far.push_str(&more)
+            }
+            (_, serde_json::Value::Null) => {}
+            (arguments, more) => *arguments = more,
+        }
+        started
+    }
+}
+
 impl Default for OllamaClient {
     fn default() -> Self {
         Self::new()
@@ -385,11 +706,8 @@ impl LLMProvider for OllamaClient {
         let ollama_messages: Vec<OllamaMessage> =
             messages.iter().map(Self::to_ollama_message).collect();
 
-        let ollama_options = options.map(|opts| OllamaOptions {
-            temperature: opts.temperature,
-            num_predict: opts.max_tokens,
-            stop: opts.stop,
-        });
+        let timeout = request_timeout(options.as_ref());
+        let ollama_options = options.map(OllamaOptions::from);
 
         let request = ChatRequest {
             model,
@@ -403,21 +721,13 @@ impl LLMProvider for OllamaClient {
         self.debug_print("Request", &request_json);
 
         let response = self
-            .client
-            .post(format!("{}/api/chat", self.base_url))
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_connect() {
-                    PraxisError::ollama(format!(
-                        "Cannot connect to Ollama at {}. Is it running?",
-                        self.base_url
-                    ))
-                } else {
-                    PraxisError::from(e)
-                }
-            })?;
+            .send_with_retry(with_timeout(
+                self.client
+                    .post(format!("{}/api/chat", self.base_url))
+                    .json(&request),
+                timeout,
+            ))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -452,39 +762,29 @@ impl LLMProvider for OllamaClient {
         let ollama_messages: Vec<OllamaMessage> =
             messages.iter().map(Self::to_ollama_message).collect();
 
-        let ollama_options = options.map(|opts| OllamaOptions {
-            temperature: opts.temperature,
-            num_predict: opts.max_tokens,
-            stop: opts.stop,
-        });
+        let timeout = request_timeout(options.as_ref());
+        let ollama_options = options.map(OllamaOptions::from);
 
         let request = ChatRequest {
             model,
             messages: ollama_messages,
-            tools: Some(tools),
+            // Models without tool support reject even an empty list
+            tools: (!tools.is_empty()).then_some(tools),
             options: ollama_options,
-            stream: false, // Tool calling doesn't support streaming well
+            stream: false, // See chat_with_tools_stream for the streaming variant
         };
 
         let request_json = serde_json::to_string(&request)?;
         self.debug_print("Request (with tools)", &request_json);
 
         let response = self
-            .client
-            .post(format!("{}/api/chat", self.base_url))
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_connect() {
-                    PraxisError::ollama(format!(
-                        "Cannot connect to Ollama at {}. Is it running?",
-                        self.base_url
-                    ))
-                } else {
-                    PraxisError::from(e)
-                }
-            })?;
+            .send_with_retry(with_timeout(
+                self.client
+                    .post(format!("{}/api/chat", self.base_url))
+                    .json(&request),
+                timeout,
+            ))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -516,10 +816,50 @@ impl LLMProvider for OllamaClient {
         options: Option<GenerateOptions>,
         on_token: StreamCallback,
     ) -> Result<LLMResponse> {
-        self.chat_stream_internal(model, messages, options, Some(&on_token))
+        let cancel = options.as_ref().and_then(|o| o.cancel.clone());
+        self.chat_stream_internal(
+            model,
+            messages,
+            None,
+            options,
+            Some(&text_events(on_token)),
+            cancel.as_ref(),
+        )
+        .await
+    }
+
+    async fn chat_with_tools_stream(
+        &self,
+        model: &str,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        options: Option<GenerateOptions>,
+        on_token: StreamCallback,
+    ) -> Result<LLMResponse> {
+        self.chat_with_tools_stream_events(model, messages, tools, options, text_events(on_token))
             .await
     }
 
+    async fn chat_with_tools_stream_events(
+        &self,
+        model: &str,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        options: Option<GenerateOptions>,
+        on_event: StreamEventCallback,
+    ) -> Result<LLMResponse> {
+        let cancel = options.as_ref().and_then(|o| o.cancel.clone());
+        self.chat_stream_internal(
+            model,
+            messages,
+            Some(tools),
+            options,
+            Some(&on_event),
+            cancel.as_ref(),
+        )
+        .await
+    }
+
     async fn is_model_available(&self, model: &str) -> Result<bool> {
         let models = self.list_models().await?;
         Ok(models
@@ -528,46 +868,121 @@ impl LLMProvider for OllamaClient {
     }
 
     async fn list_models(&self) -> Result<Vec<String>> {
-        let response = self
-            .client
-            .get(format!("{}/api/tags", self.base_url))
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_connect() {
-                    PraxisError::ollama(format!(
-                        "Cannot connect to Ollama at {}. Is it running?",
-                        self.base_url
-                    ))
-                } else {
-                    PraxisError::from(e)
+        match self.cached_models() {
+            Some(models) => Ok(models),
+            None => self.fetch_models().await,
+        }
+    }
+
+    async fn refresh_models(&self) -> Result<Vec<String>> {
+        self.invalidate_models();
+        self.fetch_models().await
+    }
+
+    async fn embeddings(&self, model: &str, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(inputs.len());
+
+        for input in inputs {
+            let response = self
+                .send_with_retry(
+                    self.client
+                        .post(format!("{}/api/embeddings", self.base_url))
+                        .json(&EmbeddingsRequest {
+                            model,
+                            prompt: input,
+                        }),
+                )
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                if status.as_u16() == 404 && error_text.contains("not found") {
+                    return Err(PraxisError::ModelNotFound(model.to_string()));
                 }
-            })?;
+                return Err(PraxisError::ollama(format!(
+                    "Ollama embeddings error ({}): {}",
+                    status, error_text
+                )));
+            }
 
-        if !response.status().is_success() {
-            return Err(PraxisError::ollama("Failed to list models"));
+            let body: EmbeddingsResponse = response.json().await?;
+            embeddings.push(body.embedding);
         }
 
-        let models_response: ModelsResponse = response.json().await?;
-        Ok(models_response.models.into_iter().map(|m| m.name).collect())
+        Ok(embeddings)
     }
 
     async fn pull_model(&self, model: &str) -> Result<()> {
+        self.pull_model_with_progress(model, Box::new(|_, _| {}))
+            .await
+    }
+
+    async fn pull_model_with_progress(
+        &self,
+        model: &str,
+        on_progress: PullProgressCallback,
+    ) -> Result<()> {
         #[derive(Serialize)]
         struct PullRequest<'a> {
             name: &'a str,
+            stream: bool,
         }
 
         let response = self
-            .client
-            .post(format!("{}/api/pull", self.base_url))
-            .json(&PullRequest { name: model })
-            .send()
+            .send_with_retry(
+                self.client
+                    .post(format!("{}/api/pull", self.base_url))
+                    .timeout(PULL_TIMEOUT)
+                    .json(&PullRequest {
+                        name: model,
+                        stream: true,
+                    }),
+            )
             .await?;
 
         if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(pull_error(model, &error_text));
+        }
+
+        // Ollama streams one JSON progress object per line
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        let mut succeeded = false;
+
+        while let Some(chunk_result) = stream.next().await {
+            let chunk =
+                chunk_result.map_err(|e| PraxisError::ollama(format!("Stream error: {}", e)))?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer = buffer[newline_pos + 1..].to_string();
+
+                if line.is_empty() {
+                    continue;
+                }
+
+                let Ok(progress) = serde_json::from_str::<PullProgressResponse>(&line) else {
+                    continue;
+                };
+
+                if let Some(error) = progress.error {
+                    return Err(pull_error(model, &error));
+                }
+
+                if progress.status == "success" {
+                    succeeded = true;
+                    self.invalidate_models();
+                }
+                on_progress(&progress.status, progress.percent());
+            }
+        }
+
+        if !succeeded {
             return Err(PraxisError::ollama(format!(
-                "Failed to pull model: {}",
+                "Pull of {} ended before completing",
                 model
             )));
         }
@@ -580,6 +995,30 @@ impl LLMProvider for OllamaClient {
     }
 }
 
+/// Per-request timeout from generation options, if one was set
+fn request_timeout(options: Option<&GenerateOptions>) -> Option<Duration> {
+    options
+        .and_then(|opts| opts.timeout_secs)
+        .map(Duration::from_secs)
+}
+
+/// Apply a per-request timeout, leaving the client default otherwise
+fn with_timeout(request: RequestBuilder, timeout: Option<Duration>) -> RequestBuilder {
+    match timeout {
+        Some(timeout) => request.timeout(timeout),
+        None => request,
+    }
+}
+
+/// Map an Ollama pull error, recognizing unknown models
+fn pull_error(model: &str, error: &str) -> PraxisError {
+    if error.contains("file does not exist") || error.contains("not found") {
+        PraxisError::ModelNotFound(model.to_string())
+    } else {
+        PraxisError::ollama(format!("Failed to pull model {}: {}", model, error.trim()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,4 +1036,278 @@ mod tests {
         assert_eq!(ollama_msg.role, "user");
         assert_eq!(ollama_msg.content, "Hello");
     }
+
+    #[tokio::test]
+    async fn test_model_list_is_cached_per_server() {
+        // Nothing listens here, so only a cache hit can succeed
+        let mut client = OllamaClient::with_base_url("http://127.0.0.1:9");
+        client.set_retry_policy(0, 0);
+        *client.model_cache.lock().unwrap() = Some(ModelCache {
+            base_url: client.base_url.clone(),
+            fetched: Instant::now(),
+            models: vec!["qwen3:8b".to_string()],
+        });
+
+        let clone = client.clone();
+        assert_eq!(clone.list_models().await.unwrap(), vec!["qwen3:8b"]);
+        assert!(client.is_model_available("qwen3:8b").await.unwrap());
+
+        // A different server doesn't share the entry
+        let mut other = client.clone();
+        other.base_url = "http://127.0.0.1:10".to_string();
+        assert!(other.list_models().await.is_err());
+
+        assert!(client.refresh_models().await.is_err());
+        assert!(client.model_cache.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_request_includes_images_when_set() {
+        let plain = Message::user("Hello");
+        let mut with_image = Message::user("What's on screen?");
+        with_image.images.push("aGVsbG8=".to_string());
+
+        let request = ChatRequest {
+            model: "qwen3-vl:8b",
+            messages: vec![
+                OllamaClient::to_ollama_message(&plain),
+                OllamaClient::to_ollama_message(&with_image),
+            ],
+            tools: None,
+            options: None,
+            stream: false,
+        };
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert!(json["messages"][0].get("images").is_none());
+        assert_eq!(
+            json["messages"][1]["images"],
+            serde_json::json!(["aGVsbG8="])
+        );
+    }
+
+    #[test]
+    fn test_embeddings_request_format() {
+        let request = EmbeddingsRequest {
+            model: "nomic-embed-text",
+            prompt: "fn main() {}",
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({"model": "nomic-embed-text", "prompt": "fn main() {}"})
+        );
+
+        let response: EmbeddingsResponse =
+            serde_json::from_str(r#"{"embedding": [0.5, -1.25]}"#).unwrap();
+        assert_eq!(response.embedding, vec![0.5, -1.25]);
+    }
+
+    #[test]
+    fn test_string_arguments_are_parsed() {
+        let call: OllamaToolCall = serde_json::from_value(serde_json::json!({
+            "function": {
+                "name": "browser_url",
+                "arguments": "{\"url\": \"https://example.com\"}"
+            }
+        }))
+        .unwrap();
+        let tool_call = OllamaClient::to_tool_call(&call);
+        assert_eq!(
+            tool_call.get_string("url").as_deref(),
+            Some("https://example.com")
+        );
+
+        // Strings that aren't JSON are left alone
+        let call: OllamaToolCall = serde_json::from_value(serde_json::json!({
+            "function": {"name": "browser_url", "arguments": "example.com"}
+        }))
+        .unwrap();
+        assert_eq!(OllamaClient::to_tool_call(&call).arguments, "example.com");
+    }
+
+    #[test]
+    fn test_options_skip_unset_fields() {
+        let options = OllamaOptions::from(GenerateOptions {
+            temperature: Some(0.2),
+            num_ctx: Some(8192),
+            seed: Some(42),
+            ..Default::default()
+        });
+
+        let json = serde_json::to_value(&options).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"temperature": 0.2f32, "num_ctx": 8192, "seed": 42})
+        );
+    }
+
+    /// Serve canned HTTP responses in order, counting requests
+    async fn serve(statuses: Vec<u16>) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hits = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let body = r#"{"models":[{"name":"qwen3:8b"}]}"#;
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (url, hits)
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_stream_returns_partial_content() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let head = "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\nConnection: close\r\n\r\n";
+            let line =
+                r#"{"model":"m","message":{"role":"assistant","content":"Hel"},"done":false}"#;
+            let _ = socket
+                .write_all(format!("{}{}\n", head, line).as_bytes())
+                .await;
+            // Never finish the response
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+
+        let client = OllamaClient::with_base_url(url);
+        let cancel = CancellationToken::new();
+        let on_first = cancel.clone();
+        let options = GenerateOptions {
+            cancel: Some(cancel),
+            ..Default::default()
+        };
+
+        let response = tokio::time::timeout(
+            Duration::from_secs(5),
+            client.chat_stream(
+                "m",
+                &[Message::user("hi")],
+                Some(options),
+                Box::new(move |_| on_first.cancel()),
+            ),
+        )
+        .await
+        .expect("cancel should end the stream")
+        .unwrap();
+
+        assert_eq!(response.content, "Hel");
+        assert!(response.usage.is_none());
+    }
+
+    #[test]
+    fn test_stream_reassembles_split_tool_calls() {
+        let mut state = StreamState::default();
+
+        // One tool-call line split mid-object, then a multi-byte character split across chunks
+        let line = r#"{"model":"m","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"read_file","arguments":{"path":"src/main.rs"}}}]},"done":false}"#;
+        let (first, second) = line.split_at(60);
+        assert!(state.push(first.as_bytes()).is_empty());
+        assert_eq!(
+            state.push(format!("{}\n", second).as_bytes()),
+            vec![StreamEvent::ToolCallStarted("read_file".to_string())]
+        );
+
+        let text =
+            r#"{"model":"m","message":{"role":"assistant","content":"é"},"done":false}"#.as_bytes();
+        let split = text.iter().position(|&b| b == 0xC3).unwrap() + 1;
+        assert!(state.push(&text[..split]).is_empty());
+        assert_eq!(
+            state.push(&[&text[split..], b"\n"].concat()),
+            vec![StreamEvent::Token("é".to_string())]
+        );
+
+        // OpenAI-style deltas: the arguments string arrives in pieces, and
+        // the call is reported started only once
+        let mut events = Vec::new();
+        for delta in [
+            r#"{"model":"m","message":{"content":"","tool_calls":[{"function":{"index":1,"name":"write_file","arguments":"{\"path\": "}}]},"done":false}"#,
+            r#"{"model":"m","message":{"content":"","tool_calls":[{"function":{"index":1,"arguments":"\"a.txt\"}"}}]},"done":false}"#,
+        ] {
+            events.extend(state.push(format!("{}\n", delta).as_bytes()));
+        }
+        assert_eq!(
+            events,
+            vec![StreamEvent::ToolCallStarted("write_file".to_string())]
+        );
+        // The final line has no trailing newline
+        state.push(br#"{"model":"m","done":true,"prompt_eval_count":10,"eval_count":5}"#);
+        assert!(state.finish().is_empty());
+
+        assert!(state.errors.is_empty());
+        let calls: Vec<ToolCall> = state
+            .tool_calls
+            .iter()
+            .map(OllamaClient::to_tool_call)
+            .collect();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].name, "read_file");
+        assert_eq!(calls[0].arguments["path"], "src/main.rs");
+        assert_eq!(calls[1].name, "write_file");
+        assert_eq!(calls[1].arguments["path"], "a.txt");
+        assert_eq!(state.content, "é");
+        assert_eq!(state.completion_tokens, Some(5));
+    }
+
+    #[test]
+    fn test_pull_progress_parsing() {
+        let line = r#"{"status":"pulling abc","digest":"sha256:abc","total":200,"completed":50}"#;
+        let progress: PullProgressResponse = serde_json::from_str(line).unwrap();
+        assert_eq!(progress.percent(), Some(25.0));
+
+        let line = r#"{"status":"pulling manifest"}"#;
+        let progress: PullProgressResponse = serde_json::from_str(line).unwrap();
+        assert_eq!(progress.percent(), None);
+
+        assert!(matches!(
+            pull_error("nope", "pull model manifest: file does not exist"),
+            PraxisError::ModelNotFound(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors_only() {
+        use std::sync::atomic::Ordering;
+
+        let (url, hits) = serve(vec![500, 503, 200]).await;
+        let mut client = OllamaClient::with_base_url(url);
+        client.set_retry_policy(3, 1);
+        let models = client.list_models().await.unwrap();
+        assert_eq!(models, vec!["qwen3:8b"]);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (url, hits) = serve(vec![404, 200]).await;
+        let mut client = OllamaClient::with_base_url(url);
+        client.set_retry_policy(3, 1);
+        assert!(client.list_models().await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        let (url, hits) = serve(vec![500, 500]).await;
+        let mut client = OllamaClient::with_base_url(url);
+        client.set_retry_policy(1, 1);
+        assert!(client.list_models().await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
 }
@@ -0,0 +1,195 @@
+//! Prompt-based tool calling
+//!
+//! Lets models without native tool support (see `capabilities`) drive the
+//! agent anyway: tools are described in the system prompt, the model
+//! answers with `<tool_call>{...}</tool_call>` blocks, and those blocks are
+//! parsed back into `ToolCall`s.
+
+use crate::core::{Message, ToolCall, ToolDefinition};
+use crate::llm::traits::LLMResponse;
+
+const OPEN_TAG: &str = "<tool_call>";
+const CLOSE_TAG: &str = "</tool_call>";
+
+/// Wraps requests and responses for a model that can't take tool definitions
+pub struct PromptToolAdapter<'a> {
+    tools: &'a [ToolDefinition],
+}
+
+impl<'a> PromptToolAdapter<'a> {
+    /// Describe `tools` to the model
+    pub fn new(tools: &'a [ToolDefinition]) -> Self {
+        Self { tools }
+    }
+
+    /// The tool list and calling protocol, for the system prompt
+    pub fn instructions(&self) -> String {
+        let mut text = String::from(
+            "## Calling tools\n\
+             To call a tool, reply with one block per call, exactly like this:\n\
+             <tool_call>{\"name\": \"tool_name\", \"arguments\": {\"arg\": \"value\"}}</tool_call>\n\
+             Put nothing but the JSON inside a block. Results come back in the next message. \
+             When no tool is needed, answer in plain text without any block.\n\n\
+             Available tools:\n",
+        );
+        for tool in self.tools {
+            text.push_str(&format!(
+                "- {}: {} Arguments: {}\n",
+                tool.function.name, tool.function.description, tool.function.parameters
+            ));
+        }
+        text
+    }
+
+    /// `messages` rewritten for the model: the instructions join the system
+    /// prompt, and earlier tool calls and results become plain text
+    pub fn prepare(&self, messages: &[Message]) -> Vec<Message> {
+        let mut prepared: Vec<Message> = messages
+            .iter()
+            .map(|message| {
+                let mut message = message.clone();
+                if message.role == "tool" {
+                    message = Message::user(format!(
+                        "<tool_result name=\"{}\">\n{}\n</tool_result>",
+                        message.tool_name.as_deref().unwrap_or("tool"),
+                        message.content
+                    ));
+                } else if let Some(calls) = message.tool_calls.take() {
+                    for call in calls {
+                        message.content.push_str(&format!(
+                            "\n{}{}{}",
+                            OPEN_TAG,
+                            serde_json::json!({"name": call.name, "arguments": call.arguments}),
+                            CLOSE_TAG
+                        ));
+                    }
+                }
+                message
+            })
+            .collect();
+
+        match prepared.iter_mut().find(|m| m.role == "system") {
+            Some(system) => {
+                system.content.push_str("\n\n");
+                system.content.push_str(&self.instructions());
+            }
+            None => prepared.insert(0, Message::system(self.instructions())),
+        }
+        prepared
+    }
+
+    /// Move any `<tool_call>` blocks in the response into its tool calls
+    pub fn parse_response(&self, mut response: LLMResponse) -> LLMResponse {
+        let (content, calls) = parse_tool_calls(&response.content);
+        if !calls.is_empty() {
+            response.content = content;
+            response.tool_calls.extend(calls);
+        }
+        response
+    }
+}
+
+/// Split `text` into the prose outside `<tool_call>` blocks and the calls
+/// inside them
+///
+/// Blocks that aren't valid JSON with a `name` are left in the text; an
+/// unclosed final block runs to the end of the text.
+pub fn parse_tool_calls(text: &str) -> (String, Vec<ToolCall>) {
+    let mut prose = String::new();
+    let mut calls = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(OPEN_TAG) {
+        prose.push_str(&rest[..start]);
+        let body_start = start + OPEN_TAG.len();
+        let (body, next) = match rest[body_start..].find(CLOSE_TAG) {
+            Some(end) => (
+                &rest[body_start..body_start + end],
+                body_start + end + CLOSE_TAG.len(),
+            ),
+            None => (&rest[body_start..], rest.len()),
+        };
+
+        match parse_call(body) {
+            Some(call) => calls.push(call),
+            None => prose.push_str(&rest[start..next]),
+        }
+        rest = &rest[next..];
+    }
+    prose.push_str(rest);
+
+    (prose.trim().to_string(), calls)
+}
+
+/// One block's JSON as a tool call
+fn parse_call(body: &str) -> Option<ToolCall> {
+    let body = body
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let name = value["name"].as_str().filter(|n| !n.is_empty())?;
+    let arguments = match value.get("arguments").or_else(|| value.get("parameters")) {
+        // Some models double-encode the arguments
+        Some(serde_json::Value::String(s)) => serde_json::from_str(s).ok()?,
+        Some(arguments) => arguments.clone(),
+        None => serde_json::json!({}),
+    };
+    Some(ToolCall::new(name, arguments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_multiple_tool_call_blocks() {
+        let text = r#"I'll look at the files first.
+<tool_call>{"name": "list_files", "arguments": {"dir": "src"}}</tool_call>
+<tool_call>
+{"name": "read_file", "arguments": "{\"path\": \"Cargo.toml\"}"}
+</tool_call>
+<tool_call>not json</tool_call>
+<tool_call>{"name": "write_code", "parameters": {"task": "t", "language": "rust"}}"#;
+
+        let (prose, calls) = parse_tool_calls(text);
+        let names: Vec<&str> = calls.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["list_files", "read_file", "write_code"]);
+        assert_eq!(calls[0].arguments["dir"], "src");
+        assert_eq!(calls[1].arguments["path"], "Cargo.toml");
+        assert_eq!(calls[2].arguments["language"], "rust");
+        assert_eq!(
+            prose,
+            "I'll look at the files first.\n\n\n<tool_call>not json</tool_call>"
+        );
+    }
+
+    #[test]
+    fn test_prepare_rewrites_tool_messages() {
+        let tools = vec![ToolDefinition::function(
+            "read_file",
+            "Read a file.",
+            serde_json::json!({"type": "object"}),
+        )];
+        let adapter = PromptToolAdapter::new(&tools);
+        let messages = vec![
+            Message::system("You are an agent."),
+            Message::assistant_with_tools(
+                "",
+                vec![ToolCall::new("read_file", serde_json::json!({"path": "a"}))],
+            ),
+            Message::tool("read_file", "contents"),
+        ];
+
+        let prepared = adapter.prepare(&messages);
+        assert!(prepared[0].content.contains("- read_file: Read a file."));
+        assert!(prepared[1].tool_calls.is_none());
+        assert!(prepared[1]
+            .content
+            .contains(r#"<tool_call>{"arguments":{"path":"a"},"name":"read_file"}</tool_call>"#));
+        assert_eq!(prepared[2].role, "user");
+        assert!(prepared[2].content.contains("contents"));
+    }
+}
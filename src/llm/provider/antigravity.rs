@@ -17,6 +17,8 @@ const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
 
 pub struct AntigravityProvider {
     config: Config,
+    /// HTTP client reused across requests
+    client: reqwest::Client,
 }
 
 impl AntigravityProvider {
@@ -27,6 +29,7 @@ impl AntigravityProvider {
     pub fn from_config(config: &Config) -> Self {
         Self {
             config: config.clone(),
+            client: super::http_client(),
         }
     }
 
@@ -55,7 +58,11 @@ impl AntigravityProvider {
             .append_pair("client_id", &client_id)
             .append_pair("redirect_uri", &redirect_uri)
             .append_pair("response_type", "code")
-            .append_pair("scope", "https://www.googleapis.com/auth/cloud-platform") // Assuming this scope
+            .append_pair(
+                "scope",
+                // cloud-platform is assumed; userinfo.email lets `whoami` name the account
+                "https://www.googleapis.com/auth/cloud-platform https://www.googleapis.com/auth/userinfo.email",
+            )
             .append_pair("state", &state)
             .append_pair("access_type", "offline")
             .append_pair("prompt", "consent");
@@ -103,8 +110,8 @@ impl AntigravityProvider {
         stream.write_all(response.as_bytes()).unwrap();
 
         // 5. Exchange code for token
-        let client = reqwest::Client::new();
-        let resp = client
+        let resp = self
+            .client
             .post(TOKEN_URL)
             .form(&[
                 ("client_id", client_id.as_str()),
@@ -175,6 +182,12 @@ impl AntigravityProvider {
     }
 }
 
+impl Default for AntigravityProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl LLMProvider for AntigravityProvider {
     async fn chat(
@@ -185,8 +198,6 @@ impl LLMProvider for AntigravityProvider {
     ) -> Result<LLMResponse> {
         let token = self.get_valid_token().await?;
 
-        let client = reqwest::Client::new();
-
         // Convert messages to Gemini format
         let contents: Vec<serde_json::Value> = messages
             .iter()
@@ -209,13 +220,13 @@ impl LLMProvider for AntigravityProvider {
             }
         });
 
-        let resp = client
+        let request = self
+            .client
             .post(url)
             .header("Authorization", format!("Bearer {}", token))
             .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
+        let resp = super::send_rate_limited(request, PraxisError::Http).await?;
 
         if !resp.status().is_success() {
             let error_text = resp.text().await.unwrap_or_default();
@@ -264,7 +275,9 @@ impl LLMProvider for AntigravityProvider {
     }
 
     async fn is_model_available(&self, _model: &str) -> Result<bool> {
-        Ok(true) // Stub
+        // `list_models` is a fixed stub rather than a real listing, so it
+        // can't rule a model out
+        Ok(true)
     }
 
     async fn list_models(&self) -> Result<Vec<String>> {
@@ -278,6 +291,17 @@ impl LLMProvider for AntigravityProvider {
         Ok(())
     }
 
+    async fn account_info(&self) -> Result<String> {
+        let token = self.get_valid_token().await?;
+        let project = self
+            .config
+            .providers
+            .google_antigravity
+            .project_id
+            .as_deref();
+        super::google_account(&self.client, &token, project).await
+    }
+
     fn name(&self) -> &str {
         "google_antigravity"
     }
@@ -2,92 +2,163 @@
 //!
 //! Wraps the official `@google/gemini-cli` tool.
 
-use crate::core::{Config, Message, Result, ToolDefinition};
-use crate::llm::traits::{GenerateOptions, LLMProvider, LLMResponse, StreamCallback};
+use crate::core::{Config, Message, PraxisError, Result, ToolDefinition};
+use crate::llm::traits::{GenerateOptions, LLMProvider, LLMResponse, StreamCallback, TokenUsage};
 use async_trait::async_trait;
+use futures::StreamExt;
 
 pub struct GeminiProvider {
     #[allow(dead_code)]
     config: Config,
+    /// HTTP client reused across requests
+    client: reqwest::Client,
 }
 
 impl GeminiProvider {
     pub fn from_config(config: &Config) -> Self {
         Self {
             config: config.clone(),
+            client: super::http_client(),
         }
     }
 }
 
-#[async_trait]
-impl LLMProvider for GeminiProvider {
-    async fn chat(
-        &self,
-        model: &str,
-        messages: &[Message],
-        _options: Option<GenerateOptions>,
-    ) -> Result<LLMResponse> {
-        // 1. Get access token from gcloud
+impl GeminiProvider {
+    /// Get an access token from gcloud
+    fn access_token() -> Result<String> {
         let output = std::process::Command::new("gcloud")
-            .args(&["auth", "print-access-token"])
+            .args(["auth", "print-access-token"])
             .output()
             .map_err(|e| PraxisError::ProviderError(format!("Failed to execute gcloud: {}", e)))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(PraxisError::ProviderError(format!("gcloud auth failed: {}", stderr)));
+            return Err(PraxisError::ProviderError(format!(
+                "gcloud auth failed: {}",
+                stderr
+            )));
         }
 
-        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
 
-        // 2. Prepare request
-        let client = reqwest::Client::new();
+    /// Vertex AI URL for a model method (e.g. `generateContent`)
+    fn endpoint(model: &str, method: &str) -> Result<String> {
         let project_id = std::env::var("GOOGLE_PROJECT_ID")
             .map_err(|_| PraxisError::Config("GOOGLE_PROJECT_ID not set".to_string()))?;
-        
+
         // Map model name to Vertex AI endpoint format
         // e.g. gemini-1.5-pro-preview-0409 -> gemini-1.5-pro-preview-0409
-        let endpoint_model = model.replace("google/", ""); 
+        let endpoint_model = model.replace("google/", "");
         let location = "us-central1"; // TODO: Make configurable
-        
-        let url = format!(
-            "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/google/models/{}:generateContent",
-            location, project_id, location, endpoint_model
-        );
 
-        let contents: Vec<serde_json::Value> = messages.iter().map(|m| {
-            serde_json::json!({
-                "role": if m.role == "user" { "user" } else { "model" },
-                "parts": [{ "text": m.content }]
+        Ok(format!(
+            "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/google/models/{}:{}",
+            location, project_id, location, endpoint_model, method
+        ))
+    }
+
+    /// Build the request body for a conversation
+    fn request_body(messages: &[Message]) -> serde_json::Value {
+        let contents: Vec<serde_json::Value> = messages
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "role": if m.role == "user" { "user" } else { "model" },
+                    "parts": [{ "text": m.content }]
+                })
             })
-        }).collect();
+            .collect();
 
-        let body = serde_json::json!({
+        serde_json::json!({
             "contents": contents,
             "generation_config": {
                 "candidate_count": 1,
             }
-        });
+        })
+    }
 
-        // 3. Send request
-        let resp = client.post(&url)
+    /// Send a request, mapping non-success statuses to provider errors
+    async fn send(&self, url: &str, messages: &[Message]) -> Result<reqwest::Response> {
+        let token = Self::access_token()?;
+
+        let request = self
+            .client
+            .post(url)
             .header("Authorization", format!("Bearer {}", token))
             .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+            .json(&Self::request_body(messages));
+        let resp = super::send_rate_limited(request, |e| {
+            PraxisError::ProviderError(format!("Gemini request failed: {}", e))
+        })
+        .await?;
 
         if !resp.status().is_success() {
             let error_text = resp.text().await.unwrap_or_default();
-            return Err(PraxisError::ProviderError(format!("Gemini API error: {}", error_text)));
+            return Err(PraxisError::ProviderError(format!(
+                "Gemini API error: {}",
+                error_text
+            )));
         }
 
+        Ok(resp)
+    }
+}
+
+/// Text and usage carried by one streamed `data:` event
+fn parse_stream_event(data: &str) -> Result<(String, Option<TokenUsage>)> {
+    let event: serde_json::Value = serde_json::from_str(data)
+        .map_err(|e| PraxisError::ProviderError(format!("Failed to parse stream event: {}", e)))?;
+
+    if let Some(error) = event.get("error") {
+        return Err(PraxisError::ProviderError(format!(
+            "Gemini stream error: {}",
+            error
+        )));
+    }
+
+    let text = event["candidates"][0]["content"]["parts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|part| part["text"].as_str())
+        .collect::<String>();
+
+    let metadata = &event["usageMetadata"];
+    let usage = metadata["promptTokenCount"].as_u64().map(|prompt| {
+        let completion = metadata["candidatesTokenCount"].as_u64().unwrap_or(0);
+        TokenUsage {
+            prompt_tokens: prompt as u32,
+            completion_tokens: completion as u32,
+            total_tokens: metadata["totalTokenCount"]
+                .as_u64()
+                .unwrap_or(prompt + completion) as u32,
+            partial: false,
+        }
+    });
+
+    Ok((text, usage))
+}
+
+#[async_trait]
+impl LLMProvider for GeminiProvider {
+    async fn chat(
+        &self,
+        model: &str,
+        messages: &[Message],
+        _options: Option<GenerateOptions>,
+    ) -> Result<LLMResponse> {
+        let url = Self::endpoint(model, "generateContent")?;
+        let resp = self.send(&url, messages).await?;
+
         let response_json: serde_json::Value = resp.json().await?;
-        
-        // 4. Parse response
+
+        // Parse response
         let content = response_json["candidates"][0]["content"]["parts"][0]["text"]
             .as_str()
-            .ok_or_else(|| PraxisError::ProviderError("Failed to parse response content".to_string()))?
+            .ok_or_else(|| {
+                PraxisError::ProviderError("Failed to parse response content".to_string())
+            })?
             .to_string();
 
         Ok(LLMResponse {
@@ -110,15 +181,58 @@ impl LLMProvider for GeminiProvider {
 
     async fn chat_stream(
         &self,
-        _model: &str,
-        _messages: &[Message],
+        model: &str,
+        messages: &[Message],
         _options: Option<GenerateOptions>,
-        _on_token: StreamCallback,
+        on_token: StreamCallback,
     ) -> Result<LLMResponse> {
-        todo!("Gemini CLI stream not implemented")
+        let url = format!(
+            "{}?alt=sse",
+            Self::endpoint(model, "streamGenerateContent")?
+        );
+        let resp = self.send(&url, messages).await?;
+
+        let mut content = String::new();
+        let mut usage = None;
+        let mut stream = resp.bytes_stream();
+        let mut buffer = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|e| PraxisError::ProviderError(format!("Stream error: {}", e)))?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            // SSE events are separated by lines; only `data:` lines carry payloads
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer = buffer[newline_pos + 1..].to_string();
+
+                let Some(data) = line.strip_prefix("data:") else {
+                    continue;
+                };
+
+                let (text, event_usage) = parse_stream_event(data.trim())?;
+                if !text.is_empty() {
+                    on_token(&text);
+                    content.push_str(&text);
+                }
+                if event_usage.is_some() {
+                    usage = event_usage;
+                }
+            }
+        }
+
+        Ok(LLMResponse {
+            content,
+            tool_calls: vec![],
+            usage,
+            model: model.to_string(),
+        })
     }
 
     async fn is_model_available(&self, _model: &str) -> Result<bool> {
+        // `list_models` is a fixed stub rather than a real listing, so it
+        // can't rule a model out
         Ok(true)
     }
 
@@ -130,7 +244,34 @@ impl LLMProvider for GeminiProvider {
         Ok(())
     }
 
+    async fn account_info(&self) -> Result<String> {
+        let token = Self::access_token()?;
+        let project = std::env::var("GOOGLE_PROJECT_ID").ok();
+        super::google_account(&self.client, &token, project.as_deref()).await
+    }
+
     fn name(&self) -> &str {
         "google_gemini_cli"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_event() {
+        let data = r#"{"candidates":[{"content":{"role":"model","parts":[{"text":"Hel"},{"text":"lo"}]}}]}"#;
+        let (text, usage) = parse_stream_event(data).unwrap();
+        assert_eq!(text, "Hello");
+        assert!(usage.is_none());
+
+        let data = r#"{"candidates":[{"content":{"parts":[{"text":"!"}]},"finishReason":"STOP"}],
+            "usageMetadata":{"promptTokenCount":7,"candidatesTokenCount":3,"totalTokenCount":10}}"#;
+        let (text, usage) = parse_stream_event(data).unwrap();
+        assert_eq!(text, "!");
+        assert_eq!(usage.unwrap().total_tokens, 10);
+
+        assert!(parse_stream_event(r#"{"error":{"code":403}}"#).is_err());
+    }
+}
@@ -50,7 +50,11 @@ impl LLMProvider for KolaborateProvider {
         todo!("Kolaborate provider not implemented")
     }
 
-    async fn is_model_available(&self, _model: &str) -> Result<bool> {
+    async fn is_model_available(&self, model: &str) -> Result<bool> {
+        tracing::warn!(
+            model,
+            "Kolaborate provider is not available yet; can't use the model"
+        );
         Ok(false)
     }
 
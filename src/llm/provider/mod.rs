@@ -8,9 +8,13 @@ pub mod kolaborate;
 pub mod openrouter;
 
 use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
 
 use crate::core::config::{Config, ProviderType};
-use crate::core::Result;
+use crate::core::{PraxisError, Result};
 use crate::llm::traits::LLMProvider;
 use crate::llm::OllamaClient;
 
@@ -19,6 +23,120 @@ use self::gemini::GeminiProvider;
 use self::kolaborate::KolaborateProvider;
 use self::openrouter::OpenRouterProvider;
 
+/// Build the HTTP client a provider keeps for all its requests
+///
+/// Uses a read timeout rather than a total one so long streams aren't cut off
+/// while tokens are still arriving.
+pub(crate) fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .read_timeout(Duration::from_secs(120))
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+/// Retries after a 429 before giving up with `RateLimited`
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Wait after a 429 that doesn't say how long to wait
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(2);
+
+/// Longest `Retry-After` waited out; a longer one fails straight away
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Send a request to a remote provider, waiting out 429 responses
+///
+/// Sleeps for the `Retry-After` the provider suggests and retries, up to
+/// `MAX_RATE_LIMIT_RETRIES` times; after that, or if the suggested wait is
+/// too long, fails with `RateLimited`. Other responses are returned as is;
+/// `on_error` describes a request that couldn't be sent.
+pub(crate) async fn send_rate_limited(
+    request: reqwest::RequestBuilder,
+    on_error: impl Fn(reqwest::Error) -> PraxisError,
+) -> Result<reqwest::Response> {
+    let mut attempt = 0;
+    loop {
+        // Bodies here are always buffered JSON, so this only fails for streams
+        let Some(next) = request.try_clone() else {
+            return request.send().await.map_err(on_error);
+        };
+        let resp = next.send().await.map_err(&on_error)?;
+        if resp.status() != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(resp);
+        }
+
+        let retry_after = retry_after(resp.headers()).unwrap_or(DEFAULT_RATE_LIMIT_WAIT);
+        if attempt >= MAX_RATE_LIMIT_RETRIES || retry_after > MAX_RATE_LIMIT_WAIT {
+            return Err(PraxisError::RateLimited { retry_after });
+        }
+        attempt += 1;
+        tracing::warn!(
+            wait_secs = retry_after.as_secs_f32(),
+            attempt,
+            "Rate limited by the provider, waiting before retrying"
+        );
+        tokio::time::sleep(retry_after).await;
+    }
+}
+
+/// The wait a `Retry-After` header asks for, when given in seconds
+///
+/// The HTTP-date form isn't parsed; callers fall back to a default wait.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let secs: f64 = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Check if `model` appears in a provider's model list
+///
+/// Matching is case-insensitive and ignores a `google/` prefix on either side.
+pub(crate) fn model_in_list(model: &str, models: &[String]) -> bool {
+    let normalize = |name: &str| name.to_lowercase().replace("google/", "");
+    let wanted = normalize(model);
+    models.iter().any(|m| normalize(m) == wanted)
+}
+
+/// Google's endpoint describing an OAuth access token
+const GOOGLE_TOKENINFO_URL: &str = "https://oauth2.googleapis.com/tokeninfo";
+
+/// Who a Google access token belongs to, plus `project` when one is set
+pub(crate) async fn google_account(
+    client: &reqwest::Client,
+    token: &str,
+    project: Option<&str>,
+) -> Result<String> {
+    let request = client
+        .get(GOOGLE_TOKENINFO_URL)
+        .query(&[("access_token", token)]);
+    let resp = send_rate_limited(request, PraxisError::Http).await?;
+    if !resp.status().is_success() {
+        let error_text = resp.text().await.unwrap_or_default();
+        return Err(PraxisError::auth(format!(
+            "Google rejected the stored token: {}",
+            error_text
+        )));
+    }
+    let info: serde_json::Value = resp.json().await?;
+    Ok(describe_google_account(&info, project))
+}
+
+/// The email in a tokeninfo response, with the project if there is one
+fn describe_google_account(info: &serde_json::Value, project: Option<&str>) -> String {
+    let account = info["email"]
+        .as_str()
+        .unwrap_or("unknown account (token has no email scope)");
+    match project.filter(|p| !p.is_empty()) {
+        Some(project) => format!("{} (project {})", account, project),
+        None => account.to_string(),
+    }
+}
+
 /// Create a new LLM provider based on configuration
 pub async fn create_provider(config: &Config) -> Result<Arc<dyn LLMProvider>> {
     let provider: Arc<dyn LLMProvider> = match config.provider {
@@ -34,3 +152,104 @@ pub async fn create_provider(config: &Config) -> Result<Arc<dyn LLMProvider>> {
     };
     Ok(provider)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_in_list() {
+        let models = vec![
+            "google/gemini-3-pro".to_string(),
+            "openai/gpt-4o".to_string(),
+        ];
+
+        assert!(model_in_list("gemini-3-pro", &models));
+        assert!(model_in_list("Google/Gemini-3-Pro", &models));
+        assert!(model_in_list("OpenAI/GPT-4o", &models));
+        assert!(!model_in_list("gpt-4o", &models));
+        assert!(!model_in_list("gemini-3-pr", &models));
+    }
+
+    #[test]
+    fn test_describe_google_account() {
+        let info = serde_json::json!({"email": "dev@example.com", "expires_in": "3599"});
+        assert_eq!(
+            describe_google_account(&info, Some("my-project")),
+            "dev@example.com (project my-project)"
+        );
+        assert_eq!(describe_google_account(&info, None), "dev@example.com");
+        assert_eq!(
+            describe_google_account(&serde_json::json!({}), Some("")),
+            "unknown account (token has no email scope)"
+        );
+    }
+
+    #[test]
+    fn test_retry_after_parsing() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+        headers.insert(RETRY_AFTER, "0.5".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(500)));
+        headers.insert(RETRY_AFTER, "-5".parse().unwrap());
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_is_retried() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for reply in [
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+            ] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket.write_all(reply.as_bytes()).await;
+            }
+        });
+
+        let client = reqwest::Client::new();
+        let resp = send_rate_limited(client.post(&url).json(&"hi"), PraxisError::Http)
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), "ok");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_gives_up_on_long_waits() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket
+                .write_all(b"HTTP/1.1 429 Too Many Requests\r\nRetry-After: 3600\r\nContent-Length: 0\r\n\r\n")
+                .await;
+        });
+
+        let client = reqwest::Client::new();
+        let error = send_rate_limited(client.get(&url), PraxisError::Http)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            PraxisError::RateLimited { retry_after } if retry_after == Duration::from_secs(3600)
+        ));
+    }
+}
@@ -1,64 +1,352 @@
 //! OpenRouter Provider
 //!
-//! Implementation for OpenRouter API.
+//! Implementation for OpenRouter API. Speaks the OpenAI chat completions
+//! format, so pointing `providers.openrouter.base_url` at a local gateway
+//! (LM Studio, vLLM, LiteLLM) makes this a generic OpenAI-compatible client.
 
-use crate::core::{Config, Message, Result, ToolDefinition};
-use crate::llm::traits::{GenerateOptions, LLMProvider, LLMResponse, StreamCallback};
+use std::time::Duration;
+
+use crate::core::{Config, Message, PraxisError, Result, ToolCall, ToolDefinition};
+use crate::llm::traits::{GenerateOptions, LLMProvider, LLMResponse, StreamCallback, TokenUsage};
 use async_trait::async_trait;
+use futures::StreamExt;
 
 pub struct OpenRouterProvider {
-    #[allow(dead_code)]
-    config: Config,
+    /// API root, without a trailing slash
+    base_url: String,
+    /// Bearer token, if the endpoint needs one
+    api_key: Option<String>,
+    /// HTTP client reused across requests
+    client: reqwest::Client,
 }
 
 impl OpenRouterProvider {
     pub fn from_config(config: &Config) -> Self {
+        let openrouter = &config.providers.openrouter;
         Self {
-            config: config.clone(),
+            base_url: openrouter.base_url.trim_end_matches('/').to_string(),
+            api_key: openrouter.api_key.clone().filter(|k| !k.is_empty()),
+            client: super::http_client(),
+        }
+    }
+
+    /// Send a request, mapping non-success statuses to provider errors
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        timeout_secs: Option<u64>,
+    ) -> Result<reqwest::Response> {
+        let mut request = request;
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+        if let Some(secs) = timeout_secs {
+            request = request.timeout(Duration::from_secs(secs));
+        }
+
+        let resp = super::send_rate_limited(request, |e| {
+            PraxisError::ProviderError(format!("Request to {} failed: {}", self.base_url, e))
+        })
+        .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_text = resp.text().await.unwrap_or_default();
+            return Err(PraxisError::ProviderError(format!(
+                "OpenAI-compatible API error ({}): {}",
+                status, error_text
+            )));
         }
+
+        Ok(resp)
+    }
+
+    /// POST a chat completion request
+    async fn post_chat(
+        &self,
+        body: &serde_json::Value,
+        timeout_secs: Option<u64>,
+    ) -> Result<reqwest::Response> {
+        let url = format!("{}/chat/completions", self.base_url);
+        self.send(self.client.post(url).json(body), timeout_secs)
+            .await
+    }
+
+    async fn complete(
+        &self,
+        model: &str,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        options: Option<GenerateOptions>,
+    ) -> Result<LLMResponse> {
+        let options = options.unwrap_or_default();
+        let body = request_body(model, messages, tools, &options, false);
+        let resp = self.post_chat(&body, options.timeout_secs).await?;
+
+        let response_json: serde_json::Value = resp.json().await?;
+        parse_response(model, &response_json)
+    }
+}
+
+/// Build a chat completions request body
+fn request_body(
+    model: &str,
+    messages: &[Message],
+    tools: &[ToolDefinition],
+    options: &GenerateOptions,
+    stream: bool,
+) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": to_openai_messages(messages),
+        "stream": stream,
+    });
+
+    if !tools.is_empty() {
+        body["tools"] = serde_json::json!(tools);
+    }
+    if stream {
+        body["stream_options"] = serde_json::json!({"include_usage": true});
+    }
+    if let Some(temperature) = options.temperature {
+        body["temperature"] = serde_json::json!(temperature);
+    }
+    if let Some(max_tokens) = options.max_tokens {
+        body["max_tokens"] = serde_json::json!(max_tokens);
+    }
+    if let Some(ref stop) = options.stop {
+        body["stop"] = serde_json::json!(stop);
+    }
+    if let Some(top_p) = options.top_p {
+        body["top_p"] = serde_json::json!(top_p);
+    }
+    if let Some(seed) = options.seed {
+        body["seed"] = serde_json::json!(seed);
+    }
+
+    body
+}
+
+/// Convert messages to the OpenAI format
+///
+/// Our history doesn't keep tool call ids, so each assistant tool call gets
+/// a synthetic id and the tool results that follow it are matched in order.
+fn to_openai_messages(messages: &[Message]) -> Vec<serde_json::Value> {
+    let mut pending_ids = std::collections::VecDeque::new();
+
+    messages
+        .iter()
+        .enumerate()
+        .map(|(i, msg)| {
+            let content = if msg.images.is_empty() {
+                serde_json::json!(msg.content)
+            } else {
+                let mut parts = vec![serde_json::json!({"type": "text", "text": msg.content})];
+                parts.extend(msg.images.iter().map(|image| {
+                    serde_json::json!({
+                        "type": "image_url",
+                        "image_url": {"url": format!("data:image/png;base64,{}", image)}
+                    })
+                }));
+                serde_json::json!(parts)
+            };
+            let mut out = serde_json::json!({"role": msg.role, "content": content});
+
+            if let Some(ref calls) = msg.tool_calls {
+                pending_ids.clear();
+                let calls: Vec<serde_json::Value> = calls
+                    .iter()
+                    .enumerate()
+                    .map(|(j, call)| {
+                        let id = format!("call_{}_{}", i, j);
+                        pending_ids.push_back(id.clone());
+                        serde_json::json!({
+                            "id": id,
+                            "type": "function",
+                            "function": {
+                                "name": call.name,
+                                "arguments": call.arguments.to_string(),
+                            }
+                        })
+                    })
+                    .collect();
+                out["tool_calls"] = serde_json::json!(calls);
+            }
+
+            if msg.role == "tool" {
+                if let Some(id) = pending_ids.pop_front() {
+                    out["tool_call_id"] = serde_json::json!(id);
+                }
+                if let Some(ref name) = msg.tool_name {
+                    out["name"] = serde_json::json!(name);
+                }
+            }
+
+            out
+        })
+        .collect()
+}
+
+/// Parse a non-streaming chat completion
+fn parse_response(model: &str, json: &serde_json::Value) -> Result<LLMResponse> {
+    if let Some(error) = json.get("error") {
+        return Err(PraxisError::ProviderError(format!("API error: {}", error)));
     }
+
+    let message = json["choices"]
+        .get(0)
+        .map(|choice| &choice["message"])
+        .ok_or_else(|| PraxisError::ProviderError("Response has no choices".to_string()))?;
+
+    let tool_calls = message["tool_calls"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|call| {
+            let function = &call["function"];
+            let name = function["name"].as_str()?;
+            // Arguments arrive as a JSON-encoded string
+            let arguments = match &function["arguments"] {
+                serde_json::Value::String(s) => {
+                    serde_json::from_str(s).unwrap_or_else(|_| serde_json::Value::String(s.clone()))
+                }
+                other => other.clone(),
+            };
+            Some(ToolCall::new(name, arguments))
+        })
+        .collect();
+
+    Ok(LLMResponse {
+        content: message["content"].as_str().unwrap_or_default().to_string(),
+        tool_calls,
+        usage: parse_usage(&json["usage"]),
+        model: json["model"].as_str().unwrap_or(model).to_string(),
+    })
+}
+
+/// Token counts from a `usage` object, if present
+fn parse_usage(usage: &serde_json::Value) -> Option<TokenUsage> {
+    let prompt = usage["prompt_tokens"].as_u64()?;
+    let completion = usage["completion_tokens"].as_u64().unwrap_or(0);
+    Some(TokenUsage {
+        prompt_tokens: prompt as u32,
+        completion_tokens: completion as u32,
+        total_tokens: usage["total_tokens"]
+            .as_u64()
+            .unwrap_or(prompt + completion) as u32,
+        partial: false,
+    })
+}
+
+/// Text and usage carried by one streamed `data:` event
+fn parse_stream_event(data: &str) -> Result<(String, Option<TokenUsage>)> {
+    let event: serde_json::Value = serde_json::from_str(data)
+        .map_err(|e| PraxisError::ProviderError(format!("Failed to parse stream event: {}", e)))?;
+
+    if let Some(error) = event.get("error") {
+        return Err(PraxisError::ProviderError(format!(
+            "Stream error: {}",
+            error
+        )));
+    }
+
+    let text = event["choices"][0]["delta"]["content"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    Ok((text, parse_usage(&event["usage"])))
 }
 
 #[async_trait]
 impl LLMProvider for OpenRouterProvider {
     async fn chat(
         &self,
-        _model: &str,
-        _messages: &[Message],
-        _options: Option<GenerateOptions>,
+        model: &str,
+        messages: &[Message],
+        options: Option<GenerateOptions>,
     ) -> Result<LLMResponse> {
-        todo!("OpenRouter chat not implemented")
+        self.complete(model, messages, &[], options).await
     }
 
     async fn chat_with_tools(
         &self,
-        _model: &str,
-        _messages: &[Message],
-        _tools: &[ToolDefinition],
-        _options: Option<GenerateOptions>,
+        model: &str,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        options: Option<GenerateOptions>,
     ) -> Result<LLMResponse> {
-        todo!("OpenRouter tools not implemented")
+        self.complete(model, messages, tools, options).await
     }
 
     async fn chat_stream(
         &self,
-        _model: &str,
-        _messages: &[Message],
-        _options: Option<GenerateOptions>,
-        _on_token: StreamCallback,
+        model: &str,
+        messages: &[Message],
+        options: Option<GenerateOptions>,
+        on_token: StreamCallback,
     ) -> Result<LLMResponse> {
-        todo!("OpenRouter stream not implemented")
+        let options = options.unwrap_or_default();
+        let body = request_body(model, messages, &[], &options, true);
+        let resp = self.post_chat(&body, options.timeout_secs).await?;
+
+        let mut content = String::new();
+        let mut usage = None;
+        let mut stream = resp.bytes_stream();
+        let mut buffer = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|e| PraxisError::ProviderError(format!("Stream error: {}", e)))?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            // SSE events are separated by lines; only `data:` lines carry payloads
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer = buffer[newline_pos + 1..].to_string();
+
+                let Some(data) = line.strip_prefix("data:") else {
+                    continue;
+                };
+                let data = data.trim();
+                if data == "[DONE]" {
+                    continue;
+                }
+
+                let (text, event_usage) = parse_stream_event(data)?;
+                if !text.is_empty() {
+                    on_token(&text);
+                    content.push_str(&text);
+                }
+                if event_usage.is_some() {
+                    usage = event_usage;
+                }
+            }
+        }
+
+        Ok(LLMResponse {
+            content,
+            tool_calls: vec![],
+            usage,
+            model: model.to_string(),
+        })
     }
 
-    async fn is_model_available(&self, _model: &str) -> Result<bool> {
-        Ok(true)
+    async fn is_model_available(&self, model: &str) -> Result<bool> {
+        Ok(super::model_in_list(model, &self.list_models().await?))
     }
 
     async fn list_models(&self) -> Result<Vec<String>> {
-        Ok(vec![
-            "anthropic/claude-3-opus".to_string(),
-            "openai/gpt-4o".to_string(),
-        ])
+        let url = format!("{}/models", self.base_url);
+        let resp = self.send(self.client.get(url), None).await?;
+        let json: serde_json::Value = resp.json().await?;
+
+        Ok(json["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|m| m["id"].as_str().map(String::from))
+            .collect())
     }
 
     async fn pull_model(&self, _model: &str) -> Result<()> {
@@ -69,3 +357,77 @@ impl LLMProvider for OpenRouterProvider {
         "openrouter"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_results_reference_their_calls() {
+        let mut user = Message::user("Read it");
+        user.images.push("aGk=".to_string());
+        let messages = vec![
+            user,
+            Message::assistant_with_tools(
+                "",
+                vec![ToolCall::new(
+                    "read_file",
+                    serde_json::json!({"path": "a.rs"}),
+                )],
+            ),
+            Message::tool("read_file", "fn main() {}"),
+        ];
+        let converted = to_openai_messages(&messages);
+
+        assert_eq!(converted[0]["content"][1]["type"], "image_url");
+        assert_eq!(
+            converted[1]["tool_calls"][0]["function"]["arguments"],
+            r#"{"path":"a.rs"}"#
+        );
+        assert_eq!(
+            converted[2]["tool_call_id"],
+            converted[1]["tool_calls"][0]["id"]
+        );
+    }
+
+    #[test]
+    fn test_parse_response_with_tool_calls() {
+        let json = serde_json::json!({
+            "model": "qwen3-8b",
+            "choices": [{"message": {
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{"id": "x", "type": "function", "function": {
+                    "name": "browser_url",
+                    "arguments": "{\"url\": \"https://example.com\"}"
+                }}]
+            }}],
+            "usage": {"prompt_tokens": 12, "completion_tokens": 3, "total_tokens": 15}
+        });
+        let response = parse_response("local", &json).unwrap();
+
+        assert_eq!(response.content, "");
+        assert_eq!(response.tool_calls[0].name, "browser_url");
+        assert_eq!(
+            response.tool_calls[0].get_string("url").as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(response.usage.unwrap().total_tokens, 15);
+        assert_eq!(response.model, "qwen3-8b");
+    }
+
+    #[test]
+    fn test_parse_stream_event() {
+        let (text, usage) =
+            parse_stream_event(r#"{"choices":[{"delta":{"content":"Hel"}}]}"#).unwrap();
+        assert_eq!(text, "Hel");
+        assert!(usage.is_none());
+
+        let (text, usage) = parse_stream_event(
+            r#"{"choices":[],"usage":{"prompt_tokens":5,"completion_tokens":2,"total_tokens":7}}"#,
+        )
+        .unwrap();
+        assert_eq!(text, "");
+        assert_eq!(usage.unwrap().total_tokens, 7);
+    }
+}
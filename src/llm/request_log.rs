@@ -0,0 +1,180 @@
+//! Request transcript logging
+//!
+//! Appends every LLM request and response to a JSON Lines file per day in
+//! `.praxis/logs`, for debugging model behavior over long sessions.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::debug;
+
+/// JSON keys whose values are never written to the log
+const SECRET_KEYS: &[&str] = &[
+    "authorization",
+    "api_key",
+    "apikey",
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "password",
+];
+
+/// Writes request/response records to `requests-<date>.jsonl`
+#[derive(Debug, Clone)]
+pub struct RequestLog {
+    dir: PathBuf,
+}
+
+impl RequestLog {
+    /// Log into `dir`, which is created on first write
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Log into `.praxis/logs` under the project root `workdir`
+    pub fn in_workdir(workdir: &Path) -> Self {
+        Self::new(workdir.join(".praxis").join("logs"))
+    }
+
+    /// Append one record; `body` is stored as JSON when it parses as JSON
+    ///
+    /// Failures are only reported at debug level so logging never breaks a request.
+    pub fn record(&self, kind: &str, body: &str) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let mut body = serde_json::from_str(body)
+            .unwrap_or_else(|_| serde_json::Value::String(body.to_string()));
+        redact(&mut body);
+
+        let line = serde_json::json!({
+            "timestamp": now,
+            "kind": kind,
+            "body": body,
+        });
+
+        if let Err(e) = self.append(&self.file_for(now), &line.to_string()) {
+            debug!(error = %e, "Failed to write request log");
+        }
+    }
+
+    /// Path of the log file for a Unix timestamp
+    fn file_for(&self, secs: u64) -> PathBuf {
+        self.dir.join(format!("requests-{}.jsonl", utc_date(secs)))
+    }
+
+    fn append(&self, path: &Path, line: &str) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)
+    }
+}
+
+/// Blank out secret fields and bearer tokens anywhere in `value`
+fn redact(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SECRET_KEYS.contains(&key.to_lowercase().as_str()) {
+                    *value = serde_json::Value::String("[REDACTED]".to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact),
+        serde_json::Value::String(s) => {
+            if let Some(redacted) = redact_bearer(s) {
+                *s = redacted;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace the token after each `Bearer ` in `text`, if there is one
+fn redact_bearer(text: &str) -> Option<String> {
+    const PREFIX: &str = "bearer ";
+
+    let lower = text.to_ascii_lowercase();
+    if !lower.contains(PREFIX) {
+        return None;
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = 0;
+    while let Some(found) = lower[rest..].find(PREFIX) {
+        let token_start = rest + found + PREFIX.len();
+        out.push_str(&text[rest..token_start]);
+        out.push_str("[REDACTED]");
+        rest = text[token_start..]
+            .find(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+            .map_or(text.len(), |end| token_start + end);
+    }
+    out.push_str(&text[rest..]);
+    Some(out)
+}
+
+/// `YYYY-MM-DD` (UTC) for a Unix timestamp
+fn utc_date(secs: u64) -> String {
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utc_date() {
+        assert_eq!(utc_date(0), "1970-01-01");
+        assert_eq!(utc_date(951_782_400), "2000-02-29");
+        assert_eq!(utc_date(1_767_225_599), "2025-12-31");
+    }
+
+    #[test]
+    fn test_records_are_redacted() {
+        let dir = std::env::temp_dir().join("praxis_test_request_log");
+        let _ = fs::remove_dir_all(&dir);
+        let log = RequestLog::new(&dir);
+
+        log.record(
+            "Request",
+            r#"{"model": "m", "api_key": "sk-123", "note": "Authorization: Bearer abc.def rest"}"#,
+        );
+        log.record("Parse Error", "not json");
+
+        let entry = fs::read_dir(&dir).unwrap().next().unwrap().unwrap();
+        let content = fs::read_to_string(entry.path()).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["body"]["api_key"], "[REDACTED]");
+        assert_eq!(
+            lines[0]["body"]["note"],
+            "Authorization: Bearer [REDACTED] rest"
+        );
+        assert_eq!(lines[1]["body"], "not json");
+        assert!(!content.contains("sk-123"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -4,29 +4,71 @@
 
 use async_trait::async_trait;
 use futures::Stream;
+use serde::{Deserialize, Serialize};
 use std::pin::Pin;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
-use crate::core::{Message, Result, ToolCall, ToolDefinition};
+use crate::core::{Message, PraxisError, Result, ToolCall, ToolDefinition};
 
 /// Response from an LLM provider
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LLMResponse {
     /// Text content of the response
     pub content: String,
     /// Any tool calls the model wants to make
+    #[serde(default)]
     pub tool_calls: Vec<ToolCall>,
     /// Token usage information
+    #[serde(default)]
     pub usage: Option<TokenUsage>,
     /// Model that generated the response
     pub model: String,
 }
 
 /// Token usage information
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TokenUsage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
+    /// Whether some contributing response didn't report usage
+    #[serde(default)]
+    pub partial: bool,
+}
+
+impl std::fmt::Display for TokenUsage {
+    /// "123 prompt + 456 completion = 579 total", noting a partial count
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} prompt + {} completion = {} total",
+            self.prompt_tokens, self.completion_tokens, self.total_tokens
+        )?;
+        if self.partial {
+            write!(f, " (partial)")?;
+        }
+        Ok(())
+    }
+}
+
+impl TokenUsage {
+    /// Add a response's usage to this total
+    ///
+    /// Responses without usage count as zero and mark the total as partial.
+    pub fn add(&mut self, usage: Option<&TokenUsage>) {
+        match usage {
+            Some(usage) => {
+                self.prompt_tokens = self.prompt_tokens.saturating_add(usage.prompt_tokens);
+                self.completion_tokens = self
+                    .completion_tokens
+                    .saturating_add(usage.completion_tokens);
+                self.total_tokens = self.total_tokens.saturating_add(usage.total_tokens);
+                self.partial |= usage.partial;
+            }
+            None => self.partial = true,
+        }
+    }
 }
 
 /// Options for LLM generation
@@ -40,6 +82,20 @@ pub struct GenerateOptions {
     pub stop: Option<Vec<String>>,
     /// Whether to stream the response
     pub stream: bool,
+    /// Timeout for this request in seconds, overriding the client default
+    pub timeout_secs: Option<u64>,
+    /// Context window size in tokens
+    pub num_ctx: Option<u32>,
+    /// Nucleus sampling threshold
+    pub top_p: Option<f32>,
+    /// Sample only from the k most likely tokens
+    pub top_k: Option<u32>,
+    /// Penalty applied to repeated tokens
+    pub repeat_penalty: Option<f32>,
+    /// Random seed, for reproducible output
+    pub seed: Option<i64>,
+    /// Stops a streaming request early; the text received so far is returned
+    pub cancel: Option<CancellationToken>,
 }
 
 /// A chunk from a streaming response
@@ -88,6 +144,33 @@ pub type StreamResponse = Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send>
 /// Callback function for streaming tokens
 pub type StreamCallback = Box<dyn Fn(&str) + Send + Sync>;
 
+/// Something that happened during a streaming response
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// A piece of response text
+    Token(String),
+    /// The model started a call to the named tool
+    ToolCallStarted(String),
+    /// The response finished; usage is marked partial if the provider didn't report it
+    Done(TokenUsage),
+}
+
+/// Callback for structured streaming events
+pub type StreamEventCallback = Box<dyn Fn(&StreamEvent) + Send + Sync>;
+
+/// Wrap a text callback as an event callback that only sees tokens
+pub fn text_events(on_token: StreamCallback) -> StreamEventCallback {
+    Box::new(move |event| {
+        if let StreamEvent::Token(text) = event {
+            on_token(text);
+        }
+    })
+}
+
+/// Callback for model download progress: status text and percent complete
+/// (when the provider reports sizes)
+pub type PullProgressCallback = Box<dyn Fn(&str, Option<f32>) + Send + Sync>;
+
 /// Trait for LLM providers
 #[async_trait]
 pub trait LLMProvider: Send + Sync {
@@ -117,15 +200,165 @@ pub trait LLMProvider: Send + Sync {
         on_token: StreamCallback,
     ) -> Result<LLMResponse>;
 
+    /// Generate a streaming response with tool definitions
+    ///
+    /// Assistant text is passed to `on_token` as it arrives and tool calls
+    /// are returned once the response completes. Providers without native
+    /// support fall back to `chat_with_tools` and emit the full text at once.
+    async fn chat_with_tools_stream(
+        &self,
+        model: &str,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        options: Option<GenerateOptions>,
+        on_token: StreamCallback,
+    ) -> Result<LLMResponse> {
+        let response = self
+            .chat_with_tools(model, messages, tools, options)
+            .await?;
+        if !response.content.is_empty() {
+            on_token(&response.content);
+        }
+        Ok(response)
+    }
+
+    /// Generate a streaming response with tool definitions, reporting
+    /// structured events
+    ///
+    /// Providers without native support fall back to `chat_with_tools_stream`
+    /// and report tool calls and usage once the response completes.
+    async fn chat_with_tools_stream_events(
+        &self,
+        model: &str,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        options: Option<GenerateOptions>,
+        on_event: StreamEventCallback,
+    ) -> Result<LLMResponse> {
+        let on_event = Arc::new(on_event);
+        let forward = Arc::clone(&on_event);
+        let response = self
+            .chat_with_tools_stream(
+                model,
+                messages,
+                tools,
+                options,
+                Box::new(move |token| forward(&StreamEvent::Token(token.to_string()))),
+            )
+            .await?;
+
+        for call in &response.tool_calls {
+            on_event(&StreamEvent::ToolCallStarted(call.name.clone()));
+        }
+        on_event(&StreamEvent::Done(usage_or_partial(
+            response.usage.as_ref(),
+        )));
+        Ok(response)
+    }
+
     /// Check if a model is available
     async fn is_model_available(&self, model: &str) -> Result<bool>;
 
     /// List available models
     async fn list_models(&self) -> Result<Vec<String>>;
 
+    /// List available models, bypassing any cached list
+    ///
+    /// Providers without a cache just call `list_models`.
+    async fn refresh_models(&self) -> Result<Vec<String>> {
+        self.list_models().await
+    }
+
     /// Pull/download a model
     async fn pull_model(&self, model: &str) -> Result<()>;
 
+    /// Pull/download a model, reporting progress as it goes
+    ///
+    /// Providers without progress reporting just call `pull_model`.
+    async fn pull_model_with_progress(
+        &self,
+        model: &str,
+        on_progress: PullProgressCallback,
+    ) -> Result<()> {
+        let _ = on_progress;
+        self.pull_model(model).await
+    }
+
+    /// Embed each input as a vector, in order
+    ///
+    /// Providers without an embeddings endpoint return an error.
+    async fn embeddings(&self, model: &str, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+        let _ = (model, inputs);
+        Err(PraxisError::Other("embeddings not supported".to_string()))
+    }
+
+    /// The account or project the provider is signed in as
+    ///
+    /// Providers without accounts (like Ollama) return "not applicable".
+    async fn account_info(&self) -> Result<String> {
+        Ok("not applicable".to_string())
+    }
+
     /// Get the provider name
     fn name(&self) -> &str;
 }
+
+/// `usage`, or an empty total marked partial when it wasn't reported
+pub(crate) fn usage_or_partial(usage: Option<&TokenUsage>) -> TokenUsage {
+    let mut total = TokenUsage::default();
+    total.add(usage);
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_add_marks_missing_counts_partial() {
+        let mut total = TokenUsage::default();
+        total.add(Some(&TokenUsage {
+            prompt_tokens: 10,
+            completion_tokens: 5,
+            total_tokens: 15,
+            partial: false,
+        }));
+        assert_eq!(total.total_tokens, 15);
+        assert!(!total.partial);
+
+        total.add(None);
+        assert_eq!(total.total_tokens, 15);
+        assert!(total.partial);
+    }
+
+    #[test]
+    fn test_response_round_trips_through_json() {
+        let response = LLMResponse {
+            content: "Reading it".to_string(),
+            tool_calls: vec![ToolCall::new(
+                "read_file",
+                serde_json::json!({"path": "src/main.rs", "lines": [1, 20]}),
+            )],
+            usage: Some(TokenUsage {
+                prompt_tokens: 123,
+                completion_tokens: 456,
+                total_tokens: 579,
+                partial: false,
+            }),
+            model: "qwen3:8b".to_string(),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LLMResponse>(&json).unwrap(),
+            response
+        );
+        assert_eq!(
+            response.usage.unwrap().to_string(),
+            "123 prompt + 456 completion = 579 total"
+        );
+
+        let bare: LLMResponse = serde_json::from_str(r#"{"content": "hi", "model": "m"}"#).unwrap();
+        assert!(bare.tool_calls.is_empty() && bare.usage.is_none());
+    }
+}
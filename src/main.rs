@@ -2,7 +2,10 @@
 //!
 //! Main entry point for the CLI application.
 
-use clap::Parser;
+use std::io::IsTerminal;
+
+use clap::{Parser, Subcommand};
+use praxis::core::Overrides;
 use praxis::{Config, Repl};
 
 /// Praxis - Offline-First AI Coding Agent
@@ -33,34 +36,80 @@ struct Args {
     /// Single prompt mode (non-interactive)
     #[arg(long, short = 'p')]
     prompt: Option<String>,
+
+    /// Answer the single prompt with the executor directly, without tools
+    #[arg(long, requires = "prompt")]
+    chat: bool,
+
+    /// Don't stream model output (orchestrator or executor)
+    #[arg(long)]
+    no_stream: bool,
+
+    /// Config file to use instead of ~/.config/praxis/config.toml
+    /// (also settable with PRAXIS_CONFIG)
+    #[arg(long, value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
+
+    /// Sampling seed, for reproducible output
+    #[arg(long)]
+    seed: Option<i64>,
+
+    /// Print only the code from a single-prompt answer, without markdown fences
+    #[arg(long, requires = "prompt")]
+    raw: bool,
+
+    /// Skip the startup banner, printing only the provider and models
+    #[arg(long, short = 'q')]
+    quiet: bool,
+
+    /// Project root for sessions, file tools and shell commands
+    /// (default: the current directory)
+    #[arg(long, value_name = "PATH")]
+    cwd: Option<std::path::PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check that Ollama, the models, agent-browser and the config are set up
+    Doctor,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
-    // Build configuration
-    let mut config = Config::load();
-
-    // Apply CLI overrides
-    if let Some(ref orchestrator) = args.orchestrator {
-        config.models.orchestrator = orchestrator.clone();
-    }
-
-    if let Some(ref executor) = args.executor {
-        config.models.executor = executor.clone();
-    }
+    // Logging first, so problems loading the config are reported
+    praxis::core::logging::init(args.debug);
 
-    if args.debug {
-        config.agent.debug = true;
-    }
-
-    if args.no_browser {
-        config.browser.enabled = false;
+    // Build configuration
+    // CLI overrides are kept so `config reload` applies them again
+    let overrides = Overrides {
+        orchestrator: args.orchestrator.clone(),
+        executor: args.executor.clone(),
+        debug: args.debug,
+        seed: args.seed,
+        no_stream: args.no_stream,
+        no_browser: args.no_browser,
+        headed: args.headed,
+        quiet: args.quiet,
+        workdir: args.cwd.clone(),
+    };
+    let mut config = Config::load_with_overrides(args.config.clone(), overrides)?;
+    if !args.debug && config.agent.debug {
+        praxis::core::logging::set_debug(true);
     }
-
-    if args.headed {
-        config.browser.headed = true;
+    config.check_workdir()?;
+
+    if let Some(Command::Doctor) = args.command {
+        let checks = praxis::cli::doctor::diagnose(&config).await;
+        println!("{}", praxis::cli::doctor::format_report(&checks));
+        if checks.iter().any(|c| !c.ok) {
+            std::process::exit(1);
+        }
+        return Ok(());
     }
 
     // Single prompt mode
@@ -68,8 +117,24 @@ async fn main() -> anyhow::Result<()> {
         let mut agent = praxis::Agent::with_config(config).await?;
         agent.initialize().await?;
 
-        let response = agent.process(&prompt).await?;
-        println!("{}", response);
+        let response = if args.chat {
+            agent.chat(&prompt).await
+        } else {
+            agent.process(&prompt).await
+        };
+        agent.shutdown().await;
+        let response = response?;
+        if args.raw || agent.config().agent.strip_code_fences {
+            println!("{}", praxis::agent::strip_code_fences(&response));
+        } else if std::io::stdout().is_terminal() {
+            // On a terminal, a streamed answer was already shown on stderr
+            let unseen = agent.unstreamed_part(&response);
+            if !unseen.is_empty() {
+                println!("{}", unseen);
+            }
+        } else {
+            println!("{}", response);
+        }
         return Ok(());
     }
 
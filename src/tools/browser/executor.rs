@@ -2,11 +2,274 @@
 //!
 //! Provides async interface to agent-browser commands.
 
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncReadExt;
 use tokio::process::Command;
+use url::Url;
 
 use crate::core::{PraxisError, Result, ToolResult};
-use crate::tools::browser::snapshot::Snapshot;
+use crate::tools::browser::snapshot::{Element, Snapshot};
+
+/// Why a navigation failed, with advice the model can act on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationFailure {
+    /// URL has no scheme (e.g. `example.com`)
+    MissingScheme,
+    /// URL couldn't be parsed
+    MalformedUrl,
+    /// Scheme other than http, https or file
+    UnsupportedScheme,
+    /// Input isn't a URL at all (e.g. a search phrase)
+    NotAUrl,
+    /// Host name didn't resolve
+    DnsFailure,
+    /// Server refused or reset the connection
+    ConnectionRefused,
+    /// Page took too long to load
+    Timeout,
+    /// TLS certificate problem
+    Certificate,
+    /// Anything else
+    Other,
+}
+
+impl NavigationFailure {
+    /// Classify agent-browser error output
+    pub fn classify(stderr: &str) -> Self {
+        let stderr = stderr.to_lowercase();
+
+        if stderr.contains("err_name_not_resolved")
+            || stderr.contains("enotfound")
+            || stderr.contains("getaddrinfo")
+        {
+            Self::DnsFailure
+        } else if stderr.contains("err_connection_refused")
+            || stderr.contains("econnrefused")
+            || stderr.contains("err_connection_reset")
+        {
+            Self::ConnectionRefused
+        } else if stderr.contains("timeout") || stderr.contains("timed out") {
+            Self::Timeout
+        } else if stderr.contains("err_cert") || stderr.contains("certificate") {
+            Self::Certificate
+        } else if stderr.contains("invalid url") || stderr.contains("err_invalid_url") {
+            Self::MalformedUrl
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Human-readable category
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::MissingScheme => "missing URL scheme",
+            Self::MalformedUrl => "malformed URL",
+            Self::UnsupportedScheme => "unsupported URL scheme",
+            Self::NotAUrl => "not a URL",
+            Self::DnsFailure => "host not found",
+            Self::ConnectionRefused => "connection refused",
+            Self::Timeout => "timed out",
+            Self::Certificate => "certificate error",
+            Self::Other => "navigation failed",
+        }
+    }
+
+    /// What to try next
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            Self::MissingScheme => "add https:// to the start of the URL",
+            Self::MalformedUrl => "check the URL for typos or invalid characters",
+            Self::UnsupportedScheme => "use an http:// or https:// URL",
+            Self::NotAUrl => {
+                "provide a valid URL like https://example.com, or search with \
+                 https://duckduckgo.com/?q=your+terms"
+            }
+            Self::DnsFailure => "check the domain spelling; the site may not exist",
+            Self::ConnectionRefused => "the site may be down; try again later or another source",
+            Self::Timeout => "the site may be slow or down; retry or try another source",
+            Self::Certificate => "the site's HTTPS is misconfigured; try another source",
+            Self::Other => "check the URL or try another source",
+        }
+    }
+
+    /// Build the tool result reported to the model
+    fn into_result(self, url: &str, detail: &str) -> ToolResult {
+        let mut output = format!(
+            "Could not open {}: {}. Suggestion: {}.",
+            url,
+            self.category(),
+            self.suggestion()
+        );
+        if !detail.is_empty() {
+            output.push_str(&format!("\nDetails: {}", detail));
+        }
+
+        ToolResult {
+            tool_name: "browser_url".to_string(),
+            success: false,
+            output,
+            data: Some(serde_json::json!({
+                "url": url,
+                "category": self.category(),
+                "suggestion": self.suggestion(),
+            })),
+        }
+    }
+}
+
+/// Check that a URL can be navigated to before launching the browser
+pub fn validate_url(url: &str) -> std::result::Result<Url, NavigationFailure> {
+    let parsed = Url::parse(url.trim()).map_err(|e| match e {
+        url::ParseError::RelativeUrlWithoutBase => NavigationFailure::MissingScheme,
+        _ => NavigationFailure::MalformedUrl,
+    })?;
+
+    match parsed.scheme() {
+        "http" | "https" => {
+            if parsed.host_str().is_none_or(|h| h.is_empty()) {
+                return Err(NavigationFailure::MalformedUrl);
+            }
+            Ok(parsed)
+        }
+        "file" | "about" | "data" => Ok(parsed),
+        // "example.com:8080" and "localhost:3000" parse with the host as the scheme
+        _ if !url.contains("://")
+            && (url.contains('.')
+                || url
+                    .split_once(':')
+                    .is_some_and(|(_, rest)| rest.starts_with(|c: char| c.is_ascii_digit()))) =>
+        {
+            Err(NavigationFailure::MissingScheme)
+        }
+        _ => Err(NavigationFailure::UnsupportedScheme),
+    }
+}
+
+/// Turn model-provided input into a navigable URL
+///
+/// Bare domains get `https://` (or `http://` for localhost); inputs that
+/// can't be a URL, such as search phrases, are rejected.
+pub fn normalize_url(input: &str) -> std::result::Result<String, NavigationFailure> {
+    let input = input.trim();
+
+    if input.is_empty() || input.chars().any(char::is_whitespace) {
+        return Err(NavigationFailure::NotAUrl);
+    }
+
+    match validate_url(input) {
+        Ok(url) => Ok(url.to_string()),
+        Err(NavigationFailure::MissingScheme) => {
+            let host = input.split(['/', ':', '?', '#']).next().unwrap_or("");
+            let is_local = host == "localhost" || host.parse::<std::net::IpAddr>().is_ok();
+
+            if !is_local && !host.contains('.') {
+                return Err(NavigationFailure::NotAUrl);
+            }
+
+            let scheme = if is_local { "http" } else { "https" };
+            validate_url(&format!("{}://{}", scheme, input)).map(|url| url.to_string())
+        }
+        Err(failure) => Err(failure),
+    }
+}
+
+/// An open browser tab
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabInfo {
+    /// Index to pass to `switch_tab`
+    pub index: usize,
+    /// Page title
+    pub title: String,
+    /// Page URL
+    pub url: String,
+    /// Whether this is the active tab
+    pub active: bool,
+}
+
+/// Parse `agent-browser tabs --json` output
+///
+/// Accepts a bare array or one nested under `tabs` / `data.tabs`.
+fn parse_tabs(output: &str) -> Vec<TabInfo> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(output) else {
+        return Vec::new();
+    };
+
+    let tabs = [
+        &value,
+        &value["tabs"],
+        &value["data"]["tabs"],
+        &value["data"],
+    ]
+    .into_iter()
+    .find_map(|v| v.as_array())
+    .cloned()
+    .unwrap_or_default();
+
+    tabs.iter()
+        .enumerate()
+        .map(|(i, tab)| TabInfo {
+            index: tab["index"].as_u64().map(|n| n as usize).unwrap_or(i),
+            title: tab["title"].as_str().unwrap_or("(untitled)").to_string(),
+            url: tab["url"].as_str().unwrap_or("").to_string(),
+            active: tab["active"].as_bool().unwrap_or(false),
+        })
+        .collect()
+}
+
+/// A `major.minor.patch` version number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    /// Create a version
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Find the first version number in `text`, e.g. "agent-browser v0.5.2"
+    ///
+    /// A missing patch number counts as 0; pre-release suffixes are ignored.
+    pub fn parse(text: &str) -> Option<Self> {
+        text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .find_map(|word| {
+                let mut parts = word.split('.').map(|p| p.parse::<u32>().ok());
+                let major = parts.next()??;
+                let minor = parts.next()??;
+                let patch = parts.next().unwrap_or(Some(0))?;
+                Some(Self::new(major, minor, patch))
+            })
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// How `BrowserExecutor::attach` found its session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStart {
+    /// The session already had this page open
+    Reused(String),
+    /// The session had no page, so a blank one was opened
+    Started,
+}
+
+/// Oldest agent-browser whose snapshot output `Snapshot` parses correctly
+pub const MIN_AGENT_BROWSER_VERSION: Version = Version::new(0, 4, 0);
 
 /// Executor for browser automation via agent-browser CLI
 pub struct BrowserExecutor {
@@ -14,6 +277,16 @@ pub struct BrowserExecutor {
     session_name: String,
     /// Whether to run in headed mode
     headed: bool,
+    /// How long a single agent-browser command may run before it's killed
+    timeout: Duration,
+    /// Most recent parsed snapshot, used to resolve refs given by description
+    last_snapshot: Mutex<Option<Snapshot>>,
+    /// Report what changed after an action instead of the full snapshot
+    snapshot_diffs: bool,
+    /// Most links `extract_links` returns
+    max_links: usize,
+    /// Directory relative screenshot paths are resolved against
+    workdir: PathBuf,
 }
 
 impl BrowserExecutor {
@@ -22,9 +295,61 @@ impl BrowserExecutor {
         Self {
             session_name: session_name.into(),
             headed: false,
+            timeout: Duration::from_millis(30_000),
+            last_snapshot: Mutex::new(None),
+            snapshot_diffs: true,
+            max_links: DEFAULT_MAX_LINKS,
+            workdir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         }
     }
 
+    /// Set the directory relative screenshot paths are resolved against
+    pub fn set_workdir(&mut self, workdir: impl Into<PathBuf>) {
+        self.workdir = workdir.into();
+    }
+
+    /// Connect to `session_name`, reusing its open page if it has one
+    ///
+    /// Otherwise a blank page is opened, so the browser is running before the
+    /// first browser tool needs it.
+    pub async fn attach(session_name: impl Into<String>) -> Result<(Self, SessionStart)> {
+        let executor = Self::new(session_name);
+        let start = executor.resume().await?;
+        Ok((executor, start))
+    }
+
+    /// Reuse this session's open page, or open a blank one, like `attach`
+    pub async fn resume(&self) -> Result<SessionStart> {
+        match self.get_url().await {
+            Ok(url) if !url.is_empty() && url != "about:blank" => {
+                return Ok(SessionStart::Reused(url))
+            }
+            Err(PraxisError::AgentBrowserNotFound) => {
+                return Err(PraxisError::AgentBrowserNotFound)
+            }
+            // No page yet (agent-browser errors when there's nothing open)
+            _ => {}
+        }
+
+        self.run_command(&["open", "about:blank"]).await?;
+        Ok(SessionStart::Started)
+    }
+
+    /// Set the per-command timeout
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Show only what changed after actions (true) or full snapshots (false)
+    pub fn set_snapshot_diffs(&mut self, enabled: bool) {
+        self.snapshot_diffs = enabled;
+    }
+
+    /// Set the most links `extract_links` returns
+    pub fn set_max_links(&mut self, max_links: usize) {
+        self.max_links = max_links;
+    }
+
     /// Set headed mode
     pub fn set_headed(&mut self, headed: bool) {
         self.headed = headed;
@@ -42,8 +367,55 @@ impl BrowserExecutor {
             .unwrap_or(false)
     }
 
+    /// Get the installed agent-browser's version
+    ///
+    /// Fails with `AgentBrowserNotFound` if it isn't installed, and with a
+    /// browser error naming both versions if it's older than
+    /// `MIN_AGENT_BROWSER_VERSION`, whose snapshots may not parse.
+    pub async fn check_version() -> Result<Version> {
+        let output = Command::new("agent-browser")
+            .arg("--version")
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    PraxisError::AgentBrowserNotFound
+                } else {
+                    PraxisError::browser(format!("Failed to run agent-browser: {}", e))
+                }
+            })?;
+        if !output.status.success() {
+            return Err(PraxisError::AgentBrowserNotFound);
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let version = Version::parse(&text).ok_or_else(|| {
+            PraxisError::browser(format!(
+                "Couldn't read the agent-browser version from '{}'",
+                text.trim()
+            ))
+        })?;
+        if version < MIN_AGENT_BROWSER_VERSION {
+            return Err(PraxisError::browser(format!(
+                "agent-browser {} is older than {}, the oldest supported version. \
+                 Update with: npm install -g agent-browser@latest",
+                version, MIN_AGENT_BROWSER_VERSION
+            )));
+        }
+        Ok(version)
+    }
+
     /// Run an agent-browser command
+    ///
+    /// A command that runs past the timeout is killed and reaped, and a
+    /// timeout error is returned.
     async fn run_command(&self, args: &[&str]) -> Result<String> {
+        self.run_command_within(args, self.timeout).await
+    }
+
+    /// Run an agent-browser command with a specific timeout
+    async fn run_command_within(&self, args: &[&str], timeout: Duration) -> Result<String> {
         let mut cmd = Command::new("agent-browser");
         cmd.args(["--session", &self.session_name]);
 
@@ -55,7 +427,7 @@ impl BrowserExecutor {
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
 
-        let output = cmd.output().await.map_err(|e| {
+        let mut child = cmd.spawn().map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 PraxisError::AgentBrowserNotFound
             } else {
@@ -63,10 +435,43 @@ impl BrowserExecutor {
             }
         })?;
 
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        let (Some(mut stdout_pipe), Some(mut stderr_pipe)) =
+            (child.stdout.take(), child.stderr.take())
+        else {
+            return Err(PraxisError::browser(
+                "Failed to capture agent-browser output",
+            ));
+        };
+
+        // Read both pipes while waiting so a chatty command can't fill one and block
+        let run = async {
+            let mut stdout = Vec::new();
+            let mut stderr = Vec::new();
+            let (status, _, _) = tokio::try_join!(
+                child.wait(),
+                stdout_pipe.read_to_end(&mut stdout),
+                stderr_pipe.read_to_end(&mut stderr)
+            )?;
+            Ok::<_, std::io::Error>((status, stdout, stderr))
+        };
+
+        let (status, stdout, stderr) = match tokio::time::timeout(timeout, run).await {
+            Ok(result) => result
+                .map_err(|e| PraxisError::browser(format!("Failed to run agent-browser: {}", e)))?,
+            Err(_) => {
+                let _ = child.kill().await;
+                return Err(PraxisError::browser(format!(
+                    "timeout: agent-browser {} did not finish within {}ms",
+                    args.first().unwrap_or(&""),
+                    timeout.as_millis()
+                )));
+            }
+        };
+
+        if status.success() {
+            Ok(String::from_utf8_lossy(&stdout).into_owned())
         } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr = String::from_utf8_lossy(&stderr);
             Err(PraxisError::browser(format!(
                 "agent-browser command failed: {}",
                 stderr
@@ -82,9 +487,31 @@ impl BrowserExecutor {
     }
 
     /// Navigate to a URL
+    ///
+    /// The URL is normalized first (see [`normalize_url`]). Invalid URLs and
+    /// common navigation failures are reported as failed results with a
+    /// category and suggestion rather than raw stderr.
     pub async fn open(&self, url: &str, wait_for_load: bool) -> Result<ToolResult> {
+        let url = match normalize_url(url) {
+            Ok(url) => url,
+            Err(failure) => return Ok(failure.into_result(url, "")),
+        };
+        let url = url.as_str();
+
         // Open the URL
-        self.run_command(&["open", url]).await?;
+        match self.run_json_command(&["open", url]).await {
+            Ok(output) => {
+                if let Some(error) = Snapshot::reported_error(&output) {
+                    let failure = NavigationFailure::classify(&error);
+                    return Ok(failure.into_result(url, &error));
+                }
+            }
+            Err(PraxisError::Browser(stderr)) => {
+                let failure = NavigationFailure::classify(&stderr);
+                return Ok(failure.into_result(url, stderr.trim()));
+            }
+            Err(e) => return Err(e),
+        }
 
         // Always wait for network idle for more robust loading
         if wait_for_load {
@@ -92,7 +519,13 @@ impl BrowserExecutor {
         }
 
         // Get a compact interactive snapshot
-        let snapshot_output = self.run_json_command(&["snapshot", "-i", "-c"]).await?;
+        let snapshot_output = self.take_snapshot().await?;
+        if let Some(error) = Snapshot::reported_error(&snapshot_output) {
+            return Ok(ToolResult::failure(
+                "browser_url",
+                format!("Opened {} but couldn't read the page: {}", url, error),
+            ));
+        }
 
         Ok(ToolResult::success_with_data(
             "browser_url",
@@ -105,18 +538,24 @@ impl BrowserExecutor {
     pub async fn click(&self, ref_id: &str) -> Result<ToolResult> {
         let formatted_ref = self.format_ref(ref_id);
 
-        self.run_command(&["click", &formatted_ref]).await?;
+        let output = self.run_json_command(&["click", &formatted_ref]).await?;
+        if let Some(error) = Snapshot::reported_error(&output) {
+            return Ok(ToolResult::failure(
+                "browser_click",
+                format!("Couldn't click {}: {}", ref_id, error),
+            ));
+        }
 
         // Wait for page to stabilize
         let _ = self.run_command(&["wait", "--load", "networkidle"]).await;
 
         // Get updated compact interactive snapshot after click
-        let snapshot_output = self.run_json_command(&["snapshot", "-i", "-c"]).await?;
+        let (page, data) = self.snapshot_after_action().await?;
 
         Ok(ToolResult::success_with_data(
             "browser_click",
-            format!("Clicked {}. Updated page:\n{}", ref_id, &snapshot_output),
-            serde_json::from_str(&snapshot_output).unwrap_or(serde_json::Value::Null),
+            format!("Clicked {}. Updated page:\n{}", ref_id, &page),
+            data,
         ))
     }
 
@@ -124,21 +563,98 @@ impl BrowserExecutor {
     pub async fn fill(&self, ref_id: &str, text: &str) -> Result<ToolResult> {
         let formatted_ref = self.format_ref(ref_id);
 
-        self.run_command(&["fill", &formatted_ref, text]).await?;
+        let output = self
+            .run_json_command(&["fill", &formatted_ref, text])
+            .await?;
+        if let Some(error) = Snapshot::reported_error(&output) {
+            return Ok(ToolResult::failure(
+                "browser_fill",
+                format!("Couldn't fill {}: {}", ref_id, error),
+            ));
+        }
 
         // Wait for potential UI updates
         let _ = self.run_command(&["wait", "--load", "networkidle"]).await;
 
         // Get updated snapshot as fill can trigger dynamic changes
-        let snapshot_output = self.run_json_command(&["snapshot", "-i", "-c"]).await?;
+        let (page, data) = self.snapshot_after_action().await?;
 
         Ok(ToolResult::success_with_data(
             "browser_fill",
             format!(
                 "Filled {} with '{}'. Updated page:\n{}",
-                ref_id, text, &snapshot_output
+                ref_id, text, &page
             ),
-            serde_json::from_str(&snapshot_output).unwrap_or(serde_json::Value::Null),
+            data,
+        ))
+    }
+
+    /// Fill a field, press Enter and return the page it leads to
+    ///
+    /// Covers the common search-box pattern in one step, so the ref can't
+    /// be lost between turns.
+    pub async fn fill_and_submit(&self, ref_id: &str, text: &str) -> Result<ToolResult> {
+        let formatted_ref = self.format_ref(ref_id);
+
+        self.run_command(&["fill", &formatted_ref, text]).await?;
+        self.run_command(&["press", "Enter"]).await?;
+
+        // Submitting usually navigates
+        let _ = self.run_command(&["wait", "--load", "networkidle"]).await;
+
+        let (page, data) = self.snapshot_after_action().await?;
+
+        Ok(ToolResult::success_with_data(
+            "browser_submit",
+            format!(
+                "Filled {} with '{}' and pressed Enter. Resulting page:\n{}",
+                ref_id, text, &page
+            ),
+            data,
+        ))
+    }
+
+    /// Choose an option in a select element
+    pub async fn select(&self, ref_id: &str, value: &str) -> Result<ToolResult> {
+        let formatted_ref = self.format_ref(ref_id);
+
+        self.run_command(&["select", &formatted_ref, value]).await?;
+
+        // Selecting can trigger navigation or dynamic updates
+        let _ = self.run_command(&["wait", "--load", "networkidle"]).await;
+
+        let (page, data) = self.snapshot_after_action().await?;
+
+        Ok(ToolResult::success_with_data(
+            "browser_select",
+            format!(
+                "Selected '{}' in {}. Updated page:\n{}",
+                value, ref_id, &page
+            ),
+            data,
+        ))
+    }
+
+    /// Hover over an element to reveal menus or tooltips
+    ///
+    /// Elements revealed by the hover appear in the returned snapshot.
+    pub async fn hover(&self, ref_id: &str) -> Result<ToolResult> {
+        let formatted_ref = self.format_ref(ref_id);
+
+        self.run_command(&["hover", &formatted_ref]).await?;
+
+        // Give menus and tooltips a moment to render
+        let _ = self.run_command(&["wait", "300"]).await;
+
+        let (page, data) = self.snapshot_after_action().await?;
+
+        Ok(ToolResult::success_with_data(
+            "browser_hover",
+            format!(
+                "Hovered over {}. Updated page (revealed elements included):\n{}",
+                ref_id, &page
+            ),
+            data,
         ))
     }
 
@@ -151,30 +667,73 @@ impl BrowserExecutor {
         Ok(ToolResult::success("browser_get_text", output.trim()))
     }
 
-    /// Take a screenshot
+    /// Get an attribute of an element (e.g. a link's `href`)
+    pub async fn get_attribute(&self, ref_id: &str, attribute: &str) -> Result<ToolResult> {
+        let formatted_ref = self.format_ref(ref_id);
+
+        let output = self
+            .run_command(&["get", "attr", &formatted_ref, attribute])
+            .await?;
+
+        Ok(ToolResult::success_with_data(
+            "browser_get_attribute",
+            output.trim(),
+            serde_json::json!({
+                "ref": ref_id,
+                "attribute": attribute,
+                "value": output.trim(),
+            }),
+        ))
+    }
+
+    /// Take a screenshot and save it to a file
+    ///
+    /// Without a `path`, it's saved under `.praxis/screenshots` in the
+    /// working directory. The result includes the saved path.
     pub async fn screenshot(&self, path: Option<&str>, full_page: bool) -> Result<ToolResult> {
-        let mut args = vec!["screenshot"];
+        let path = match path {
+            Some(p) if !p.trim().is_empty() => self.workdir.join(p.trim()),
+            _ => default_screenshot_path(&self.workdir.join(".praxis").join("screenshots")),
+        };
 
-        if let Some(p) = path {
-            args.push(p);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                PraxisError::browser(format!(
+                    "Failed to create screenshot directory {}: {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
         }
 
+        let path_arg = path.to_string_lossy().into_owned();
+        let mut args = vec!["screenshot", path_arg.as_str()];
         if full_page {
             args.push("--full");
         }
 
         let output = self.run_command(&args).await?;
 
-        let message = if let Some(p) = path {
-            format!("Screenshot saved to {}", p)
-        } else {
-            format!(
-                "Screenshot captured (base64): {}...",
-                &output[..output.len().min(100)]
-            )
-        };
+        if !path.exists() {
+            return Ok(ToolResult::failure(
+                "browser_screenshot",
+                format!(
+                    "Screenshot was not saved to {} (agent-browser returned {} bytes of output)",
+                    path.display(),
+                    output.len()
+                ),
+            ));
+        }
 
-        Ok(ToolResult::success("browser_screenshot", message))
+        let saved = std::fs::canonicalize(&path).unwrap_or(path);
+        Ok(ToolResult::success_with_data(
+            "browser_screenshot",
+            format!("Screenshot saved to {}", saved.display()),
+            serde_json::json!({
+                "path": saved.to_string_lossy(),
+                "full_page": full_page,
+            }),
+        ))
     }
 
     /// Get page snapshot
@@ -186,6 +745,7 @@ impl BrowserExecutor {
         args.push("-c"); // Always use compact mode for cleaner AI parsing
 
         let output = self.run_json_command(&args).await?;
+        self.remember_snapshot(&output);
 
         // Try to parse and store the snapshot
         if let Ok(snapshot) = serde_json::from_str::<Snapshot>(&output) {
@@ -200,18 +760,87 @@ impl BrowserExecutor {
         Ok(ToolResult::success("browser_snapshot", output))
     }
 
+    /// List open tabs
+    pub async fn list_tabs(&self) -> Result<ToolResult> {
+        let output = self.run_json_command(&["tabs"]).await?;
+        let tabs = parse_tabs(&output);
+
+        if tabs.is_empty() {
+            return Ok(ToolResult::success("browser_list_tabs", output.trim()));
+        }
+
+        let list = tabs
+            .iter()
+            .map(|tab| {
+                format!(
+                    "{}. {} - {}{}",
+                    tab.index,
+                    tab.title,
+                    tab.url,
+                    if tab.active { " (active)" } else { "" }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Ok(ToolResult::success(
+            "browser_list_tabs",
+            format!("Open tabs:\n{}", list),
+        ))
+    }
+
+    /// Switch to a tab by index
+    pub async fn switch_tab(&self, index: usize) -> Result<ToolResult> {
+        self.run_command(&["tab", &index.to_string()]).await?;
+
+        let snapshot_output = self.take_snapshot().await?;
+
+        Ok(ToolResult::success_with_data(
+            "browser_switch_tab",
+            format!("Switched to tab {}. Page:\n{}", index, &snapshot_output),
+            serde_json::from_str(&snapshot_output).unwrap_or(serde_json::Value::Null),
+        ))
+    }
+
     /// Close the browser
     pub async fn close(&self) -> Result<ToolResult> {
         self.run_command(&["close"]).await?;
         Ok(ToolResult::success("browser_close", "Browser closed"))
     }
 
-    /// Press a key
+    /// Press a key or combo such as `Escape` or `Control+A`
+    ///
+    /// Enter usually submits or navigates, so it returns the updated snapshot;
+    /// other keys just acknowledge the press.
     pub async fn press(&self, key: &str) -> Result<ToolResult> {
+        let key = key.trim();
+        if key.is_empty() {
+            return Ok(ToolResult::failure(
+                "browser_press",
+                "Missing 'key', e.g. \"Enter\", \"Escape\", \"Tab\" or \"Control+A\"",
+            ));
+        }
+
         self.run_command(&["press", key]).await?;
-        Ok(ToolResult::success(
+
+        if !key
+            .rsplit('+')
+            .next()
+            .is_some_and(|k| k.eq_ignore_ascii_case("enter"))
+        {
+            return Ok(ToolResult::success(
+                "browser_press",
+                format!("Pressed {}", key),
+            ));
+        }
+
+        let _ = self.run_command(&["wait", "--load", "networkidle"]).await;
+        let (page, data) = self.snapshot_after_action().await?;
+
+        Ok(ToolResult::success_with_data(
             "browser_press",
-            format!("Pressed {}", key),
+            format!("Pressed {}. Resulting page:\n{}", key, page),
+            data,
         ))
     }
 
@@ -257,6 +886,72 @@ impl BrowserExecutor {
         ))
     }
 
+    /// Take an interactive, compact snapshot and remember it for ref lookups
+    async fn take_snapshot(&self) -> Result<String> {
+        let output = self.run_json_command(&["snapshot", "-i", "-c"]).await?;
+        self.remember_snapshot(&output);
+        Ok(output)
+    }
+
+    /// Snapshot the page after an action, returning text for the model and
+    /// the full snapshot as data
+    ///
+    /// With diffs on and an earlier snapshot cached, the text summarizes what
+    /// changed rather than repeating the whole page.
+    async fn snapshot_after_action(&self) -> Result<(String, serde_json::Value)> {
+        let previous = self
+            .last_snapshot
+            .lock()
+            .ok()
+            .and_then(|s| s.clone())
+            .filter(|_| self.snapshot_diffs);
+        let output = self.take_snapshot().await?;
+
+        Ok((
+            describe_update(previous.as_ref(), &output),
+            serde_json::from_str(&output).unwrap_or(serde_json::Value::Null),
+        ))
+    }
+
+    /// Cache a snapshot if it parses
+    fn remember_snapshot(&self, output: &str) {
+        if let Ok(snapshot) = serde_json::from_str::<Snapshot>(output) {
+            if let Ok(mut last) = self.last_snapshot.lock() {
+                *last = Some(snapshot);
+            }
+        }
+    }
+
+    /// Turn what the model passed as a ref into a real ref
+    ///
+    /// Refs like `e5` or `@e5` and CSS selectors (`#login`, `button.primary`,
+    /// `input[name=q]`) pass through. Anything else (e.g. "the search box")
+    /// is matched against element names, then roles, in the last snapshot. The error lists the valid refs when there's no
+    /// single match.
+    pub fn resolve_ref(&self, input: &str) -> std::result::Result<String, String> {
+        let input = input.trim();
+        if is_ref(input) || is_css_selector(input) {
+            return Ok(input.to_string());
+        }
+
+        let last = self.last_snapshot.lock().ok().and_then(|s| s.clone());
+        let Some(snapshot) = last else {
+            return Err(format!(
+                "'{}' is not an element ref. Take a browser_snapshot and use a ref like \"e5\"",
+                input
+            ));
+        };
+
+        match match_element(&snapshot, input) {
+            Some(ref_id) => Ok(ref_id),
+            None => Err(format!(
+                "'{}' is not an element ref and doesn't match a single element. Valid refs:\n{}",
+                input,
+                list_refs(&snapshot)
+            )),
+        }
+    }
+
     /// Helper to format a ref or selector
     /// If it's a ref like "e1" or "@e1", ensures it's "@e1"
     fn format_ref(&self, s: &str) -> String {
@@ -281,11 +976,155 @@ impl BrowserExecutor {
         ))
     }
 
+    /// Wait for an element (`selector`) or for `text` to appear
+    ///
+    /// Timing out is reported as a failed result, not an error, so the
+    /// model can retry or carry on.
+    pub async fn wait(
+        &self,
+        selector: Option<&str>,
+        text: Option<&str>,
+        timeout_ms: Option<u64>,
+    ) -> Result<ToolResult> {
+        let timeout = timeout_ms.map_or(self.timeout, Duration::from_millis);
+        let (args, target) = match (selector, text) {
+            (Some(selector), _) => (
+                vec!["wait".to_string(), self.format_ref(selector)],
+                selector,
+            ),
+            (None, Some(text)) => (
+                vec!["wait".to_string(), "--text".to_string(), text.to_string()],
+                text,
+            ),
+            (None, None) => {
+                return Ok(ToolResult::failure(
+                    "browser_wait",
+                    "Provide either 'selector' or 'text' to wait for",
+                ))
+            }
+        };
+
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        match self.run_command_within(&args, timeout).await {
+            Ok(_) => Ok(ToolResult::success(
+                "browser_wait",
+                format!("'{}' is now visible", target),
+            )),
+            Err(PraxisError::Browser(msg)) if msg.starts_with("timeout") => {
+                Ok(ToolResult::failure(
+                    "browser_wait",
+                    format!(
+                        "Timed out after {}ms waiting for '{}'. Take a snapshot to see the page, or wait longer",
+                        timeout.as_millis(),
+                        target
+                    ),
+                ))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Evaluate JavaScript
     pub async fn eval(&self, script: &str) -> Result<ToolResult> {
         let output = self.run_command(&["eval", script]).await?;
         Ok(ToolResult::success("browser_eval", output))
     }
+
+    /// Every link on the page as `{text, href}`, deduplicated by href and
+    /// capped at `max_links`
+    pub async fn extract_links(&self) -> Result<ToolResult> {
+        let output = self.eval(EXTRACT_LINKS_SCRIPT).await?.output;
+        let Some(found) = parse_links(&output) else {
+            return Ok(ToolResult::failure(
+                "browser_extract_links",
+                format!("Couldn't read links from the page: {}", output.trim()),
+            ));
+        };
+
+        let total = found.len();
+        let links: Vec<Link> = found.into_iter().take(self.max_links).collect();
+        let mut text = if links.len() < total {
+            format!("Found {} links (showing the first {}):", total, links.len())
+        } else {
+            format!("Found {} links:", total)
+        };
+        for link in &links {
+            let label = if link.text.is_empty() {
+                "(no text)"
+            } else {
+                &link.text
+            };
+            text.push_str(&format!("\n- {} → {}", label, link.href));
+        }
+
+        Ok(ToolResult::success_with_data(
+            "browser_extract_links",
+            text,
+            serde_json::json!({ "links": links, "total": total }),
+        ))
+    }
+}
+
+/// Default cap on the links `extract_links` returns
+pub const DEFAULT_MAX_LINKS: usize = 100;
+
+/// Collects the page URL and `{text, href}` for every `<a href>` as a JSON
+/// string
+const EXTRACT_LINKS_SCRIPT: &str = "JSON.stringify({page: location.href, links: Array.from(document.querySelectorAll('a[href]')).map(a => ({text: (a.innerText || a.textContent || '').replace(/\\s+/g, ' ').trim().slice(0, 200), href: a.href}))})";
+
+/// A link on the page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link {
+    /// Visible text, whitespace collapsed
+    #[serde(default)]
+    pub text: String,
+    /// Absolute URL
+    pub href: String,
+}
+
+/// What the extraction script returns
+#[derive(Deserialize)]
+struct PageLinks {
+    /// URL of the page the links are on
+    page: String,
+    links: Vec<Link>,
+}
+
+/// Links from the extraction script's output, deduplicated by href
+///
+/// `eval` may print the returned string quoted or bare, so both are read.
+/// Script links and anchors back to the same page are skipped; `a.href` is
+/// always absolute, so anchors are found by comparing without the fragment.
+fn parse_links(output: &str) -> Option<Vec<Link>> {
+    let value: serde_json::Value = serde_json::from_str(output.trim()).ok()?;
+    let value = match value {
+        serde_json::Value::String(inner) => serde_json::from_str(&inner).ok()?,
+        other => other,
+    };
+    let found: PageLinks = serde_json::from_value(value).ok()?;
+    let without_fragment = |url: &str| {
+        url.split_once('#')
+            .map_or(url, |(base, _)| base)
+            .to_string()
+    };
+    let page = without_fragment(&found.page);
+
+    let mut links: Vec<Link> = Vec::new();
+    for link in found.links {
+        if link.href.is_empty()
+            || link.href.starts_with("javascript:")
+            || (link.href.contains('#') && without_fragment(&link.href) == page)
+        {
+            continue;
+        }
+        match links.iter_mut().find(|l| l.href == link.href) {
+            // Keep the first text, unless only a later copy has any
+            Some(existing) if existing.text.is_empty() => existing.text = link.text,
+            Some(_) => {}
+            None => links.push(link),
+        }
+    }
+    Some(links)
 }
 
 impl Default for BrowserExecutor {
@@ -294,10 +1133,348 @@ impl Default for BrowserExecutor {
     }
 }
 
+/// The page after an action: a diff against `previous` when there is one and
+/// it's shorter than the full snapshot (it isn't after navigating), otherwise
+/// the snapshot itself
+fn describe_update(previous: Option<&Snapshot>, output: &str) -> String {
+    let current = serde_json::from_str::<Snapshot>(output);
+    let (Some(previous), Ok(current)) = (previous, current) else {
+        return output.to_string();
+    };
+
+    let summary = format!(
+        "(changes since the last snapshot; other refs are unchanged)\n{}",
+        previous.diff(&current).summary()
+    );
+    if summary.len() < output.len() {
+        summary
+    } else {
+        output.to_string()
+    }
+}
+
+/// Whether `s` is an element ref (`e5` or `@e5`)
+fn is_ref(s: &str) -> bool {
+    let s = s.strip_prefix('@').unwrap_or(s);
+    s.len() > 1 && s.starts_with('e') && s[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// Whether `s` reads as a CSS selector rather than prose or a URL
+///
+/// Every word must be made of selector characters, and at least one must
+/// use selector syntax (`#`, `.`, `[` or `:`). Words ending in sentence
+/// punctuation, and anything with a `/`, count as prose.
+fn is_css_selector(s: &str) -> bool {
+    if s.starts_with(['#', '.', '[']) {
+        return true;
+    }
+
+    let mut has_syntax = false;
+    for word in s.split_whitespace() {
+        if matches!(word, ">" | "+" | "~") {
+            continue;
+        }
+        let valid = word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_#.[]=:\"'()*^$|>+~".contains(c))
+            && word.starts_with(|c: char| c.is_ascii_alphabetic() || "#.[*:".contains(c))
+            && !word.ends_with(['.', ':', '#', '=', ',']);
+        if !valid {
+            return false;
+        }
+        has_syntax |= word.contains(['#', '.', '[', ':']);
+    }
+    has_syntax
+}
+
+/// Find the one element `description` refers to, by name and then by role
+///
+/// Interactive elements and exact name matches win over other candidates.
+fn match_element(snapshot: &Snapshot, description: &str) -> Option<String> {
+    let wanted = description.trim_start_matches("the ").trim().to_lowercase();
+    if wanted.is_empty() {
+        return None;
+    }
+
+    let pick = |candidates: Vec<(&String, &Element)>| -> Option<String> {
+        let narrowed: Vec<_> = if candidates.iter().any(|(_, el)| el.is_interactive()) {
+            candidates
+                .into_iter()
+                .filter(|(_, el)| el.is_interactive())
+                .collect()
+        } else {
+            candidates
+        };
+        let exact: Vec<_> = narrowed
+            .iter()
+            .filter(|(_, el)| el.name.to_lowercase() == wanted)
+            .collect();
+
+        match (exact.as_slice(), narrowed.as_slice()) {
+            ([(ref_id, _)], _) | ([], [(ref_id, _)]) => Some(ref_id.to_string()),
+            _ => None,
+        }
+    };
+
+    if let Some(ref_id) = pick(snapshot.find_by_text(&wanted)) {
+        return Some(ref_id);
+    }
+
+    let role = wanted.replace(' ', "");
+    pick(snapshot.elements_by_role(&role))
+}
+
+/// One line per element ref in a snapshot, in ref order
+fn list_refs(snapshot: &Snapshot) -> String {
+    let mut refs: Vec<(&String, &Element)> = snapshot
+        .data
+        .as_ref()
+        .map(|d| d.refs.iter().collect())
+        .unwrap_or_default();
+    refs.sort_by_key(|(ref_id, _)| ref_id[1..].parse::<u64>().unwrap_or(u64::MAX));
+
+    refs.iter()
+        .map(|(ref_id, el)| format!("  {}: {} \"{}\"", ref_id, el.role, el.name))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A new timestamped screenshot path in `dir`
+fn default_screenshot_path(dir: &Path) -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    dir.join(format!("{}.png", millis))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_screenshot_path() {
+        let dir = Path::new(".praxis/screenshots");
+        let path = default_screenshot_path(dir);
+
+        assert_eq!(path.parent(), Some(dir));
+        assert_eq!(path.extension().and_then(|e| e.to_str()), Some("png"));
+        assert!(path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|s| s.parse::<u128>().is_ok()));
+    }
+
+    #[test]
+    fn test_resolve_ref_from_description() {
+        let executor = BrowserExecutor::new("test-session");
+        assert_eq!(executor.resolve_ref("e5").unwrap(), "e5");
+        assert!(executor.resolve_ref("the search box").is_err());
+
+        executor.remember_snapshot(
+            r#"{"success": true, "data": {"snapshot": "", "refs": {
+                "e1": {"role": "searchbox", "name": "Search"},
+                "e2": {"role": "button", "name": "Search"},
+                "e3": {"role": "link", "name": "Search help"},
+                "e4": {"role": "heading", "name": "Results"}
+            }}}"#,
+        );
+
+        assert_eq!(executor.resolve_ref("the search box").unwrap(), "e1");
+        assert_eq!(executor.resolve_ref("Search help").unwrap(), "e3");
+
+        // CSS selectors are left alone, even with a snapshot to match against
+        for selector in [
+            "input[name=q]",
+            "button.primary",
+            "#login",
+            "form input:focus",
+        ] {
+            assert_eq!(executor.resolve_ref(selector).unwrap(), selector);
+        }
+        assert!(!is_css_selector("Search help."));
+        assert!(!is_css_selector("e.g. the search box"));
+
+        let err = executor
+            .resolve_ref("https://example.com/login")
+            .unwrap_err();
+        assert!(err.contains("e1: searchbox \"Search\""));
+        assert!(err.contains("e4: heading \"Results\""));
+    }
+
+    #[test]
+    fn test_describe_update_prefers_short_diff() {
+        let before = r#"{"success": true, "data": {"snapshot": "- textbox \"Search\" [ref=e1]\n- link \"About us\" [ref=e2]\n- link \"Careers\" [ref=e3]", "refs": {
+            "e1": {"role": "textbox", "name": "Search"},
+            "e2": {"role": "link", "name": "About us"},
+            "e3": {"role": "link", "name": "Careers"}
+        }}}"#;
+        let after = before.replace(
+            r#""name": "Search"}"#,
+            r#""name": "Search", "value": "rust"}"#,
+        );
+        let previous: Snapshot = serde_json::from_str(before).unwrap();
+
+        let update = describe_update(Some(&previous), &after);
+        assert!(update.contains("1 changed element: e1 textbox \"Search\" = \"rust\""));
+        assert!(!update.contains("Careers"));
+
+        // Without a cached snapshot the full page is returned
+        assert_eq!(describe_update(None, &after), after);
+    }
+
+    #[test]
+    fn test_version_parse() {
+        assert_eq!(
+            Version::parse("agent-browser v0.5.12\n"),
+            Some(Version::new(0, 5, 12))
+        );
+        assert_eq!(Version::parse("1.2"), Some(Version::new(1, 2, 0)));
+        assert_eq!(Version::parse("0.6.0-beta.1"), Some(Version::new(0, 6, 0)));
+        assert_eq!(Version::parse("agent-browser"), None);
+        assert!(Version::new(0, 3, 9) < MIN_AGENT_BROWSER_VERSION);
+        assert!(Version::new(0, 10, 0) > Version::new(0, 9, 5));
+    }
+
+    #[test]
+    fn test_parse_links_dedupes_by_href() {
+        let links = serde_json::json!({"page": "https://example.com/#intro", "links": [
+            {"text": "", "href": "https://example.com/a"},
+            {"text": "Docs", "href": "https://example.com/a"},
+            {"text": "Menu", "href": "javascript:void(0)"},
+            {"text": "Skip to content", "href": "https://example.com/#main"},
+            {"text": "Blog", "href": "https://example.com/blog"},
+            {"text": "Docs again", "href": "https://example.com/a"}
+        ]})
+        .to_string();
+
+        // agent-browser may print the returned string quoted
+        let quoted = serde_json::Value::String(links.clone()).to_string();
+        for output in [links, quoted] {
+            let parsed = parse_links(&output).unwrap();
+            assert_eq!(
+                parsed,
+                vec![
+                    Link {
+                        text: "Docs".to_string(),
+                        href: "https://example.com/a".to_string()
+                    },
+                    Link {
+                        text: "Blog".to_string(),
+                        href: "https://example.com/blog".to_string()
+                    },
+                ]
+            );
+        }
+        assert!(parse_links("ReferenceError: x is not defined").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_press_needs_key() {
+        let executor = BrowserExecutor::new("test-session");
+        let result = executor.press("  ").await.unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("Missing 'key'"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_needs_selector_or_text() {
+        let executor = BrowserExecutor::new("test-session");
+        let result = executor.wait(None, None, Some(100)).await.unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("'selector' or 'text'"));
+    }
+
+    #[test]
+    fn test_validate_scheme_less_url() {
+        assert_eq!(
+            validate_url("example.com").unwrap_err(),
+            NavigationFailure::MissingScheme
+        );
+        assert_eq!(
+            validate_url("example.com:8080/docs").unwrap_err(),
+            NavigationFailure::MissingScheme
+        );
+        assert!(NavigationFailure::MissingScheme
+            .suggestion()
+            .contains("https://"));
+    }
+
+    #[test]
+    fn test_validate_malformed_url() {
+        assert_eq!(
+            validate_url("https://exa mple.com").unwrap_err(),
+            NavigationFailure::MalformedUrl
+        );
+        assert_eq!(
+            validate_url("https://").unwrap_err(),
+            NavigationFailure::MalformedUrl
+        );
+        assert_eq!(
+            validate_url("ftp://example.com").unwrap_err(),
+            NavigationFailure::UnsupportedScheme
+        );
+        assert!(validate_url("https://example.com/path?q=1").is_ok());
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(normalize_url("google.com").unwrap(), "https://google.com/");
+        assert_eq!(
+            normalize_url("  docs.rs/serde?search=x ").unwrap(),
+            "https://docs.rs/serde?search=x"
+        );
+        assert_eq!(
+            normalize_url("localhost:3000/app").unwrap(),
+            "http://localhost:3000/app"
+        );
+        assert_eq!(
+            normalize_url("http://example.com/a").unwrap(),
+            "http://example.com/a"
+        );
+        assert_eq!(
+            normalize_url("latest rust release notes").unwrap_err(),
+            NavigationFailure::NotAUrl
+        );
+        assert_eq!(
+            normalize_url("homepage").unwrap_err(),
+            NavigationFailure::NotAUrl
+        );
+        assert_eq!(normalize_url("").unwrap_err(), NavigationFailure::NotAUrl);
+    }
+
+    #[test]
+    fn test_parse_tabs() {
+        let output = r#"{"success":true,"data":{"tabs":[
+            {"index":0,"title":"Home","url":"https://example.com/","active":false},
+            {"index":1,"title":"Docs","url":"https://example.com/docs","active":true}
+        ]}}"#;
+        let tabs = parse_tabs(output);
+        assert_eq!(tabs.len(), 2);
+        assert_eq!(tabs[1].title, "Docs");
+        assert!(tabs[1].active);
+
+        let tabs = parse_tabs(r#"[{"title":"A","url":"https://a.com"}]"#);
+        assert_eq!(tabs[0].index, 0);
+        assert!(parse_tabs("not json").is_empty());
+    }
+
+    #[test]
+    fn test_classify_navigation_errors() {
+        assert_eq!(
+            NavigationFailure::classify("net::ERR_NAME_NOT_RESOLVED at https://nope.invalid"),
+            NavigationFailure::DnsFailure
+        );
+        assert_eq!(
+            NavigationFailure::classify("net::ERR_CONNECTION_REFUSED"),
+            NavigationFailure::ConnectionRefused
+        );
+        assert_eq!(
+            NavigationFailure::classify("Timeout 30000ms exceeded"),
+            NavigationFailure::Timeout
+        );
+    }
+
     #[test]
     fn test_executor_creation() {
         let executor = BrowserExecutor::new("test-session");
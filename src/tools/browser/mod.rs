@@ -5,5 +5,8 @@
 mod executor;
 mod snapshot;
 
-pub use executor::BrowserExecutor;
-pub use snapshot::{Element, Snapshot};
+pub use executor::{
+    normalize_url, validate_url, BrowserExecutor, Link, NavigationFailure, SessionStart, TabInfo,
+    Version, DEFAULT_MAX_LINKS, MIN_AGENT_BROWSER_VERSION,
+};
+pub use snapshot::{Element, Snapshot, SnapshotDiff};
@@ -26,6 +26,17 @@ pub struct SnapshotData {
     pub refs: std::collections::HashMap<String, Element>,
 }
 
+/// What changed between two snapshots, keyed by ref
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotDiff {
+    /// Refs only in the newer snapshot
+    pub added: Vec<(String, Element)>,
+    /// Refs only in the older snapshot
+    pub removed: Vec<(String, Element)>,
+    /// Refs in both whose role, name, value or focus differs (newer element)
+    pub changed: Vec<(String, Element)>,
+}
+
 /// An element in the snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Element {
@@ -47,6 +58,23 @@ pub struct Element {
 }
 
 impl Snapshot {
+    /// The error in agent-browser JSON output that says `"success": false`
+    ///
+    /// agent-browser can exit cleanly and still fail this way. Output that
+    /// isn't JSON or has no `success` field isn't treated as a failure.
+    pub fn reported_error(output: &str) -> Option<String> {
+        let value: serde_json::Value = serde_json::from_str(output.trim()).ok()?;
+        if value.get("success")?.as_bool()? {
+            return None;
+        }
+        let error = value["error"]
+            .as_str()
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .unwrap_or("agent-browser reported a failure without details");
+        Some(error.to_string())
+    }
+
     /// Count the number of elements with refs
     pub fn count_elements(&self) -> usize {
         self.data.as_ref().map(|d| d.refs.len()).unwrap_or(0)
@@ -107,6 +135,34 @@ impl Snapshot {
             .unwrap_or_default()
     }
 
+    /// Compare with a later snapshot of the same page
+    pub fn diff(&self, other: &Snapshot) -> SnapshotDiff {
+        let empty = std::collections::HashMap::new();
+        let before = self.data.as_ref().map_or(&empty, |d| &d.refs);
+        let after = other.data.as_ref().map_or(&empty, |d| &d.refs);
+
+        let mut diff = SnapshotDiff::default();
+        for (ref_id, element) in after {
+            match before.get(ref_id) {
+                None => diff.added.push((ref_id.clone(), element.clone())),
+                Some(old) if !old.same_state(element) => {
+                    diff.changed.push((ref_id.clone(), element.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        for (ref_id, element) in before {
+            if !after.contains_key(ref_id) {
+                diff.removed.push((ref_id.clone(), element.clone()));
+            }
+        }
+
+        for list in [&mut diff.added, &mut diff.removed, &mut diff.changed] {
+            list.sort_by_key(|(ref_id, _)| ref_number(ref_id));
+        }
+        diff
+    }
+
     /// Get the raw accessibility tree string
     pub fn raw_tree(&self) -> Option<&str> {
         self.data.as_ref().map(|d| d.snapshot.as_str())
@@ -144,7 +200,74 @@ impl Snapshot {
     }
 }
 
+impl SnapshotDiff {
+    /// Check if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// One-line-per-group summary, e.g. `2 new elements: e12 link "Results", ...`
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "No changes to the page's elements.".to_string();
+        }
+
+        let mut lines = Vec::new();
+        for (label, elements) in [
+            ("new", &self.added),
+            ("changed", &self.changed),
+            ("removed", &self.removed),
+        ] {
+            if elements.is_empty() {
+                continue;
+            }
+            let listed = elements
+                .iter()
+                .map(|(ref_id, el)| el.describe(ref_id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!(
+                "{} {} element{}: {}",
+                elements.len(),
+                label,
+                if elements.len() == 1 { "" } else { "s" },
+                listed
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Numeric part of a ref like `e12`, for ordering
+fn ref_number(ref_id: &str) -> u64 {
+    ref_id
+        .trim_start_matches('@')
+        .trim_start_matches('e')
+        .parse()
+        .unwrap_or(u64::MAX)
+}
+
 impl Element {
+    /// Whether two elements look the same to the model
+    fn same_state(&self, other: &Element) -> bool {
+        self.role == other.role
+            && self.name == other.name
+            && self.value == other.value
+            && self.focused == other.focused
+    }
+
+    /// Short description such as `e5 textbox "Search" = "rust"`
+    pub fn describe(&self, ref_id: &str) -> String {
+        let mut text = format!("{} {} \"{}\"", ref_id, self.role, self.name);
+        if let Some(ref value) = self.value {
+            text.push_str(&format!(" = \"{}\"", value));
+        }
+        if self.focused {
+            text.push_str(" [focused]");
+        }
+        text
+    }
+
     /// Check if this is an interactive element
     pub fn is_interactive(&self) -> bool {
         matches!(
@@ -183,6 +306,20 @@ impl Element {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_reported_error() {
+        assert_eq!(
+            Snapshot::reported_error(r#"{"success": false, "error": "Element not found: @e9"}"#),
+            Some("Element not found: @e9".to_string())
+        );
+        assert!(Snapshot::reported_error(r#"{"success": false}"#)
+            .unwrap()
+            .contains("without details"));
+        assert_eq!(Snapshot::reported_error(r#"{"success": true}"#), None);
+        assert_eq!(Snapshot::reported_error(r#"{"data": {}}"#), None);
+        assert_eq!(Snapshot::reported_error("✓ Done"), None);
+    }
+
     #[test]
     fn test_element_is_interactive() {
         let button = Element {
@@ -197,6 +334,39 @@ mod tests {
         assert!(!button.is_input());
     }
 
+    #[test]
+    fn test_snapshot_diff() {
+        let parse = |json: &str| serde_json::from_str::<Snapshot>(json).unwrap();
+        let before = parse(
+            r#"{"success": true, "data": {"refs": {
+                "e1": {"role": "textbox", "name": "Search"},
+                "e2": {"role": "button", "name": "Go"},
+                "e3": {"role": "link", "name": "Old"}
+            }}}"#,
+        );
+        let after = parse(
+            r#"{"success": true, "data": {"refs": {
+                "e1": {"role": "textbox", "name": "Search", "value": "rust"},
+                "e2": {"role": "button", "name": "Go"},
+                "e12": {"role": "link", "name": "Results"},
+                "e4": {"role": "link", "name": "Next"}
+            }}}"#,
+        );
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added.len(), 2);
+        assert_eq!(diff.added[0].0, "e4");
+        assert_eq!(diff.changed[0].0, "e1");
+        assert_eq!(diff.removed[0].0, "e3");
+        assert_eq!(
+            diff.summary(),
+            "2 new elements: e4 link \"Next\", e12 link \"Results\"\n\
+             1 changed element: e1 textbox \"Search\" = \"rust\"\n\
+             1 removed element: e3 link \"Old\""
+        );
+        assert!(after.diff(&after).is_empty());
+    }
+
     #[test]
     fn test_snapshot_get_element() {
         let mut refs = std::collections::HashMap::new();
@@ -1,24 +1,45 @@
 //! Write code tool
 //!
-//! Generates code based on task description and language.
+//! Generates code based on task description and language. Given a
+//! `file_path`, it edits that file instead and suggests a unified diff;
+//! given an `output_path`, the generated code is written there.
 
-use crate::core::{Result, ToolCall, ToolResult};
+use std::fs;
+use std::path::PathBuf;
+
+use similar::TextDiff;
+
+use crate::core::{PraxisError, Result, ToolCall, ToolResult};
+use crate::tools::fs::resolve_path;
 
 /// Tool for writing code
-pub struct WriteTool;
+pub struct WriteTool {
+    /// Directory that `file_path` and `output_path` are resolved against
+    root: PathBuf,
+}
 
 impl WriteTool {
-    /// Create a new write tool
+    /// Create a new write tool rooted at the current working directory
     pub fn new() -> Self {
-        Self
+        Self {
+            root: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        }
+    }
+
+    /// Set the directory `file_path` and `output_path` are resolved against
+    pub fn set_root(&mut self, root: impl Into<PathBuf>) {
+        self.root = root.into();
     }
 
     /// Build a prompt for the executor model
-    pub fn build_prompt(&self, tool_call: &ToolCall) -> String {
+    ///
+    /// Fails if `file_path` is given but the file can't be read, or if
+    /// `output_path` exists and `overwrite` isn't set.
+    pub fn build_prompt(&self, tool_call: &ToolCall) -> Result<String> {
+        let output = self.output_target(tool_call)?;
+
         let task = tool_call.get_string("task").unwrap_or_default();
-        let language = tool_call
-            .get_string("language")
-            .unwrap_or_else(|| "rust".to_string());
+        let language = tool_call.get_string_or("language", "rust");
         let context = tool_call.get_string("context").unwrap_or_default();
 
         let mut prompt = format!(
@@ -31,6 +52,17 @@ impl WriteTool {
             prompt.push_str(&format!("\nContext: {}\n", context));
         }
 
+        if let Some((path, original)) = self.read_target(tool_call)? {
+            prompt.push_str(&format!(
+                "\nCurrent contents of {}:\n```{}\n{}\n```\n\
+                 \nReply with the complete updated file in a single code block.\n",
+                path,
+                language,
+                original.trim_end()
+            ));
+            return Ok(prompt);
+        }
+
         prompt.push_str(
             "\nProvide well-commented code with best practices. Include:\n\
              - Clear function/variable names\n\
@@ -38,16 +70,101 @@ impl WriteTool {
              - Brief inline comments for complex logic\n",
         );
 
-        prompt
+        if let Some((path, _)) = output {
+            prompt.push_str(&format!(
+                "\nThe code will be saved to {}, so put all of it in a single code block.\n",
+                path
+            ));
+        }
+
+        Ok(prompt)
+    }
+
+    /// Turn the model's response into the tool output
+    ///
+    /// With an `output_path`, the code in the response is written there and
+    /// the output says where. For an edit (`file_path` given) it is a unified
+    /// diff from the file to the code in the response. Otherwise the response
+    /// is returned as is.
+    pub fn finish(&self, tool_call: &ToolCall, response: String) -> Result<String> {
+        if let Some((path, resolved)) = self.output_target(tool_call)? {
+            let (language, code) = code_block(&response);
+            let code = format!("{}\n", code.trim_end());
+            if let Some(parent) = resolved.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| PraxisError::tool(format!("Cannot create '{}': {}", path, e)))?;
+            }
+            fs::write(&resolved, &code)
+                .map_err(|e| PraxisError::tool(format!("Cannot write '{}': {}", path, e)))?;
+
+            return Ok(format!(
+                "Wrote {} bytes to {}\n\n```{}\n{}```",
+                code.len(),
+                path,
+                language.unwrap_or_default(),
+                code
+            ));
+        }
+
+        let Some((path, original)) = self.read_target(tool_call)? else {
+            return Ok(response);
+        };
+
+        let (_, updated) = code_block(&response);
+        if updated.trim_end() == original.trim_end() {
+            return Ok(format!("No changes suggested for {}", path));
+        }
+        Ok(unified_diff(&path, &original, updated))
     }
 
     /// Execute the tool (returns prompt for now, actual execution happens via LLM)
     pub fn execute(&self, tool_call: &ToolCall) -> Result<ToolResult> {
         // For coding tools, we don't execute directly - we build prompts
         // The orchestrator will send this to the executor model
-        let prompt = self.build_prompt(tool_call);
+        let prompt = self.build_prompt(tool_call)?;
         Ok(ToolResult::success("write_code", prompt))
     }
+
+    /// The `output_path` argument and where it resolves to, if one was given
+    ///
+    /// Fails if the file exists and `overwrite` isn't true.
+    fn output_target(&self, tool_call: &ToolCall) -> Result<Option<(String, PathBuf)>> {
+        let Some(path) = tool_call
+            .get_string("output_path")
+            .filter(|p| !p.trim().is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let resolved = resolve_path(&self.root, &path)?;
+        let overwrite = tool_call.arguments["overwrite"].as_bool().unwrap_or(false);
+        if resolved.exists() && !overwrite {
+            return Err(PraxisError::tool(format!(
+                "'{}' already exists. Pass \"overwrite\": true to replace it, or use file_path to edit it.",
+                path
+            )));
+        }
+        Ok(Some((path, resolved)))
+    }
+
+    /// The `file_path` argument and the file's contents, if one was given
+    fn read_target(&self, tool_call: &ToolCall) -> Result<Option<(String, String)>> {
+        let Some(path) = tool_call
+            .get_string("file_path")
+            .filter(|p| !p.trim().is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let resolved = resolve_path(&self.root, &path)?;
+        let content = fs::read_to_string(&resolved).map_err(|e| {
+            PraxisError::tool(format!(
+                "Cannot read '{}' to edit it: {}. Leave out file_path to write new code.",
+                path, e
+            ))
+        })?;
+        Ok(Some((path, content)))
+    }
 }
 
 impl Default for WriteTool {
@@ -55,3 +172,105 @@ impl Default for WriteTool {
         Self::new()
     }
 }
+
+/// The language hint and contents of the first fenced code block in
+/// `response`, or all of it when there is no fence
+fn code_block(response: &str) -> (Option<&str>, &str) {
+    let Some(start) = response.find("```") else {
+        return (None, response.trim());
+    };
+    let body = &response[start + 3..];
+    let (hint, body) = match body.find('\n') {
+        Some(newline) => (body[..newline].trim(), &body[newline + 1..]),
+        None => (body.trim(), ""),
+    };
+    let code = match body.find("```") {
+        Some(end) => &body[..end],
+        None => body,
+    };
+    (Some(hint).filter(|h| !h.is_empty()), code)
+}
+
+/// A unified diff between two versions of `path`
+fn unified_diff(path: &str, original: &str, updated: &str) -> String {
+    let original = format!("{}\n", original.trim_end());
+    let updated = format!("{}\n", updated.trim_end());
+    TextDiff::from_lines(&original, &updated)
+        .unified_diff()
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_suggests_diff() {
+        let root = std::env::temp_dir().join("praxis_write_code_edit");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+
+        let mut tool = WriteTool::new();
+        tool.set_root(&root);
+        let call = ToolCall::new(
+            "write_code",
+            serde_json::json!({"task": "rename b", "language": "rust", "file_path": "lib.rs"}),
+        );
+
+        assert!(tool.build_prompt(&call).unwrap().contains("fn b() {}"));
+        let diff = tool
+            .finish(
+                &call,
+                "Here:\n```rust\nfn a() {}\nfn c() {}\n```".to_string(),
+            )
+            .unwrap();
+        assert!(diff.starts_with("--- a/lib.rs\n+++ b/lib.rs\n"));
+        assert!(diff.contains("-fn b() {}\n+fn c() {}\n"));
+
+        let missing = ToolCall::new(
+            "write_code",
+            serde_json::json!({"task": "t", "language": "rust", "file_path": "nope.rs"}),
+        );
+        let error = tool.build_prompt(&missing).unwrap_err().to_string();
+        assert!(error.contains("Cannot read 'nope.rs'"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_output_path_writes_code_block() {
+        let root = std::env::temp_dir().join("praxis_write_code_output");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        let mut tool = WriteTool::new();
+        tool.set_root(&root);
+        let call = ToolCall::new(
+            "write_code",
+            serde_json::json!({"task": "hello", "language": "python", "output_path": "src/hello.py"}),
+        );
+
+        let output = tool
+            .finish(
+                &call,
+                "Sure:\n```python\nprint('hi')\n```\nDone.".to_string(),
+            )
+            .unwrap();
+        assert!(output.starts_with("Wrote 12 bytes to src/hello.py"));
+        assert!(output.ends_with("```python\nprint('hi')\n```"));
+        assert_eq!(
+            fs::read_to_string(root.join("src/hello.py")).unwrap(),
+            "print('hi')\n"
+        );
+
+        // Existing files are only replaced on request
+        assert!(tool.build_prompt(&call).is_err());
+        let mut overwrite = call.clone();
+        overwrite.arguments["overwrite"] = serde_json::json!(true);
+        assert!(tool.build_prompt(&overwrite).is_ok());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
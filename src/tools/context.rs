@@ -3,7 +3,7 @@
 //! This implements the "Recursive Language Model" pattern where the agent
 //! can query its own history as an external resource.
 
-use crate::core::ToolCall;
+use crate::core::{Message, ToolCall, ToolResult};
 
 /// Tool for recursively analyzing conversation history
 #[derive(Debug, Clone, Default)]
@@ -22,7 +22,7 @@ impl RecursiveContextTool {
     }
 
     /// Build a prompt for the recursive call
-    pub fn build_prompt(&self, query: &str, context_messages: &[crate::core::Message]) -> String {
+    pub fn build_prompt(&self, query: &str, context_messages: &[Message]) -> String {
         let mut prompt = String::new();
 
         prompt.push_str("Analyze the following conversation segment to answer the query.\n\n");
@@ -42,3 +42,162 @@ impl RecursiveContextTool {
         prompt
     }
 }
+
+/// Most matches `search_history` lists
+const MAX_HISTORY_MATCHES: usize = 20;
+
+/// Characters kept on each side of a match in a snippet
+const SNIPPET_RADIUS: usize = 60;
+
+/// Tool for finding messages in the conversation history by keyword
+///
+/// Unlike `analyze_conversation` it doesn't call a model, so it's fast and
+/// gives the same answer every time.
+#[derive(Debug, Clone, Default)]
+pub struct HistorySearchTool;
+
+impl HistorySearchTool {
+    /// Create a new instance
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// List the messages containing the call's `pattern`, ignoring case
+    ///
+    /// Each match has its history index (as `analyze_conversation` takes),
+    /// role and a snippet around the first occurrence.
+    pub fn search<'a>(
+        &self,
+        tool_call: &ToolCall,
+        history: impl IntoIterator<Item = &'a Message>,
+    ) -> ToolResult {
+        let pattern = tool_call.get_string("pattern").unwrap_or_default();
+        if pattern.trim().is_empty() {
+            return ToolResult::failure("search_history", "'pattern' must not be empty");
+        }
+        let needle = pattern.to_lowercase();
+
+        let matches: Vec<serde_json::Value> = history
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, message)| {
+                let (start, end) = find_ignore_case(&message.content, &needle)?;
+                Some(serde_json::json!({
+                    "index": index,
+                    "role": message.role,
+                    "snippet": snippet(&message.content, start, end),
+                }))
+            })
+            .collect();
+
+        if matches.is_empty() {
+            return ToolResult::success_with_data(
+                "search_history",
+                format!("No messages contain '{}'", pattern),
+                serde_json::json!({ "matches": [], "total": 0 }),
+            );
+        }
+
+        let total = matches.len();
+        let shown = &matches[..total.min(MAX_HISTORY_MATCHES)];
+        let mut output = format!("{} messages contain '{}':", total, pattern);
+        for m in shown {
+            output.push_str(&format!(
+                "\n[{} {}] {}",
+                m["index"],
+                m["role"].as_str().unwrap_or_default(),
+                m["snippet"].as_str().unwrap_or_default()
+            ));
+        }
+        if total > shown.len() {
+            output.push_str(&format!("\n... and {} more", total - shown.len()));
+        }
+
+        ToolResult::success_with_data(
+            "search_history",
+            output,
+            serde_json::json!({ "matches": shown, "total": total }),
+        )
+    }
+}
+
+/// Byte range in `text` of the first occurrence of `needle` (lowercase),
+/// ignoring case
+fn find_ignore_case(text: &str, needle: &str) -> Option<(usize, usize)> {
+    // Lowercasing can change byte lengths, so remember where each byte came from
+    let mut lowered = String::with_capacity(text.len());
+    let mut origin = Vec::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        let before = lowered.len();
+        lowered.extend(c.to_lowercase());
+        origin.extend(std::iter::repeat_n(i, lowered.len() - before));
+    }
+
+    let at = lowered.find(needle)?;
+    let end = origin.get(at + needle.len()).copied().unwrap_or(text.len());
+    Some((origin[at], end))
+}
+
+/// `text[start..end]` with up to `SNIPPET_RADIUS` characters either side,
+/// on one line
+fn snippet(text: &str, start: usize, end: usize) -> String {
+    let before: Vec<char> = text[..start]
+        .chars()
+        .rev()
+        .take(SNIPPET_RADIUS + 1)
+        .collect();
+    let after: Vec<char> = text[end..].chars().take(SNIPPET_RADIUS + 1).collect();
+
+    let mut snippet = String::new();
+    if before.len() > SNIPPET_RADIUS {
+        snippet.push_str("...");
+    }
+    snippet.extend(before.iter().take(SNIPPET_RADIUS).rev());
+    snippet.push_str(&text[start..end]);
+    snippet.extend(after.iter().take(SNIPPET_RADIUS));
+    if after.len() > SNIPPET_RADIUS {
+        snippet.push_str("...");
+    }
+    snippet.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_history_finds_messages_ignoring_case() {
+        let history = vec![
+            Message::user("My API key is in .env, don't print it"),
+            Message::assistant("Understood."),
+            Message::user(format!("{} then the api KEY again", "x ".repeat(100))),
+        ];
+        let call = ToolCall::new("search_history", serde_json::json!({"pattern": "api key"}));
+
+        let result = HistorySearchTool::new().search(&call, &history);
+        assert!(result.success);
+        let data = result.data.unwrap();
+        assert_eq!(data["total"], 2);
+        assert_eq!(data["matches"][0]["index"], 0);
+        assert_eq!(
+            data["matches"][0]["snippet"],
+            "My API key is in .env, don't print it"
+        );
+        assert_eq!(data["matches"][1]["index"], 2);
+        let snippet = data["matches"][1]["snippet"].as_str().unwrap();
+        assert!(snippet.starts_with("...") && snippet.ends_with("api KEY again"));
+
+        let none = ToolCall::new("search_history", serde_json::json!({"pattern": "password"}));
+        assert!(HistorySearchTool::new()
+            .search(&none, &history)
+            .output
+            .starts_with("No messages contain"));
+    }
+
+    #[test]
+    fn test_find_ignore_case_keeps_original_offsets() {
+        let text = "İstanbul ÉCOLE";
+        let (start, end) = find_ignore_case(text, "école").unwrap();
+        assert_eq!(&text[start..end], "ÉCOLE");
+    }
+}
@@ -0,0 +1,409 @@
+//! File system tools
+//!
+//! Read and write files inside the project directory. These tools run
+//! directly instead of going through the executor model.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+
+use crate::core::{PraxisError, Result, ToolCall, ToolResult};
+
+/// Default maximum size of a file that `read_file` will return (1MB)
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Default maximum number of paths `list_files` will return
+pub const DEFAULT_MAX_LIST_ENTRIES: usize = 200;
+
+/// Resolve a tool-supplied path against `root`, rejecting anything that
+/// escapes it (via `..` components, absolute paths or symlinks).
+pub fn resolve_path(root: &Path, path: &str) -> Result<PathBuf> {
+    if path.trim().is_empty() {
+        return Err(PraxisError::tool("Missing required argument 'path'"));
+    }
+
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let candidate = root.join(path);
+
+    // Lexically normalize so `a/../../etc` is caught before touching the disk
+    let mut normalized = PathBuf::new();
+    for component in candidate.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+
+    let outside = || PraxisError::tool(format!("Path '{}' is outside the project directory", path));
+
+    // Follow symlinks for the deepest part of the path that already exists;
+    // the missing rest can't contain any
+    let mut existing = normalized.as_path();
+    let mut missing = Vec::new();
+    let resolved = loop {
+        match existing.canonicalize() {
+            Ok(base) => break missing.iter().rev().fold(base, |p, name| p.join(name)),
+            // A dangling symlink would be followed when written through
+            Err(_) if existing.symlink_metadata().is_ok() => return Err(outside()),
+            Err(_) => match (existing.parent(), existing.file_name()) {
+                (Some(parent), Some(name)) => {
+                    missing.push(name);
+                    existing = parent;
+                }
+                _ => break normalized.clone(),
+            },
+        }
+    };
+
+    if !resolved.starts_with(&root) {
+        return Err(outside());
+    }
+
+    Ok(resolved)
+}
+
+/// Tool for reading a file from the project directory
+pub struct ReadFileTool {
+    /// Directory that all paths are resolved against
+    root: PathBuf,
+    /// Maximum file size in bytes
+    max_bytes: u64,
+}
+
+impl ReadFileTool {
+    /// Create a new read tool rooted at the current working directory
+    pub fn new() -> Self {
+        Self {
+            root: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            max_bytes: DEFAULT_MAX_FILE_BYTES,
+        }
+    }
+
+    /// Set the directory paths are resolved against
+    pub fn set_root(&mut self, root: impl Into<PathBuf>) {
+        self.root = root.into();
+    }
+
+    /// Set the maximum readable file size
+    pub fn set_max_bytes(&mut self, max_bytes: u64) {
+        self.max_bytes = max_bytes;
+    }
+
+    /// Execute the tool
+    pub fn execute(&self, tool_call: &ToolCall) -> Result<ToolResult> {
+        let path = tool_call.get_string("path").unwrap_or_default();
+        let resolved = resolve_path(&self.root, &path)?;
+
+        let metadata = fs::metadata(&resolved)
+            .map_err(|e| PraxisError::tool(format!("Cannot read '{}': {}", path, e)))?;
+
+        if !metadata.is_file() {
+            return Err(PraxisError::tool(format!("'{}' is not a file", path)));
+        }
+
+        if metadata.len() > self.max_bytes {
+            return Err(PraxisError::tool(format!(
+                "File '{}' is {} bytes, which exceeds the {} byte limit",
+                path,
+                metadata.len(),
+                self.max_bytes
+            )));
+        }
+
+        let content = fs::read_to_string(&resolved)
+            .map_err(|e| PraxisError::tool(format!("Cannot read '{}': {}", path, e)))?;
+
+        Ok(ToolResult::success("read_file", content))
+    }
+}
+
+impl Default for ReadFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tool for writing a file inside the project directory
+pub struct WriteFileTool {
+    /// Directory that all paths are resolved against
+    root: PathBuf,
+}
+
+impl WriteFileTool {
+    /// Create a new write tool rooted at the current working directory
+    pub fn new() -> Self {
+        Self {
+            root: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        }
+    }
+
+    /// Set the directory paths are resolved against
+    pub fn set_root(&mut self, root: impl Into<PathBuf>) {
+        self.root = root.into();
+    }
+
+    /// Execute the tool
+    pub fn execute(&self, tool_call: &ToolCall) -> Result<ToolResult> {
+        let path = tool_call.get_string("path").unwrap_or_default();
+        let content = tool_call.get_string("content").unwrap_or_default();
+        let resolved = resolve_path(&self.root, &path)?;
+
+        if let Some(parent) = resolved.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| PraxisError::tool(format!("Cannot create '{}': {}", path, e)))?;
+        }
+
+        fs::write(&resolved, &content)
+            .map_err(|e| PraxisError::tool(format!("Cannot write '{}': {}", path, e)))?;
+
+        Ok(ToolResult::success(
+            "write_file",
+            format!("Wrote {} bytes to {}", content.len(), path),
+        ))
+    }
+}
+
+impl Default for WriteFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tool for listing the files under a project directory
+///
+/// Skips hidden files and anything matched by `.gitignore`/`.ignore`.
+pub struct ListFilesTool {
+    /// Directory that all paths are resolved against
+    root: PathBuf,
+    /// Maximum number of paths returned
+    max_entries: usize,
+}
+
+impl ListFilesTool {
+    /// Create a new list tool rooted at the current working directory
+    pub fn new() -> Self {
+        Self {
+            root: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            max_entries: DEFAULT_MAX_LIST_ENTRIES,
+        }
+    }
+
+    /// Set the directory paths are resolved against
+    pub fn set_root(&mut self, root: impl Into<PathBuf>) {
+        self.root = root.into();
+    }
+
+    /// Set the maximum number of paths returned
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries;
+    }
+
+    /// Execute the tool
+    pub fn execute(&self, tool_call: &ToolCall) -> Result<ToolResult> {
+        let dir = tool_call
+            .get_string("dir")
+            .filter(|d| !d.trim().is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        let glob = tool_call
+            .get_string("glob")
+            .filter(|g| !g.trim().is_empty());
+        let resolved = resolve_path(&self.root, &dir)?;
+
+        if !resolved.is_dir() {
+            return Err(PraxisError::tool(format!("'{}' is not a directory", dir)));
+        }
+
+        let mut walker = WalkBuilder::new(&resolved);
+        walker.require_git(false).sort_by_file_name(|a, b| a.cmp(b));
+        if let Some(ref glob) = glob {
+            let overrides = OverrideBuilder::new(&resolved)
+                .add(glob)
+                .and_then(|b| b.build())
+                .map_err(|e| PraxisError::tool(format!("Invalid glob '{}': {}", glob, e)))?;
+            walker.overrides(overrides);
+        }
+
+        let mut lines = Vec::new();
+        let mut total = 0;
+        for entry in walker.build().filter_map(|e| e.ok()) {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            // With a glob, list matching files only; directories are just walked
+            if entry.depth() == 0 || (is_dir && glob.is_some()) {
+                continue;
+            }
+
+            total += 1;
+            if lines.len() < self.max_entries {
+                let name = entry.file_name().to_string_lossy();
+                let indent = if glob.is_some() {
+                    String::new()
+                } else {
+                    "  ".repeat(entry.depth() - 1)
+                };
+                let label = if glob.is_some() {
+                    entry
+                        .path()
+                        .strip_prefix(&resolved)
+                        .unwrap_or(entry.path())
+                        .display()
+                        .to_string()
+                } else if is_dir {
+                    format!("{}/", name)
+                } else {
+                    name.to_string()
+                };
+                lines.push(format!("{}{}", indent, label));
+            }
+        }
+
+        if lines.is_empty() {
+            return Ok(ToolResult::success(
+                "list_files",
+                format!("No files found in {}", dir),
+            ));
+        }
+        if total > lines.len() {
+            lines.push(format!(
+                "... and {} more (narrow it down with `dir` or `glob`)",
+                total - lines.len()
+            ));
+        }
+
+        Ok(ToolResult::success("list_files", lines.join("\n")))
+    }
+}
+
+impl Default for ListFilesTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let root = temp_root("praxis_fs_roundtrip");
+        let mut write = WriteFileTool::new();
+        write.set_root(&root);
+        let mut read = ReadFileTool::new();
+        read.set_root(&root);
+
+        let result = write
+            .execute(&ToolCall::new(
+                "write_file",
+                serde_json::json!({"path": "src/hello.txt", "content": "hi"}),
+            ))
+            .unwrap();
+        assert!(result.success);
+
+        let result = read
+            .execute(&ToolCall::new(
+                "read_file",
+                serde_json::json!({"path": "src/hello.txt"}),
+            ))
+            .unwrap();
+        assert_eq!(result.output, "hi");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_list_files_respects_gitignore_and_glob() {
+        let root = temp_root("praxis_fs_list");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::write(root.join("Cargo.toml"), "").unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("target/out.rs"), "").unwrap();
+
+        let mut list = ListFilesTool::new();
+        list.set_root(&root);
+
+        let tree = list
+            .execute(&ToolCall::new("list_files", serde_json::json!({})))
+            .unwrap();
+        assert_eq!(tree.output, "Cargo.toml\nsrc/\n  main.rs");
+
+        let matched = list
+            .execute(&ToolCall::new(
+                "list_files",
+                serde_json::json!({"glob": "*.rs"}),
+            ))
+            .unwrap();
+        assert_eq!(matched.output, "src/main.rs");
+
+        list.set_max_entries(1);
+        let capped = list
+            .execute(&ToolCall::new("list_files", serde_json::json!({})))
+            .unwrap();
+        assert!(capped.output.starts_with("Cargo.toml\n... and 2 more"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_path_traversal() {
+        let root = temp_root("praxis_fs_traversal");
+
+        assert!(resolve_path(&root, "../outside.txt").is_err());
+        assert!(resolve_path(&root, "a/../../outside.txt").is_err());
+        assert!(resolve_path(&root, "/etc/passwd").is_err());
+        assert!(resolve_path(&root, "a/../inside.txt").is_ok());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rejects_symlink_escape_below_missing_dirs() {
+        let root = temp_root("praxis_fs_symlink_escape");
+        let outside = temp_root("praxis_fs_symlink_outside");
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.join("gone"), root.join("dangling")).unwrap();
+
+        assert!(resolve_path(&root, "link/new/dir/file.txt").is_err());
+        assert!(resolve_path(&root, "link/file.txt").is_err());
+        assert!(resolve_path(&root, "dangling").is_err());
+        assert_eq!(
+            resolve_path(&root, "new/dir/file.txt").unwrap(),
+            root.canonicalize().unwrap().join("new/dir/file.txt")
+        );
+
+        fs::remove_dir_all(root).unwrap();
+        fs::remove_dir_all(outside).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_oversized_file() {
+        let root = temp_root("praxis_fs_oversized");
+        fs::write(root.join("big.txt"), "0123456789").unwrap();
+
+        let mut read = ReadFileTool::new();
+        read.set_root(&root);
+        read.set_max_bytes(5);
+
+        let result = read.execute(&ToolCall::new(
+            "read_file",
+            serde_json::json!({"path": "big.txt"}),
+        ));
+        assert!(matches!(result, Err(PraxisError::ToolExecution(_))));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,10 +1,12 @@
 //! Tools module - Tool implementations for the agent
 //!
-//! Contains coding tools, browser automation, and the tool registry.
+//! Contains coding tools, file system tools, browser automation, and the tool registry.
 
 pub mod browser;
 pub mod coding;
 pub mod context;
+pub mod fs;
 pub mod registry;
+pub mod system;
 
-pub use registry::ToolRegistry;
+pub use registry::{ToolFuture, ToolHandler, ToolRegistry};
@@ -3,11 +3,23 @@
 //! Central hub for registering tools and routing tool calls to handlers.
 
 use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
 
 use crate::core::{Result, ToolCall, ToolCategory, ToolDefinition, ToolResult};
 use crate::tools::browser::BrowserExecutor;
 use crate::tools::coding::{DebugTool, ExplainTool, WriteTool};
-use crate::tools::context::RecursiveContextTool;
+use crate::tools::context::{HistorySearchTool, RecursiveContextTool};
+use crate::tools::fs::{ListFilesTool, ReadFileTool, WriteFileTool};
+use crate::tools::system::{ConfirmRequest, RunCommandTool};
+
+/// Future returned by a custom tool handler
+pub type ToolFuture<'a> = Pin<Box<dyn Future<Output = Result<ToolResult>> + Send + 'a>>;
+
+/// Implementation of a tool added with [`ToolRegistry::register_handler`]
+pub type ToolHandler = Arc<dyn for<'a> Fn(&'a ToolCall) -> ToolFuture<'a> + Send + Sync>;
 
 /// Registry of available tools
 pub struct ToolRegistry {
@@ -15,6 +27,8 @@ pub struct ToolRegistry {
     definitions: HashMap<String, ToolDefinition>,
     /// Tool categories
     categories: HashMap<String, ToolCategory>,
+    /// Tools implemented outside the crate, by name
+    handlers: HashMap<String, ToolHandler>,
     /// Browser executor instance
     browser: Option<BrowserExecutor>,
     /// Coding tools
@@ -23,6 +37,13 @@ pub struct ToolRegistry {
     debug_tool: DebugTool,
     /// Context tools
     context_tool: RecursiveContextTool,
+    history_search_tool: HistorySearchTool,
+    /// File system tools
+    read_file_tool: ReadFileTool,
+    write_file_tool: WriteFileTool,
+    list_files_tool: ListFilesTool,
+    /// System tools (only registered when shell access is allowed)
+    run_command_tool: RunCommandTool,
 }
 
 impl ToolRegistry {
@@ -31,17 +52,25 @@ impl ToolRegistry {
         let mut registry = Self {
             definitions: HashMap::new(),
             categories: HashMap::new(),
+            handlers: HashMap::new(),
             browser: None,
             write_tool: WriteTool::new(),
             explain_tool: ExplainTool::new(),
             debug_tool: DebugTool::new(),
             context_tool: RecursiveContextTool::new(),
+            history_search_tool: HistorySearchTool::new(),
+            read_file_tool: ReadFileTool::new(),
+            write_file_tool: WriteFileTool::new(),
+            list_files_tool: ListFilesTool::new(),
+            run_command_tool: RunCommandTool::new(),
         };
 
         // Register coding tools
         registry.register_coding_tools();
         // Register context tools
         registry.register_context_tools();
+        // Register file system tools
+        registry.register_fs_tools();
 
         registry
     }
@@ -75,6 +104,18 @@ impl ToolRegistry {
                         "context": {
                             "type": "string",
                             "description": "Additional context or requirements"
+                        },
+                        "file_path": {
+                            "type": "string",
+                            "description": "Existing file to edit, relative to the project directory; the result is a unified diff"
+                        },
+                        "output_path": {
+                            "type": "string",
+                            "description": "Write the generated code to this file, relative to the project directory"
+                        },
+                        "overwrite": {
+                            "type": "boolean",
+                            "description": "Allow output_path to replace an existing file (default: false)"
                         }
                     },
                     "required": ["task", "language"]
@@ -157,6 +198,92 @@ impl ToolRegistry {
             ),
             ToolCategory::Context,
         );
+
+        self.register(
+            ToolDefinition::function(
+                "search_history",
+                "Find past messages containing a keyword (case-insensitive). Returns message indices and snippets; cheaper than analyze_conversation.",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "pattern": {
+                            "type": "string",
+                            "description": "Text to look for"
+                        }
+                    },
+                    "required": ["pattern"]
+                }),
+            ),
+            ToolCategory::Context,
+        );
+    }
+
+    /// Register file system tools
+    fn register_fs_tools(&mut self) {
+        // Read file tool
+        self.register(
+            ToolDefinition::function(
+                "read_file",
+                "Read the contents of a file in the project directory",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Path of the file, relative to the project directory"
+                        }
+                    },
+                    "required": ["path"]
+                }),
+            ),
+            ToolCategory::FileSystem,
+        );
+
+        // Write file tool
+        self.register(
+            ToolDefinition::function(
+                "write_file",
+                "Write content to a file in the project directory, creating it if needed",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Path of the file, relative to the project directory"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "Full content to write to the file"
+                        }
+                    },
+                    "required": ["path", "content"]
+                }),
+            ),
+            ToolCategory::FileSystem,
+        );
+
+        // List files tool
+        self.register(
+            ToolDefinition::function(
+                "list_files",
+                "List the files under a project directory as a tree, skipping anything in .gitignore",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "dir": {
+                            "type": "string",
+                            "description": "Directory to list, relative to the project directory (default: the project root)"
+                        },
+                        "glob": {
+                            "type": "string",
+                            "description": "Only list files matching this glob, e.g. '*.rs' or 'src/**/*.toml'"
+                        }
+                    },
+                    "required": []
+                }),
+            ),
+            ToolCategory::FileSystem,
+        );
     }
 
     /// Register browser automation tools
@@ -226,6 +353,116 @@ impl ToolRegistry {
             ToolCategory::Browser,
         );
 
+        // Select option
+        self.register(
+            ToolDefinition::function(
+                "browser_select",
+                "Choose an option in a dropdown (combobox) by its ref",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "ref": {
+                            "type": "string",
+                            "description": "Element ref of the dropdown from snapshot"
+                        },
+                        "value": {
+                            "type": "string",
+                            "description": "Option value or label to select"
+                        }
+                    },
+                    "required": ["ref", "value"]
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
+        // Fill and submit
+        self.register(
+            ToolDefinition::function(
+                "browser_submit",
+                "Type text into a field and press Enter (e.g. a search box). Returns a snapshot of the resulting page",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "ref": {
+                            "type": "string",
+                            "description": "Element ref from snapshot (e.g., 'e1')"
+                        },
+                        "text": {
+                            "type": "string",
+                            "description": "Text to enter before submitting"
+                        }
+                    },
+                    "required": ["ref", "text"]
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
+        // Hover
+        self.register(
+            ToolDefinition::function(
+                "browser_hover",
+                "Hover over an element to open a menu or tooltip. Returns an updated snapshot that includes the revealed elements",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "ref": {
+                            "type": "string",
+                            "description": "Element ref from snapshot (e.g., 'e1')"
+                        }
+                    },
+                    "required": ["ref"]
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
+        // Press a key
+        self.register(
+            ToolDefinition::function(
+                "browser_press",
+                "Press a key or key combo in the page, e.g. Escape to close a modal or Tab to move focus. Enter returns an updated snapshot",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "key": {
+                            "type": "string",
+                            "description": "Key name (Enter, Escape, Tab, ArrowDown) or combo (Control+A)"
+                        }
+                    },
+                    "required": ["key"]
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
+        // Wait
+        self.register(
+            ToolDefinition::function(
+                "browser_wait",
+                "Wait for an element or text to appear before taking a snapshot. Provide 'selector' or 'text'",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "selector": {
+                            "type": "string",
+                            "description": "Element ref or CSS selector to wait for"
+                        },
+                        "text": {
+                            "type": "string",
+                            "description": "Text to wait for"
+                        },
+                        "timeout_ms": {
+                            "type": "integer",
+                            "description": "How long to wait in milliseconds (optional)"
+                        }
+                    }
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
         // Get text
         self.register(
             ToolDefinition::function(
@@ -245,17 +482,53 @@ impl ToolRegistry {
             ToolCategory::Browser,
         );
 
+        // Get attribute
+        self.register(
+            ToolDefinition::function(
+                "browser_get_attribute",
+                "Get an attribute of an element, such as a link's href or an input's value",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "ref": {
+                            "type": "string",
+                            "description": "Element ref from snapshot"
+                        },
+                        "attribute": {
+                            "type": "string",
+                            "description": "Attribute name (e.g., 'href', 'value')"
+                        }
+                    },
+                    "required": ["ref", "attribute"]
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
+        // Extract links
+        self.register(
+            ToolDefinition::function(
+                "browser_extract_links",
+                "List the links on the current page as text and absolute URL, deduplicated by URL",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
         // Take screenshot
         self.register(
             ToolDefinition::function(
                 "browser_screenshot",
-                "Take a screenshot of the current page",
+                "Take a screenshot of the current page. With a vision model, the image is attached to your next turn",
                 serde_json::json!({
                     "type": "object",
                     "properties": {
                         "path": {
                             "type": "string",
-                            "description": "File path to save screenshot (optional)"
+                            "description": "File path to save screenshot (optional; defaults to .praxis/screenshots/<timestamp>.png)"
                         },
                         "full_page": {
                             "type": "boolean",
@@ -280,6 +553,38 @@ impl ToolRegistry {
             ToolCategory::Browser,
         );
 
+        // List tabs
+        self.register(
+            ToolDefinition::function(
+                "browser_list_tabs",
+                "List open browser tabs with their index, title and URL",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
+        // Switch tab
+        self.register(
+            ToolDefinition::function(
+                "browser_switch_tab",
+                "Switch to another open tab and get its page snapshot",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "index": {
+                            "type": "integer",
+                            "description": "Tab index from browser_list_tabs"
+                        }
+                    },
+                    "required": ["index"]
+                }),
+            ),
+            ToolCategory::Browser,
+        );
+
         // Get page snapshot
         self.register(
             ToolDefinition::function(
@@ -299,6 +604,52 @@ impl ToolRegistry {
         );
     }
 
+    /// Allow or forbid the `run_command` tool
+    ///
+    /// The tool is only offered to the model while allowed.
+    pub fn set_allow_shell(&mut self, allowed: bool) {
+        if !allowed {
+            self.definitions.remove("run_command");
+            self.categories.remove("run_command");
+            return;
+        }
+
+        self.register(
+            ToolDefinition::function(
+                "run_command",
+                "Run a program (e.g. cargo, pytest, npm) in the project directory and return its exit code and output. No shell: pass the program in 'command' and each argument in 'args'",
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "command": {
+                            "type": "string",
+                            "description": "Program to run, e.g. 'cargo'"
+                        },
+                        "args": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Arguments, e.g. [\"test\", \"--lib\"]"
+                        },
+                        "cwd": {
+                            "type": "string",
+                            "description": "Working directory relative to the project (optional)"
+                        }
+                    },
+                    "required": ["command"]
+                }),
+            ),
+            ToolCategory::System,
+        );
+    }
+
+    /// Ask for confirmation over `sender` before each `run_command`
+    pub fn set_command_confirmation(
+        &self,
+        sender: tokio::sync::mpsc::UnboundedSender<ConfirmRequest>,
+    ) {
+        self.run_command_tool.set_confirmation(sender);
+    }
+
     /// Register a tool definition
     pub fn register(&mut self, definition: ToolDefinition, category: ToolCategory) {
         let name = definition.function.name.clone();
@@ -306,11 +657,105 @@ impl ToolRegistry {
         self.categories.insert(name, category);
     }
 
+    /// Register a tool implemented by `handler`
+    ///
+    /// Custom tools run directly, whatever their category, and replace a
+    /// built-in tool of the same name.
+    ///
+    /// ```rust
+    /// use praxis::core::{ToolCategory, ToolDefinition, ToolResult};
+    /// use praxis::tools::ToolRegistry;
+    ///
+    /// let mut tools = ToolRegistry::new();
+    /// tools.register_handler(
+    ///     ToolDefinition::function(
+    ///         "shout",
+    ///         "Repeat text in capitals",
+    ///         serde_json::json!({
+    ///             "type": "object",
+    ///             "properties": {"text": {"type": "string"}},
+    ///             "required": ["text"]
+    ///         }),
+    ///     ),
+    ///     ToolCategory::Coding,
+    ///     |call| {
+    ///         Box::pin(async move {
+    ///             let text = call.get_string("text").unwrap_or_default();
+    ///             Ok(ToolResult::success("shout", text.to_uppercase()))
+    ///         })
+    ///     },
+    /// );
+    /// assert!(tools.has_handler("shout"));
+    /// ```
+    pub fn register_handler<F>(
+        &mut self,
+        definition: ToolDefinition,
+        category: ToolCategory,
+        handler: F,
+    ) where
+        F: for<'a> Fn(&'a ToolCall) -> ToolFuture<'a> + Send + Sync + 'static,
+    {
+        self.register_shared_handler(definition, category, Arc::new(handler));
+    }
+
+    /// `register_handler` with a handler that may be shared between registries
+    pub fn register_shared_handler(
+        &mut self,
+        definition: ToolDefinition,
+        category: ToolCategory,
+        handler: ToolHandler,
+    ) {
+        self.handlers
+            .insert(definition.function.name.clone(), handler);
+        self.register(definition, category);
+    }
+
+    /// Whether `name` is a tool added with `register_handler`
+    pub fn has_handler(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// Definitions of the tools added with `register_handler`, sorted by
+    /// name so the orchestrator sees them in a stable order
+    pub fn custom_tools(&self) -> Vec<&ToolDefinition> {
+        let mut names: Vec<&String> = self.handlers.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .filter_map(|name| self.definitions.get(name))
+            .collect()
+    }
+
     /// Get all tool definitions
     pub fn all_definitions(&self) -> Vec<&ToolDefinition> {
         self.definitions.values().collect()
     }
 
+    /// Check that a call names a registered tool and has its required arguments
+    ///
+    /// The error describes the problem so the model can correct the call.
+    pub fn validate_call(&self, tool_call: &ToolCall) -> std::result::Result<(), String> {
+        let Some(definition) = self.definitions.get(&tool_call.name) else {
+            return Err(format!("unknown tool '{}'", tool_call.name));
+        };
+
+        let Some(arguments) = tool_call.arguments.as_object() else {
+            return Err("arguments must be a JSON object".to_string());
+        };
+
+        let missing = definition.function.parameters["required"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|field| field.as_str())
+            .find(|field| !arguments.contains_key(*field));
+
+        match missing {
+            Some(field) => Err(format!("missing required argument '{}'", field)),
+            None => Ok(()),
+        }
+    }
+
     /// Get tool definitions by category
     pub fn definitions_by_category(&self, category: ToolCategory) -> Vec<&ToolDefinition> {
         self.definitions
@@ -335,11 +780,67 @@ impl ToolRegistry {
         self.definitions_by_category(ToolCategory::Browser)
     }
 
+    /// Get file system tool definitions
+    pub fn fs_tools(&self) -> Vec<&ToolDefinition> {
+        self.definitions_by_category(ToolCategory::FileSystem)
+    }
+
+    /// Get the category of a registered tool
+    pub fn category(&self, name: &str) -> Option<ToolCategory> {
+        self.categories.get(name).copied()
+    }
+
+    /// Set the directory file system tools are confined to
+    pub fn set_fs_root(&mut self, root: impl Into<PathBuf>) {
+        let root = root.into();
+        self.read_file_tool.set_root(root.clone());
+        self.write_file_tool.set_root(root.clone());
+        self.list_files_tool.set_root(root.clone());
+        self.write_tool.set_root(root.clone());
+        self.run_command_tool.set_root(root);
+    }
+
+    /// Set the project root: file tools are confined to it, shell commands
+    /// run in it and screenshots are saved under it
+    pub fn set_workdir(&mut self, root: impl Into<PathBuf>) {
+        let root = root.into();
+        if let Some(browser) = self.browser.as_mut() {
+            browser.set_workdir(root.clone());
+        }
+        self.set_fs_root(root);
+    }
+
+    /// Set the maximum file size `read_file` will return
+    pub fn set_max_file_bytes(&mut self, max_bytes: u64) {
+        self.read_file_tool.set_max_bytes(max_bytes);
+    }
+
     /// Check if browser is enabled
     pub fn has_browser(&self) -> bool {
         self.browser.is_some()
     }
 
+    /// Set the timeout for each browser command
+    pub fn set_browser_timeout(&mut self, timeout: std::time::Duration) {
+        if let Some(browser) = self.browser.as_mut() {
+            browser.set_timeout(timeout);
+        }
+    }
+
+    /// Show snapshot diffs instead of full snapshots after browser actions
+    pub fn set_snapshot_diffs(&mut self, enabled: bool) {
+        if let Some(browser) = self.browser.as_mut() {
+            browser.set_snapshot_diffs(enabled);
+        }
+    }
+
+    /// Set the most links `browser_extract_links` returns
+    pub fn set_max_links(&mut self, max_links: usize) {
+        if let Some(browser) = self.browser.as_mut() {
+            browser.set_max_links(max_links);
+        }
+    }
+
     /// Get the browser executor
     pub fn browser_executor(&self) -> Option<&BrowserExecutor> {
         self.browser.as_ref()
@@ -352,11 +853,17 @@ impl ToolRegistry {
 
     /// Execute a tool call
     pub async fn execute(&self, tool_call: &ToolCall) -> Result<ToolResult> {
+        if let Some(handler) = self.handlers.get(&tool_call.name) {
+            return handler(tool_call).await;
+        }
+
         let category = self.categories.get(&tool_call.name);
 
         match category {
             Some(ToolCategory::Coding) => self.execute_coding_tool(tool_call).await,
             Some(ToolCategory::Browser) => self.execute_browser_tool(tool_call).await,
+            Some(ToolCategory::FileSystem) => self.execute_fs_tool(tool_call).await,
+            Some(ToolCategory::System) => self.execute_system_tool(tool_call).await,
             _ => Ok(ToolResult::failure(
                 &tool_call.name,
                 format!("Unknown tool: {}", tool_call.name),
@@ -377,6 +884,30 @@ impl ToolRegistry {
         }
     }
 
+    /// Execute a file system tool
+    async fn execute_fs_tool(&self, tool_call: &ToolCall) -> Result<ToolResult> {
+        match tool_call.name.as_str() {
+            "read_file" => self.read_file_tool.execute(tool_call),
+            "write_file" => self.write_file_tool.execute(tool_call),
+            "list_files" => self.list_files_tool.execute(tool_call),
+            _ => Ok(ToolResult::failure(
+                &tool_call.name,
+                format!("Unknown file system tool: {}", tool_call.name),
+            )),
+        }
+    }
+
+    /// Execute a system tool
+    async fn execute_system_tool(&self, tool_call: &ToolCall) -> Result<ToolResult> {
+        match tool_call.name.as_str() {
+            "run_command" => self.run_command_tool.execute(tool_call).await,
+            _ => Ok(ToolResult::failure(
+                &tool_call.name,
+                format!("Unknown system tool: {}", tool_call.name),
+            )),
+        }
+    }
+
     /// Execute a browser tool
     async fn execute_browser_tool(&self, tool_call: &ToolCall) -> Result<ToolResult> {
         let browser = match &self.browser {
@@ -389,25 +920,60 @@ impl ToolRegistry {
             }
         };
 
-        match tool_call.name.as_str() {
+        // Models often pass a description or URL instead of a ref; map it to
+        // a ref from the last snapshot or explain which refs are valid
+        let mut resolved_from = None;
+        let ref_id = match tool_call.get_string("ref") {
+            Some(given) => match browser.resolve_ref(&given) {
+                Ok(ref_id) => {
+                    if ref_id != given.trim() {
+                        resolved_from = Some(given);
+                    }
+                    ref_id
+                }
+                Err(e) => return Ok(ToolResult::failure(&tool_call.name, e)),
+            },
+            None => String::new(),
+        };
+
+        let result = match tool_call.name.as_str() {
             "browser_url" => {
                 let url = tool_call.get_string("url").unwrap_or_default();
                 let wait = tool_call.get_bool("wait_for_load").unwrap_or(true);
                 browser.open(&url, wait).await
             }
-            "browser_click" => {
-                let ref_id = tool_call.get_string("ref").unwrap_or_default();
-                browser.click(&ref_id).await
-            }
+            "browser_click" => browser.click(&ref_id).await,
             "browser_fill" => {
-                let ref_id = tool_call.get_string("ref").unwrap_or_default();
                 let text = tool_call.get_string("text").unwrap_or_default();
                 browser.fill(&ref_id, &text).await
             }
-            "browser_get_text" => {
-                let ref_id = tool_call.get_string("ref").unwrap_or_default();
-                browser.get_text(&ref_id).await
+            "browser_submit" => {
+                let text = tool_call.get_string("text").unwrap_or_default();
+                browser.fill_and_submit(&ref_id, &text).await
+            }
+            "browser_select" => {
+                let value = tool_call.get_string("value").unwrap_or_default();
+                browser.select(&ref_id, &value).await
+            }
+            "browser_wait" => {
+                let selector = tool_call.get_string("selector");
+                let text = tool_call.get_string("text");
+                let timeout_ms = tool_call.get_u64("timeout_ms");
+                browser
+                    .wait(selector.as_deref(), text.as_deref(), timeout_ms)
+                    .await
+            }
+            "browser_hover" => browser.hover(&ref_id).await,
+            "browser_press" => {
+                let key = tool_call.get_string("key").unwrap_or_default();
+                browser.press(&key).await
+            }
+            "browser_get_text" => browser.get_text(&ref_id).await,
+            "browser_get_attribute" => {
+                let attribute = tool_call.get_string("attribute").unwrap_or_default();
+                browser.get_attribute(&ref_id, &attribute).await
             }
+            "browser_extract_links" => browser.extract_links().await,
             "browser_screenshot" => {
                 let path = tool_call.get_string("path");
                 let full = tool_call.get_bool("full_page").unwrap_or(false);
@@ -418,20 +984,46 @@ impl ToolRegistry {
                 browser.snapshot(interactive).await
             }
             "browser_close" => browser.close().await,
+            "browser_list_tabs" => browser.list_tabs().await,
+            "browser_switch_tab" => match tool_call.get_u64("index") {
+                Some(index) => browser.switch_tab(index as usize).await,
+                None => Ok(ToolResult::failure(
+                    &tool_call.name,
+                    "Missing or invalid 'index' argument",
+                )),
+            },
             _ => Ok(ToolResult::failure(
                 &tool_call.name,
                 format!("Unknown browser tool: {}", tool_call.name),
             )),
+        };
+
+        match (result, resolved_from) {
+            (Ok(mut result), Some(given)) => {
+                result.output =
+                    format!("(Using ref {} for '{}')\n{}", ref_id, given, result.output);
+                Ok(result)
+            }
+            (result, _) => result,
         }
     }
 
     /// Get a prompt for a coding tool (for the executor model)
-    pub fn build_coding_prompt(&self, tool_call: &ToolCall) -> String {
-        match tool_call.name.as_str() {
-            "write_code" => self.write_tool.build_prompt(tool_call),
+    pub fn build_coding_prompt(&self, tool_call: &ToolCall) -> Result<String> {
+        Ok(match tool_call.name.as_str() {
+            "write_code" => self.write_tool.build_prompt(tool_call)?,
             "explain_code" => self.explain_tool.build_prompt(tool_call),
             "debug_code" => self.debug_tool.build_prompt(tool_call),
             _ => format!("Execute tool: {}", tool_call.name),
+        })
+    }
+
+    /// Turn the executor's response to a coding prompt into the tool output
+    /// (a diff, for `write_code` edits)
+    pub fn finish_coding_output(&self, tool_call: &ToolCall, response: String) -> Result<String> {
+        match tool_call.name.as_str() {
+            "write_code" => self.write_tool.finish(tool_call, response),
+            _ => Ok(response),
         }
     }
 
@@ -439,6 +1031,11 @@ impl ToolRegistry {
     pub fn context_tool(&self) -> &RecursiveContextTool {
         &self.context_tool
     }
+
+    /// Get the history search tool
+    pub fn history_search_tool(&self) -> &HistorySearchTool {
+        &self.history_search_tool
+    }
 }
 
 impl Default for ToolRegistry {
@@ -446,3 +1043,28 @@ impl Default for ToolRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_call() {
+        let registry = ToolRegistry::with_browser("test");
+
+        assert!(registry
+            .validate_call(&ToolCall::new(
+                "browser_url",
+                serde_json::json!({"url": "https://example.com"})
+            ))
+            .is_ok());
+        assert_eq!(
+            registry.validate_call(&ToolCall::new("browser_url", serde_json::json!({}))),
+            Err("missing required argument 'url'".to_string())
+        );
+        assert_eq!(
+            registry.validate_call(&ToolCall::new("browser_teleport", serde_json::json!({}))),
+            Err("unknown tool 'browser_teleport'".to_string())
+        );
+    }
+}
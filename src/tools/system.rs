@@ -0,0 +1,272 @@
+//! System tools
+//!
+//! Run programs such as `cargo build` or `pytest` in the project directory.
+//! Commands are executed directly, never through a shell, and only when
+//! shell access has been enabled in the config.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::process::Command;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::core::{PraxisError, Result, ToolCall, ToolResult};
+use crate::tools::fs::resolve_path;
+
+/// Programs that would run their arguments as a script, defeating the
+/// no-shell rule
+const SHELLS: &[&str] = &[
+    "sh",
+    "bash",
+    "zsh",
+    "fish",
+    "dash",
+    "ksh",
+    "csh",
+    "cmd",
+    "cmd.exe",
+    "powershell",
+    "pwsh",
+];
+
+/// Default time a command may run before it's killed (5 minutes)
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// A request for the user to approve a command before it runs
+pub struct ConfirmRequest {
+    /// The command line, for display
+    pub command: String,
+    /// Send `true` to run the command, `false` to decline
+    pub reply: oneshot::Sender<bool>,
+}
+
+/// Tool for running a program in the project directory
+pub struct RunCommandTool {
+    /// Directory that `cwd` is resolved against, and the default working directory
+    root: PathBuf,
+    /// Maximum run time before the process is killed
+    timeout: Duration,
+    /// Where to ask for confirmation; commands run unconfirmed when unset
+    confirm: Mutex<Option<mpsc::UnboundedSender<ConfirmRequest>>>,
+}
+
+impl RunCommandTool {
+    /// Create a new tool rooted at the current working directory
+    pub fn new() -> Self {
+        Self {
+            root: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            timeout: DEFAULT_TIMEOUT,
+            confirm: Mutex::new(None),
+        }
+    }
+
+    /// Set the project directory
+    pub fn set_root(&mut self, root: impl Into<PathBuf>) {
+        self.root = root.into();
+    }
+
+    /// Ask for confirmation over `sender` before running each command
+    pub fn set_confirmation(&self, sender: mpsc::UnboundedSender<ConfirmRequest>) {
+        if let Ok(mut confirm) = self.confirm.lock() {
+            *confirm = Some(sender);
+        }
+    }
+
+    /// Execute the tool
+    pub async fn execute(&self, tool_call: &ToolCall) -> Result<ToolResult> {
+        let command = tool_call.get_string("command").unwrap_or_default();
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(PraxisError::tool("Missing required argument 'command'"));
+        }
+
+        let args: Vec<String> = match tool_call.arguments.get("args") {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .map(|item| match item {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect(),
+            Some(_) => {
+                return Err(PraxisError::tool(
+                    "'args' must be an array of strings, e.g. [\"build\", \"--release\"]",
+                ))
+            }
+        };
+
+        if command.contains(char::is_whitespace) {
+            return Err(PraxisError::tool(format!(
+                "'command' must be a single program name; pass arguments in 'args' (got '{}')",
+                command
+            )));
+        }
+
+        let program = command.rsplit(['/', '\\']).next().unwrap_or(command);
+        if SHELLS.contains(&program.to_lowercase().as_str()) {
+            return Err(PraxisError::tool(format!(
+                "Running a shell ('{}') is not allowed; run the program directly",
+                command
+            )));
+        }
+
+        let cwd = match tool_call.get_string("cwd") {
+            Some(cwd) if !cwd.trim().is_empty() => resolve_path(&self.root, &cwd)?,
+            _ => self.root.clone(),
+        };
+
+        let command_line = std::iter::once(command.to_string())
+            .chain(args.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if !self.confirmed(&command_line).await {
+            return Ok(ToolResult::failure(
+                "run_command",
+                format!("The user declined to run `{}`", command_line),
+            ));
+        }
+
+        let child = Command::new(command)
+            .args(&args)
+            .current_dir(&cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| PraxisError::tool(format!("Failed to run '{}': {}", command, e)))?;
+
+        let output = match tokio::time::timeout(self.timeout, child.wait_with_output()).await {
+            Ok(output) => output
+                .map_err(|e| PraxisError::tool(format!("Failed to run '{}': {}", command, e)))?,
+            // Dropping the child kills it
+            Err(_) => {
+                return Ok(ToolResult::failure(
+                    "run_command",
+                    format!(
+                        "`{}` did not finish within {}s and was killed",
+                        command_line,
+                        self.timeout.as_secs()
+                    ),
+                ))
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        let exit_code = output.status.code();
+
+        let text = format!(
+            "`{}` exited with {}\nstdout:\n{}\nstderr:\n{}",
+            command_line,
+            exit_code.map_or_else(|| "a signal".to_string(), |c| format!("code {}", c)),
+            stdout,
+            stderr
+        );
+
+        Ok(ToolResult {
+            tool_name: "run_command".to_string(),
+            success: output.status.success(),
+            output: text,
+            data: Some(serde_json::json!({
+                "exit_code": exit_code,
+                "stdout": stdout,
+                "stderr": stderr,
+            })),
+        })
+    }
+
+    /// Ask the user to approve a command, if confirmation is configured
+    async fn confirmed(&self, command_line: &str) -> bool {
+        let sender = self.confirm.lock().ok().and_then(|c| c.clone());
+        let Some(sender) = sender else {
+            return true;
+        };
+
+        let (reply, response) = oneshot::channel();
+        let request = ConfirmRequest {
+            command: command_line.to_string(),
+            reply,
+        };
+        if sender.send(request).is_err() {
+            return false;
+        }
+        response.await.unwrap_or(false)
+    }
+}
+
+impl Default for RunCommandTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(args: serde_json::Value) -> ToolCall {
+        ToolCall::new("run_command", args)
+    }
+
+    #[tokio::test]
+    async fn test_runs_program_and_captures_output() {
+        let tool = RunCommandTool::new();
+        let result = tool
+            .execute(&call(
+                serde_json::json!({"command": "echo", "args": ["hello", "world"]}),
+            ))
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result.output.contains("hello world"));
+        assert_eq!(result.data.unwrap()["exit_code"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_shells_and_inline_args() {
+        let tool = RunCommandTool::new();
+
+        let err = tool
+            .execute(&call(
+                serde_json::json!({"command": "/bin/sh", "args": ["-c", "ls"]}),
+            ))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not allowed"));
+
+        let err = tool
+            .execute(&call(serde_json::json!({"command": "cargo build"})))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("pass arguments in 'args'"));
+    }
+
+    #[tokio::test]
+    async fn test_declined_command_does_not_run() {
+        let tool = RunCommandTool::new();
+        let (tx, mut rx) = mpsc::unbounded_channel::<ConfirmRequest>();
+        tool.set_confirmation(tx);
+
+        tokio::spawn(async move {
+            while let Some(request) = rx.recv().await {
+                assert_eq!(request.command, "echo hi");
+                let _ = request.reply.send(false);
+            }
+        });
+
+        let result = tool
+            .execute(&call(
+                serde_json::json!({"command": "echo", "args": ["hi"]}),
+            ))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("declined"));
+    }
+}
@@ -144,3 +144,25 @@ async fn test_ref_usage_from_snapshot() {
     // We mainly want to verify the agent doesn't error out
     let _ = result.unwrap();
 }
+
+/// Test that a second attach reuses the page the first one opened
+#[tokio::test]
+#[ignore] // Requires agent-browser to be installed
+async fn test_attach_reuses_open_session() {
+    use praxis::tools::browser::{BrowserExecutor, SessionStart};
+
+    let session = "praxis-attach-test";
+    let (first, _) = match BrowserExecutor::attach(session).await {
+        Ok(attached) => attached,
+        Err(e) => {
+            eprintln!("Skipping test: {}", e);
+            return;
+        }
+    };
+    first.open("https://example.com", true).await.unwrap();
+
+    let (second, start) = BrowserExecutor::attach(session).await.unwrap();
+    assert!(matches!(start, SessionStart::Reused(ref url) if url.contains("example.com")));
+
+    let _ = second.close().await;
+}
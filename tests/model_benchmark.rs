@@ -73,7 +73,7 @@ impl ModelBenchmark {
         // Initialize with timeout
         let init_result = timeout(Duration::from_secs(30), agent.initialize()).await;
 
-        if let Err(_) = init_result {
+        if init_result.is_err() {
             return BenchmarkResult {
                 model: model.to_string(),
                 task: task.to_string(),